@@ -0,0 +1,40 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A dry run of the fees charged on a transaction, for wallets to warn users beforehand.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct FeeQuote<AccountId, CurrencyId, Balance> {
+	/// The inclusion fee, without the tip.
+	pub base_fee: Balance,
+	pub tip: Balance,
+	/// The currency and the amount the base fee and the tip are paid in, `None` if there is no
+	/// fee or the payer can't afford it.
+	pub fee: Option<(CurrencyId, Balance)>,
+	/// The sponsor paying the base fee and the tip for the payer, if any.
+	pub sponsor: Option<AccountId>,
+	/// The currency and the amount of the extra fee, if the call has one.
+	pub extra_fee: Option<(CurrencyId, Balance)>,
+	/// The free balances of the payer after all the fees are charged.
+	pub balances_after: Vec<(CurrencyId, Balance)>,
+	/// Whether the payer can afford all the fees.
+	pub payable: bool,
+}
@@ -20,7 +20,6 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_core::{Decode, Encode, RuntimeDebug};
 use sp_runtime::{
 	generic,
 	traits::{BlakeTwo256, IdentifyAccount, Verify},
@@ -29,10 +28,12 @@ use sp_runtime::{
 
 mod bridge;
 mod currency;
+mod fee;
 mod salp;
 mod tests;
 pub mod traits;
 mod xcm;
+pub use fee::*;
 pub use salp::*;
 
 pub use crate::{
@@ -129,10 +130,5 @@ pub type LeasePeriod = BlockNumber;
 /// Index used for the child trie
 pub type TrieIndex = u32;
 
-#[derive(
-	Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord, scale_info::TypeInfo,
-)]
-pub enum ExtraFeeName {
-	SalpContribute,
-	NoExtraFee,
-}
+/// The most days the token ceiling of a bancor pool can be forecast for.
+pub const MAX_FORECAST_DAYS: u32 = 365;
@@ -33,13 +33,22 @@
 
 use std::sync::Arc;
 
+use bifrost_bancor_rpc::{BancorRpcApi, BancorStruct};
+use bifrost_bancor_runtime_api::BancorRuntimeApi;
 use bifrost_flexible_fee_rpc::{FeeRpcApi, FlexibleFeeStruct};
 use bifrost_flexible_fee_rpc_runtime_api::FlexibleFeeRuntimeApi as FeeRuntimeApi;
+use bifrost_lightening_redeem_rpc::{LighteningRedeem, LighteningRedeemApi};
+use bifrost_lightening_redeem_rpc_runtime_api::LighteningRedeemRuntimeApi;
 use bifrost_liquidity_mining_rpc_api::{LiquidityMiningRpcApi, LiquidityMiningRpcWrapper};
 use bifrost_liquidity_mining_rpc_runtime_api::LiquidityMiningRuntimeApi;
 use bifrost_salp_rpc_api::{SalpRpcApi, SalpRpcWrapper};
 use bifrost_salp_rpc_runtime_api::SalpRuntimeApi;
-use node_primitives::{AccountId, Balance, Block, Nonce, ParaId, PoolId};
+use bifrost_vtoken_mint_rpc::{VtokenMint, VtokenMintPriceApi};
+use bifrost_vtoken_mint_rpc_runtime_api::VtokenMintPriceApi as VtokenMintRuntimeApi;
+use node_primitives::{
+	AccountId, Balance, Block, BlockNumber, CurrencyId, LeasePeriod, Nonce, ParaId, PoolId,
+	TokenSymbol,
+};
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
@@ -50,6 +59,9 @@ use substrate_frame_rpc_system::{FullSystem, SystemApi};
 use zenlink_protocol_rpc::{ZenlinkProtocol, ZenlinkProtocolApi};
 use zenlink_protocol_runtime_api::ZenlinkProtocolApi as ZenlinkProtocolRuntimeApi;
 
+/// The pool of lightening redeem, as `(relay token, ParaId, first_slot, last_slot)`.
+pub type RedeemPoolId = (TokenSymbol, ParaId, LeasePeriod, LeasePeriod);
+
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
 	/// The client instance to use.
@@ -79,6 +91,8 @@ where
 	C::Api: SalpRuntimeApi<Block, ParaId, AccountId>,
 	C::Api: LiquidityMiningRuntimeApi<Block, AccountId, PoolId>,
 	C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId>,
+	C::Api: BancorRuntimeApi<Block, CurrencyId, Balance>,
+	C::Api: LighteningRedeemRuntimeApi<Block, RedeemPoolId, BlockNumber, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
@@ -98,6 +112,10 @@ where
 
 	io.extend_with(ZenlinkProtocolApi::to_delegate(ZenlinkProtocol::new(client.clone())));
 
+	io.extend_with(BancorRpcApi::to_delegate(BancorStruct::new(client.clone())));
+
+	io.extend_with(LighteningRedeemApi::to_delegate(LighteningRedeem::new(client.clone())));
+
 	io
 }
 
@@ -116,6 +134,9 @@ where
 	C::Api: SalpRuntimeApi<Block, ParaId, AccountId>,
 	C::Api: LiquidityMiningRuntimeApi<Block, AccountId, PoolId>,
 	C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId>,
+	C::Api: BancorRuntimeApi<Block, CurrencyId, Balance>,
+	C::Api: VtokenMintRuntimeApi<Block, CurrencyId, BlockNumber, Balance>,
+	C::Api: LighteningRedeemRuntimeApi<Block, RedeemPoolId, BlockNumber, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
@@ -135,5 +156,11 @@ where
 
 	io.extend_with(ZenlinkProtocolApi::to_delegate(ZenlinkProtocol::new(client.clone())));
 
+	io.extend_with(BancorRpcApi::to_delegate(BancorStruct::new(client.clone())));
+
+	io.extend_with(VtokenMintPriceApi::to_delegate(VtokenMint::new(client.clone())));
+
+	io.extend_with(LighteningRedeemApi::to_delegate(LighteningRedeem::new(client.clone())));
+
 	io
 }
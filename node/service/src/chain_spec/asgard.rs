@@ -19,9 +19,10 @@
 use asgard_runtime::{
 	constants::{currency::DOLLARS, time::DAYS},
 	AccountId, AuraId, Balance, BalancesConfig, BancorConfig, BlockNumber, CollatorSelectionConfig,
-	CouncilConfig, DemocracyConfig, GenesisConfig, IndicesConfig, MinterRewardConfig,
-	ParachainInfoConfig, PolkadotXcmConfig, SessionConfig, SudoConfig, SystemConfig,
-	TechnicalCommitteeConfig, TokensConfig, VestingConfig, VtokenMintConfig, WASM_BINARY,
+	CouncilConfig, DefaultExtraFees, DemocracyConfig, FlexibleFeeConfig, GenesisConfig,
+	IndicesConfig, MinterRewardConfig, ParachainInfoConfig, PolkadotXcmConfig, SessionConfig,
+	SudoConfig, SystemConfig, TechnicalCommitteeConfig, TokensConfig, VestingConfig,
+	VtokenMintConfig, WASM_BINARY,
 };
 use cumulus_primitives_core::ParaId;
 use frame_benchmarking::{account, whitelisted_caller};
@@ -30,7 +31,7 @@ use node_primitives::{CurrencyId, TokenSymbol};
 use sc_service::ChainType;
 use sc_telemetry::TelemetryEndpoints;
 use sp_core::{crypto::UncheckedInto, sr25519};
-use sp_runtime::traits::Zero;
+use sp_runtime::traits::{Get, Zero};
 
 use super::TELEMETRY_URL;
 use crate::chain_spec::{get_account_id_from_seed, get_from_seed, RelayExtensions};
@@ -93,6 +94,10 @@ pub fn asgard_genesis(
 		parachain_system: Default::default(),
 		vesting: VestingConfig { vesting: vestings },
 		tokens: TokensConfig { balances: tokens },
+		flexible_fee: FlexibleFeeConfig {
+			fee_currency_exchange_rates: vec![(CurrencyId::Token(TokenSymbol::KSM), (1, 100))],
+			extra_fees: DefaultExtraFees::get(),
+		},
 		bancor: BancorConfig {
 			bancor_pools: vec![
 				(CurrencyId::Token(TokenSymbol::DOT), 10_000 * DOLLARS),
@@ -22,11 +22,11 @@ use std::{
 };
 
 use bifrost_runtime::{
-	constants::currency::DOLLARS, AccountId, AuraId, Balance, BalancesConfig, BlockNumber,
-	CollatorSelectionConfig, CouncilConfig, CouncilMembershipConfig, DemocracyConfig,
-	GenesisConfig, IndicesConfig, ParachainInfoConfig, PolkadotXcmConfig, SessionConfig,
-	SystemConfig, TechnicalCommitteeConfig, TechnicalMembershipConfig, TokensConfig, VestingConfig,
-	WASM_BINARY,
+	constants::currency::DOLLARS, AccountId, AuraId, Balance, BalancesConfig, BancorConfig,
+	BlockNumber, CollatorSelectionConfig, CouncilConfig, CouncilMembershipConfig,
+	DefaultBancorPools, DefaultExtraFees, DemocracyConfig, FlexibleFeeConfig, GenesisConfig,
+	IndicesConfig, ParachainInfoConfig, PolkadotXcmConfig, SessionConfig, SystemConfig,
+	TechnicalCommitteeConfig, TechnicalMembershipConfig, TokensConfig, VestingConfig, WASM_BINARY,
 };
 use cumulus_primitives_core::ParaId;
 use frame_benchmarking::{account, whitelisted_caller};
@@ -37,7 +37,7 @@ use sc_telemetry::TelemetryEndpoints;
 use serde::de::DeserializeOwned;
 use serde_json as json;
 use sp_core::{crypto::UncheckedInto, sr25519};
-use sp_runtime::traits::Zero;
+use sp_runtime::traits::{Get, Zero};
 
 use super::TELEMETRY_URL;
 use crate::chain_spec::{get_account_id_from_seed, get_from_seed, RelayExtensions};
@@ -105,6 +105,11 @@ pub fn bifrost_genesis(
 		parachain_system: Default::default(),
 		vesting: VestingConfig { vesting: vestings },
 		tokens: TokensConfig { balances: tokens },
+		flexible_fee: FlexibleFeeConfig {
+			fee_currency_exchange_rates: vec![(CurrencyId::Token(TokenSymbol::KSM), (1, 100))],
+			extra_fees: DefaultExtraFees::get(),
+		},
+		bancor: BancorConfig { bancor_pools: DefaultBancorPools::get() },
 		polkadot_xcm: PolkadotXcmConfig { safe_xcm_version: Some(2) },
 	}
 }
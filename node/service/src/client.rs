@@ -21,7 +21,8 @@
 use std::sync::Arc;
 
 use node_primitives::{
-	AccountId, Balance, Block, BlockNumber, Hash, Header, Nonce, ParaId as BifrostParaId, PoolId,
+	AccountId, Balance, Block, BlockNumber, CurrencyId, Hash, Header, Nonce,
+	ParaId as BifrostParaId, PoolId,
 };
 use sc_client_api::{AuxStore, Backend as BackendT, BlockchainEvents, KeyIterator, UsageProvider};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
@@ -34,7 +35,7 @@ use sp_runtime::{
 };
 use sp_storage::{ChildInfo, PrefixedStorageKey, StorageData, StorageKey};
 
-use crate::{FullBackend, FullClient};
+use crate::{rpc::RedeemPoolId, FullBackend, FullClient};
 
 /// A set of APIs that polkadot-like runtimes must implement.
 pub trait RuntimeApiCollection:
@@ -51,6 +52,14 @@ pub trait RuntimeApiCollection:
 	+ bifrost_liquidity_mining_rpc_runtime_api::LiquidityMiningRuntimeApi<Block, AccountId, PoolId>
 	+ bifrost_salp_rpc_runtime_api::SalpRuntimeApi<Block, BifrostParaId, AccountId>
 	+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId>
+	+ bifrost_bancor_runtime_api::BancorRuntimeApi<Block, CurrencyId, Balance>
+	+ bifrost_vtoken_mint_rpc_runtime_api::VtokenMintPriceApi<Block, CurrencyId, BlockNumber, Balance>
+	+ bifrost_lightening_redeem_rpc_runtime_api::LighteningRedeemRuntimeApi<
+		Block,
+		RedeemPoolId,
+		BlockNumber,
+		Balance,
+	>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -74,7 +83,19 @@ where
 			AccountId,
 			PoolId,
 		> + bifrost_salp_rpc_runtime_api::SalpRuntimeApi<Block, BifrostParaId, AccountId>
-		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId>,
+		+ zenlink_protocol_runtime_api::ZenlinkProtocolApi<Block, AccountId>
+		+ bifrost_bancor_runtime_api::BancorRuntimeApi<Block, CurrencyId, Balance>
+		+ bifrost_vtoken_mint_rpc_runtime_api::VtokenMintPriceApi<
+			Block,
+			CurrencyId,
+			BlockNumber,
+			Balance,
+		> + bifrost_lightening_redeem_rpc_runtime_api::LighteningRedeemRuntimeApi<
+			Block,
+			RedeemPoolId,
+			BlockNumber,
+			Balance,
+		>,
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
@@ -20,6 +20,7 @@
 
 use codec::Codec;
 use sp_api::decl_runtime_apis;
+use sp_std::vec::Vec;
 
 decl_runtime_apis! {
 	pub trait BancorRuntimeApi<CurrencyId, Balance> where
@@ -37,5 +38,9 @@ decl_runtime_apis! {
 
 		// get unit token price in the form of vstoken presentation.
 		fn get_instant_token_price(currency_id: CurrencyId) -> (Balance, Balance);
+
+		/// get the forecast of the token ceiling at the end of each of the next `days` days, at
+		/// most `MAX_FORECAST_DAYS` days.
+		fn get_token_ceiling_forecast(currency_id: CurrencyId, days: u32) -> Vec<Balance>;
 	}
 }
@@ -18,19 +18,17 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use codec::{Codec, Decode};
+pub use bifrost_bancor_runtime_api::{self as runtime_api, BancorRuntimeApi};
+use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result as JsonRpcResult};
 use jsonrpc_derive::rpc;
-use node_primitives::{Balance, CurrencyId};
+use node_primitives::{Balance, MAX_FORECAST_DAYS};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
-use sp_runtime::{
-	generic::BlockId,
-	traits::{Block as BlockT, Zero},
-	SaturatedConversion,
-};
+use sp_runtime::{generic::BlockId, sp_std::convert::TryInto, traits::Block as BlockT};
+
+pub use self::gen_client::Client as BancorClient;
 
 #[derive(Clone, Debug)]
 pub struct BancorStruct<C, Block> {
@@ -45,140 +43,178 @@ impl<C, Block> BancorStruct<C, Block> {
 }
 
 #[rpc]
-pub trait BancorRpcApi<BlockHash, CurrencyId, Balance> {
+pub trait BancorRpcApi<BlockHash, CurrencyId> {
+	/// rpc method for getting the amount of token exchanged out by `vstoken_amount`
 	#[rpc(name = "bancor_getBancorTokenAmountOut")]
 	fn get_bancor_token_amount_out(
 		&self,
 		token_id: CurrencyId,
-		vstoken_amount: Balance,
+		vstoken_amount: NumberOrHex,
 		at: Option<BlockHash>,
-	) -> JsonRpcResult<Balance>;
+	) -> JsonRpcResult<NumberOrHex>;
 
+	/// rpc method for getting the amount of vstoken exchanged out by `token_amount`
 	#[rpc(name = "bancor_getBancorVstokenAmountOut")]
 	fn get_bancor_vstoken_amount_out(
 		&self,
 		token_id: CurrencyId,
-		token_amount: Balance,
+		token_amount: NumberOrHex,
 		at: Option<BlockHash>,
-	) -> JsonRpcResult<Balance>;
+	) -> JsonRpcResult<NumberOrHex>;
 
+	/// rpc method for getting the instant price of vstoken, as `(nominator, denominator)`
 	#[rpc(name = "bancor_getInstantVstokenPrice")]
 	fn get_instant_vstoken_price(
 		&self,
 		currency_id: CurrencyId,
 		at: Option<BlockHash>,
-	) -> JsonRpcResult<Balance>;
+	) -> JsonRpcResult<(NumberOrHex, NumberOrHex)>;
 
+	/// rpc method for getting the instant price of token, as `(nominator, denominator)`
 	#[rpc(name = "bancor_getInstantTokenPrice")]
 	fn get_instant_token_price(
 		&self,
 		currency_id: CurrencyId,
 		at: Option<BlockHash>,
-	) -> JsonRpcResult<Balance>;
-}
+	) -> JsonRpcResult<(NumberOrHex, NumberOrHex)>;
 
-/// Error type of this RPC api.
-pub enum Error {
-	/// The transaction was not decodable.
-	DecodeError,
-	/// The call to runtime failed.
-	RuntimeError,
+	/// rpc method for getting the forecast of the token ceiling in the next `days` days, at most
+	/// `MAX_FORECAST_DAYS` days
+	#[rpc(name = "bancor_getTokenCeilingForecast")]
+	fn get_token_ceiling_forecast(
+		&self,
+		currency_id: CurrencyId,
+		days: u32,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<Vec<NumberOrHex>>;
 }
 
-impl From<Error> for i64 {
-	fn from(e: Error) -> i64 {
-		match e {
-			Error::RuntimeError => 1,
-			Error::DecodeError => 2,
+impl<C, Block> BancorStruct<C, Block> {
+	fn convert_rpc_params(value: Balance) -> Result<NumberOrHex, RpcError> {
+		value.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", value),
+			data: None,
+		})
+	}
+
+	fn parse_rpc_params(value: NumberOrHex) -> Result<Balance, RpcError> {
+		value.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{:?} doesn't fit in Balance representation", value),
+			data: None,
+		})
+	}
+
+	fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+		RpcError {
+			code: ErrorCode::InternalError,
+			message: message.to_owned(),
+			data: Some(format!("{:?}", e).into()),
 		}
 	}
 }
 
-impl<C, Block, CurrencyId, Balance> BancorRpcApi<<Block as BlockT>::Hash, CurrencyId, Balance>
+impl<C, Block, CurrencyId> BancorRpcApi<<Block as BlockT>::Hash, CurrencyId>
 	for BancorStruct<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 	C::Api: BancorRuntimeApi<Block, CurrencyId, Balance>,
 	CurrencyId: Codec,
-	Balance: Codec + std::fmt::Display + std::ops::Add<Output = Balance> + sp_runtime::traits::Zero,
 {
 	fn get_bancor_token_amount_out(
 		&self,
 		token_id: CurrencyId,
-		vstoken_amount: Balance,
-		at: Option<BlockHash>,
+		vstoken_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
 	) -> JsonRpcResult<NumberOrHex> {
 		let api = self.client.runtime_api();
 		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let vstoken_amount = Self::parse_rpc_params(vstoken_amount)?;
 
 		let rs = api.get_bancor_token_amount_out(&at, token_id, vstoken_amount);
 
 		match rs {
-			Ok(val) => Ok(NumberOrHex::Number(val.saturated_into())),
-			Err(e) => Err(RpcError {
-				code: ErrorCode::InternalError,
-				message: "Failed to get bancor token amount out.".to_owned(),
-				data: Some(format!("{:?}", e).into()),
-			}),
+			Ok(val) => Self::convert_rpc_params(val),
+			Err(e) => Err(Self::runtime_error("Failed to get bancor token amount out.", e)),
 		}
 	}
 
 	fn get_bancor_vstoken_amount_out(
 		&self,
 		token_id: CurrencyId,
-		token_amount: Balance,
-		at: Option<BlockHash>,
+		token_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
 	) -> JsonRpcResult<NumberOrHex> {
 		let api = self.client.runtime_api();
 		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let token_amount = Self::parse_rpc_params(token_amount)?;
 
 		let rs = api.get_bancor_vstoken_amount_out(&at, token_id, token_amount);
+
 		match rs {
-			Ok(val) => Ok(NumberOrHex::Number(val.saturated_into())),
-			Err(e) => Err(RpcError {
-				code: ErrorCode::InternalError,
-				message: "Failed to get bancor vstoken amount out.".to_owned(),
-				data: Some(format!("{:?}", e).into()),
-			}),
+			Ok(val) => Self::convert_rpc_params(val),
+			Err(e) => Err(Self::runtime_error("Failed to get bancor vstoken amount out.", e)),
 		}
 	}
 
 	fn get_instant_vstoken_price(
 		&self,
 		currency_id: CurrencyId,
-		at: Option<BlockHash>,
-	) -> JsonRpcResult<NumberOrHex> {
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<(NumberOrHex, NumberOrHex)> {
 		let api = self.client.runtime_api();
 		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
 		let rs = api.get_instant_vstoken_price(&at, currency_id);
+
 		match rs {
-			Ok(val) => Ok(NumberOrHex::Number(val.saturated_into())),
-			Err(e) => Err(RpcError {
-				code: ErrorCode::InternalError,
-				message: "Failed to get bancor instant vstoken price.".to_owned(),
-				data: Some(format!("{:?}", e).into()),
-			}),
+			Ok((nominator, denominator)) =>
+				Ok((Self::convert_rpc_params(nominator)?, Self::convert_rpc_params(denominator)?)),
+			Err(e) => Err(Self::runtime_error("Failed to get bancor instant vstoken price.", e)),
 		}
 	}
 
 	fn get_instant_token_price(
 		&self,
 		currency_id: CurrencyId,
-		at: Option<BlockHash>,
-	) -> JsonRpcResult<NumberOrHex> {
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<(NumberOrHex, NumberOrHex)> {
 		let api = self.client.runtime_api();
 		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
 		let rs = api.get_instant_token_price(&at, currency_id);
+
+		match rs {
+			Ok((nominator, denominator)) =>
+				Ok((Self::convert_rpc_params(nominator)?, Self::convert_rpc_params(denominator)?)),
+			Err(e) => Err(Self::runtime_error("Failed to get bancor instant token price.", e)),
+		}
+	}
+
+	fn get_token_ceiling_forecast(
+		&self,
+		currency_id: CurrencyId,
+		days: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<Vec<NumberOrHex>> {
+		if days > MAX_FORECAST_DAYS {
+			return Err(RpcError {
+				code: ErrorCode::InvalidParams,
+				message: format!("Can't forecast for more than {} days", MAX_FORECAST_DAYS),
+				data: None,
+			});
+		}
+
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let rs = api.get_token_ceiling_forecast(&at, currency_id, days);
+
 		match rs {
-			Ok(val) => Ok(NumberOrHex::Number(val.saturated_into())),
-			Err(e) => Err(RpcError {
-				code: ErrorCode::InternalError,
-				message: "Failed to get bancor instant token price.".to_owned(),
-				data: Some(format!("{:?}", e).into()),
-			}),
+			Ok(ceilings) => ceilings.into_iter().map(Self::convert_rpc_params).collect(),
+			Err(e) => Err(Self::runtime_error("Failed to get bancor token ceiling forecast.", e)),
 		}
 	}
 }
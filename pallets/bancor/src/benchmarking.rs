@@ -19,14 +19,15 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::dispatch::UnfilteredDispatchable;
 use frame_system::RawOrigin;
 use node_primitives::{CurrencyId, TokenSymbol};
 use sp_runtime::traits::UniqueSaturatedFrom;
 
 use super::*;
-use crate::BancorPools;
 #[allow(unused_imports)]
 use crate::Pallet as Bancor;
+use crate::{BancorPools, BancorReserve, PausedPools, PriceOracle, PriceOracles};
 
 benchmarks! {
 	add_token_to_pool {
@@ -64,18 +65,76 @@ benchmarks! {
 		});
 	}: _(RawOrigin::Signed(caller), currency_id, token_amount, vstoken_out_min)
 
-	on_initialize {
+	create_bancor_pool {
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::ETH);
+		let vstoken_base_supply = BalanceOf::<T>::unique_saturated_from(10_000u128);
+		let reserve_ratio = Perbill::from_percent(20);
+		let call = Call::<T>::create_bancor_pool { currency_id, vstoken_base_supply, reserve_ratio };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	retire_bancor_pool {
+		let origin = T::ControlOrigin::successful_origin();
 		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
-		let caller: T::AccountId = whitelisted_caller();
-		let token_amount = BalanceOf::<T>::unique_saturated_from(7200 as u128);
+		let beneficiary: T::AccountId = whitelisted_caller();
 
+		BancorReserve::<T>::insert(currency_id, BalanceOf::<T>::unique_saturated_from(100u128));
 		BancorPools::<T>::mutate(currency_id, |pool| {
 			let pool_info = pool.as_mut().unwrap();
-			pool_info.token_pool = pool_info.token_pool.saturating_add(token_amount);
+			pool_info.token_ceiling = BalanceOf::<T>::unique_saturated_from(100u128);
+			pool_info.vstoken_pool = BalanceOf::<T>::unique_saturated_from(100u128);
+		});
+
+		let call = Call::<T>::retire_bancor_pool { currency_id, beneficiary };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	set_price_oracle {
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let call = Call::<T>::set_price_oracle {
+			currency_id,
+			twap_window: 3_600,
+			max_deviation: Perbill::from_percent(20),
+		};
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	remove_price_oracle {
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+
+		PriceOracles::<T>::insert(currency_id, PriceOracle {
+			twap_window: 3_600,
+			max_deviation: Perbill::from_percent(20),
+			price: FixedU128::one(),
+			..Default::default()
 		});
 
-		let block_num = T::BlockNumber::from(100u32);
-	}:{Bancor::<T>::on_initialize(block_num);}
+		let call = Call::<T>::remove_price_oracle { currency_id };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	resume_bancor_pool {
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+
+		PausedPools::<T>::insert(currency_id, true);
+
+		let call = Call::<T>::resume_bancor_pool { currency_id };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	on_initialize {
+		let n in 0 .. 100;
+
+		for i in 0 .. n {
+			let currency_id = CurrencyId::VSBond(TokenSymbol::KSM, i, 13, 20);
+			PriceOracles::<T>::insert(currency_id, PriceOracle {
+				twap_window: 0,
+				max_deviation: Perbill::from_percent(20),
+				price: FixedU128::one(),
+				spot_price: FixedU128::one(),
+				..Default::default()
+			});
+		}
+	}: {Bancor::<T>::on_initialize(T::BlockNumber::from(1u32));}
 
 }
 
@@ -18,34 +18,42 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{OnRuntimeUpgrade, StorageVersion, UnixTime},
+};
 use frame_system::pallet_prelude::*;
-use node_primitives::{traits::BancorHandler, CurrencyId, CurrencyIdExt};
-use num_bigint::BigUint;
+use node_primitives::{traits::BancorHandler, CurrencyId, CurrencyIdExt, MAX_FORECAST_DAYS};
 use orml_traits::MultiCurrency;
 use scale_info::TypeInfo;
 use sp_arithmetic::per_things::{PerThing, Perbill, Percent};
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating, Zero},
-	SaturatedConversion,
+	traits::{CheckedDiv, Saturating, Zero},
+	FixedPointNumber, FixedU128, SaturatedConversion,
 };
+use sp_std::vec::Vec;
 pub use weights::WeightInfo;
 
+mod math;
 mod mock;
+pub mod oracle;
 mod tests;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub use oracle::{MarketPriceProvider, ZenlinkMarketPrice};
 pub use pallet::*;
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
 
-const BILLION: u128 = 1_000_000_000;
-// These time units are defined in number of blocks.
-const BLOCKS_PER_DAY: u32 = 60 / 12 * 60 * 24;
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+// The connector weight of the pools created before it became configurable.
+const DEFAULT_RESERVE_RATIO: Perbill = Perbill::from_percent(50);
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
 pub struct BancorPool<Balance> {
@@ -55,6 +63,89 @@ pub struct BancorPool<Balance> {
 	token_ceiling: Balance,       // token available for sale
 	token_base_supply: Balance,   // initial virtual supply of token for the pool
 	vstoken_base_supply: Balance, // initial virtual balance of vstoken for the pool
+	reserve_ratio: Perbill,       // connector weight(CW) of the pool
+}
+
+/// The price oracle guarding a bancor pool, which samples the market price at the start of each
+/// block.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, Debug, TypeInfo)]
+pub struct PriceOracle {
+	twap_window: u64, // the window in seconds over which the market price is averaged
+	max_deviation: Perbill, // the pool is paused once its price deviates more than this
+	price: FixedU128, // average market price of vstoken over the last completed window, or zero
+	spot_price: FixedU128, // the market price sampled last time, zero if not sampled
+	updated_at: u64,  // when the market price was sampled last time, in seconds
+	cumulative: FixedU128, // the sum of the sampled prices, each weighted by the seconds it held
+	window_cumulative: FixedU128, // the cumulative price at the start of the current window
+	window_start: u64, // when the current window started, in seconds
+	deviations: u32,  // the consecutive samples at which the pool deviates too much
+}
+
+pub mod migration {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	struct OldBancorPool<Balance> {
+		currency_id: CurrencyId,
+		token_pool: Balance,
+		vstoken_pool: Balance,
+		token_ceiling: Balance,
+		token_base_supply: Balance,
+		vstoken_base_supply: Balance,
+	}
+
+	/// Migrate the existing pools, whose connector weight was hard-coded as 1/2.
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return 0;
+		}
+
+		let mut translated: Weight = 0;
+		BancorPools::<T>::translate::<OldBancorPool<BalanceOf<T>>, _>(|_, old| {
+			translated = translated.saturating_add(1);
+
+			Some(BancorPool {
+				currency_id: old.currency_id,
+				token_pool: old.token_pool,
+				vstoken_pool: old.vstoken_pool,
+				token_ceiling: old.token_ceiling,
+				token_base_supply: old.token_base_supply,
+				vstoken_base_supply: old.vstoken_base_supply,
+				reserve_ratio: DEFAULT_RESERVE_RATIO,
+			})
+		});
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+
+	/// Create the pools of the runtime which are missing, for the chains launched before the pools
+	/// were in the genesis: (currency_id, vstoken_base_supply)
+	pub struct CreateBancorPools<T, Pools>(PhantomData<(T, Pools)>);
+
+	impl<T, Pools> OnRuntimeUpgrade for CreateBancorPools<T, Pools>
+	where
+		T: Config,
+		Pools: Get<Vec<(CurrencyId, BalanceOf<T>)>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let pools = Pools::get();
+			let mut created: Weight = 0;
+			for (currency_id, vstoken_base_supply) in pools.iter() {
+				if !BancorPools::<T>::contains_key(currency_id) {
+					Pallet::<T>::inner_create_bancor_pool(
+						*currency_id,
+						*vstoken_base_supply,
+						DEFAULT_RESERVE_RATIO,
+					);
+					created = created.saturating_add(1);
+				}
+			}
+
+			T::DbWeight::get().reads_writes(pools.len() as Weight, created.saturating_mul(2))
+		}
+	}
 }
 
 #[frame_support::pallet]
@@ -72,6 +163,24 @@ pub mod pallet {
 		#[pallet::constant]
 		type DailyReleasePercentage: Get<Percent>;
 
+		/// The length of a release epoch in seconds, the reserve is released once per epoch
+		#[pallet::constant]
+		type ReleaseEpoch: Get<u64>;
+
+		/// The source of the current time, which drives the release of the reserve
+		type UnixTime: UnixTime;
+
+		/// The source of the market price of vstoken, `()` if there is no market to refer to
+		type MarketPrice: MarketPriceProvider;
+
+		/// The origin that can create, retire or guard bancor pools
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of consecutive samples at which the price of a pool has to deviate too much
+		/// from the oracle price, before the pool is paused
+		#[pallet::constant]
+		type CircuitBreakerSamples: Get<u32>;
+
 		/// Set default weight.
 		type WeightInfo: WeightInfo;
 	}
@@ -88,6 +197,14 @@ pub mod pallet {
 		PriceNotQualified,
 		CalculationOverflow,
 		NotSupportTokenType,
+		BancorPoolAlreadyExist,
+		InvalidReserveRatio,
+		BancorPoolPaused,
+		BancorPoolNotPaused,
+		PriceOracleNotExist,
+		InvalidMaxDeviation,
+		ExchangePriceAboveMarket,
+		ForecastTooLong,
 	}
 
 	#[pallet::event]
@@ -99,6 +216,31 @@ pub mod pallet {
 		TokenSold(AccountIdOf<T>, CurrencyId, BalanceOf<T>, BalanceOf<T>),
 		/// [buyer, currencyId, vsToken_sold, Token_paid]
 		VSTokenSold(AccountIdOf<T>, CurrencyId, BalanceOf<T>, BalanceOf<T>),
+		/// A bancor pool has been created.
+		///
+		/// [currencyId, reserve_ratio, token_base_supply, vsToken_base_supply]
+		BancorPoolCreated(CurrencyId, Perbill, BalanceOf<T>, BalanceOf<T>),
+		/// A bancor pool has been retired, the tokens left are paid to the beneficiary.
+		///
+		/// [currencyId, beneficiary, token_paid, vsToken_paid]
+		BancorPoolRetired(CurrencyId, AccountIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// Token has been released from the reserve to the bancor pool.
+		///
+		/// [currencyId, token_put_in_ceiling, token_sold]
+		ReserveReleased(CurrencyId, BalanceOf<T>, BalanceOf<T>),
+		/// A price oracle has been set for the bancor pool.
+		///
+		/// [currencyId, twap_window, max_deviation]
+		PriceOracleSet(CurrencyId, u64, Perbill),
+		/// [currencyId]
+		PriceOracleRemoved(CurrencyId),
+		/// The circuit breaker has been tripped, since the price of the pool deviates too much
+		/// from the market.
+		///
+		/// [currencyId, pool_price, oracle_price]
+		BancorPoolPaused(CurrencyId, FixedU128, FixedU128),
+		/// [currencyId]
+		BancorPoolResumed(CurrencyId),
 	}
 
 	// key is token, value is BancorPool struct.
@@ -111,6 +253,21 @@ pub mod pallet {
 	#[pallet::getter(fn get_bancor_reserve)]
 	pub type BancorReserve<T> = StorageMap<_, Blake2_128Concat, CurrencyId, BalanceOf<T>>;
 
+	/// The epoch in which the reserve of the pool was released last time
+	#[pallet::storage]
+	#[pallet::getter(fn last_release_epoch)]
+	pub type LastReleaseEpoch<T> = StorageMap<_, Blake2_128Concat, CurrencyId, u64>;
+
+	/// The price oracles guarding the bancor pools
+	#[pallet::storage]
+	#[pallet::getter(fn price_oracle)]
+	pub type PriceOracles<T> = StorageMap<_, Blake2_128Concat, CurrencyId, PriceOracle>;
+
+	/// The bancor pools paused by the circuit breaker
+	#[pallet::storage]
+	#[pallet::getter(fn is_paused)]
+	pub type PausedPools<T> = StorageMap<_, Blake2_128Concat, CurrencyId, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub bancor_pools: Vec<(CurrencyId, BalanceOf<T>)>,
@@ -127,107 +284,34 @@ pub mod pallet {
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			for (currency_id, base_balance) in self.bancor_pools.iter() {
-				let pool = BancorPool {
-					currency_id: *currency_id,
-					token_pool: Zero::zero(),
-					vstoken_pool: Zero::zero(),
-					token_ceiling: Zero::zero(),
-					token_base_supply: base_balance.saturating_mul(BalanceOf::<T>::from(2u32)),
-					vstoken_base_supply: *base_balance,
-				};
-
-				BancorPools::<T>::insert(currency_id.clone(), pool);
-				BancorReserve::<T>::insert(currency_id.clone(), BalanceOf::<T>::from(0u32));
+				Pallet::<T>::inner_create_bancor_pool(
+					*currency_id,
+					*base_balance,
+					DEFAULT_RESERVE_RATIO,
+				);
 			}
 		}
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		//  check whether the price of vstoken (token/vstoken) is lower than 75%. if yes, then half
-		// of this newly released token should be used to buy vstoken,  so that the price of vstoken
-		// will increase. Meanwhile, the other half will be put on the ceiling variable to indicate
-		// exchange availability. 	If not, all the newly release token should be put aside to the
-		// ceiling to not to impact the pool price.
-		fn on_initialize(_: T::BlockNumber) -> Weight {
-			// for each bancor pool currency_id, release 5% of reserve tokens to the pool
-			for (currency_id, reserve_amount) in BancorReserve::<T>::iter() {
-				let token_amount = reserve_amount /
-					T::DailyReleasePercentage::get()
-						.saturating_reciprocal_mul_floor(BalanceOf::<T>::from(BLOCKS_PER_DAY));
-
-				if token_amount > Zero::zero() {
-					// get the current price of vstoken
-					// let (nominator, denominator) = Self::get_instant_vstoken_price(currency_id);
-					if let Ok((nominator, denominator)) =
-						Self::get_instant_vstoken_price(currency_id)
-					{
-						let amount_kept: BalanceOf<T>;
-						// if vstoken price is lower than 0.75 token
-						if T::InterventionPercentage::get()
-							.saturating_reciprocal_mul_floor(nominator) <=
-							denominator
-						{
-							amount_kept = token_amount / BalanceOf::<T>::saturated_from(2u128);
-						} else {
-							amount_kept = token_amount;
-						}
-
-						let sell_amount = token_amount.saturating_sub(amount_kept);
-						// deal with ceiling variable
-						if amount_kept != Zero::zero() {
-							if let Err(_) =
-								Self::increase_bancor_pool_ceiling(currency_id, amount_kept)
-							{
-								continue;
-							}
-						}
-						// deal with exchange transaction
-						if sell_amount != Zero::zero() {
-							// make changes in the bancor pool
-							if let Ok(vstoken_amount) =
-								Self::calculate_price_for_vstoken(currency_id, sell_amount)
-							{
-								let sell_result = Self::revise_bancor_pool_token_buy_vstoken(
-									currency_id,
-									sell_amount,
-									vstoken_amount,
-								);
-								// if somehow not able to sell token, then add the amount to
-								// ceiling.
-								if let Err(err_msg) = sell_result {
-									match err_msg {
-										Error::<T>::BancorPoolNotExist => (),
-										_ => {
-											if let Err(_) = Self::increase_bancor_pool_ceiling(
-												currency_id,
-												sell_amount,
-											) {
-												continue;
-											}
-										},
-									};
-								}
-							}
-						}
-
-						// deduct token_amount from BancorReserve
-						BancorReserve::<T>::mutate(currency_id, |reserve_option| {
-							match reserve_option {
-								Some(reserve) => {
-									*reserve = reserve.saturating_sub(token_amount);
-								},
-								_ => (),
-							}
-						});
-					}
-				}
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			// Sample the market prices before any swap in the block.
+			let currency_ids: Vec<CurrencyId> = PriceOracles::<T>::iter_keys().collect();
+			let sampled = currency_ids.len() as u32;
+			for currency_id in currency_ids {
+				Self::sample_market_price(currency_id);
 			}
 
-			T::WeightInfo::on_initialize()
+			T::WeightInfo::on_initialize(sampled)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
 		}
 	}
 
@@ -268,10 +352,26 @@ pub mod pallet {
 			let vstoken_balance = T::MultiCurrency::free_balance(vstoken_id, &exchanger);
 			ensure!(vstoken_balance >= vstoken_amount, Error::<T>::NotEnoughBalance);
 
+			// Not transactional, so that the reserve stays released even though the exchange
+			// fails.
+			let oracle_price = Self::touch_pool(currency_id);
+			ensure!(!Self::is_paused(currency_id), Error::<T>::BancorPoolPaused);
+
 			// make changes in the bancor pool
 			let token_amount = Self::calculate_price_for_token(currency_id, vstoken_amount)?;
 
 			ensure!(token_amount >= token_out_min, Error::<T>::PriceNotQualified);
+			// vstoken can not be exchanged for more token than it is worth on the market.
+			if let Some(oracle_price) = oracle_price {
+				ensure!(
+					token_amount <=
+						BalanceOf::<T>::saturated_from(
+							oracle_price
+								.saturating_mul_int(vstoken_amount.saturated_into::<u128>())
+						),
+					Error::<T>::ExchangePriceAboveMarket
+				);
+			}
 
 			Self::revise_bancor_pool_vstoken_buy_token(currency_id, token_amount, vstoken_amount)?;
 
@@ -306,6 +406,11 @@ pub mod pallet {
 			let token_balance = T::MultiCurrency::free_balance(currency_id, &exchanger);
 			ensure!(token_balance >= token_amount, Error::<T>::NotEnoughBalance);
 
+			// Not transactional, so that the reserve stays released even though the exchange
+			// fails.
+			Self::touch_pool(currency_id);
+			ensure!(!Self::is_paused(currency_id), Error::<T>::BancorPoolPaused);
+
 			// make changes in the bancor pool
 			let vstoken_amount = Self::calculate_price_for_vstoken(currency_id, token_amount)?;
 
@@ -326,6 +431,137 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Create a bancor pool whose price of vstoken starts at 1 token.
+		///
+		/// `reserve_ratio` is the connector weight(CW) of the pool, the lower CW is, the more
+		/// sensitive the price is to the exchanges.
+		#[pallet::weight(T::WeightInfo::create_bancor_pool())]
+		pub fn create_bancor_pool(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			vstoken_base_supply: BalanceOf<T>,
+			reserve_ratio: Perbill,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(currency_id.is_token(), Error::<T>::NotSupportTokenType);
+			ensure!(
+				!BancorPools::<T>::contains_key(currency_id),
+				Error::<T>::BancorPoolAlreadyExist
+			);
+			ensure!(vstoken_base_supply > Zero::zero(), Error::<T>::AmountNotGreaterThanZero);
+			ensure!(!reserve_ratio.is_zero(), Error::<T>::InvalidReserveRatio);
+
+			let pool =
+				Self::inner_create_bancor_pool(currency_id, vstoken_base_supply, reserve_ratio);
+
+			Self::deposit_event(Event::BancorPoolCreated(
+				currency_id,
+				reserve_ratio,
+				pool.token_base_supply,
+				pool.vstoken_base_supply,
+			));
+
+			Ok(())
+		}
+
+		/// Retire a bancor pool, the tokens in reserve and ceiling and the vstokens in the pool
+		/// are paid to the `beneficiary`.
+		#[pallet::weight(T::WeightInfo::retire_bancor_pool())]
+		pub fn retire_bancor_pool(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			beneficiary: AccountIdOf<T>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let pool_info =
+				Self::get_bancor_pool(currency_id).ok_or(Error::<T>::BancorPoolNotExist)?;
+			let vstoken_id = currency_id.to_vstoken().map_err(|_| Error::<T>::ConversionError)?;
+
+			let reserve = BancorReserve::<T>::take(currency_id).unwrap_or_else(Zero::zero);
+			LastReleaseEpoch::<T>::remove(currency_id);
+			PriceOracles::<T>::remove(currency_id);
+			PausedPools::<T>::remove(currency_id);
+			let token_amount = reserve.saturating_add(pool_info.token_ceiling);
+			let vstoken_amount = pool_info.vstoken_pool;
+
+			BancorPools::<T>::remove(currency_id);
+
+			T::MultiCurrency::deposit(currency_id, &beneficiary, token_amount)?;
+			T::MultiCurrency::deposit(vstoken_id, &beneficiary, vstoken_amount)?;
+
+			Self::deposit_event(Event::BancorPoolRetired(
+				currency_id,
+				beneficiary,
+				token_amount,
+				vstoken_amount,
+			));
+
+			Ok(())
+		}
+
+		/// Guard a bancor pool by the market price of vstoken.
+		///
+		/// The market price sampled in each block is averaged over windows of `twap_window`
+		/// seconds. The intervention and the exchanges for token are bounded by the average of the
+		/// last window, and the pool is paused once its price deviates more than `max_deviation`
+		/// from it at `CircuitBreakerSamples` consecutive samples.
+		#[pallet::weight(T::WeightInfo::set_price_oracle())]
+		pub fn set_price_oracle(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			twap_window: u64,
+			max_deviation: Perbill,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(BancorPools::<T>::contains_key(currency_id), Error::<T>::BancorPoolNotExist);
+			ensure!(!max_deviation.is_zero(), Error::<T>::InvalidMaxDeviation);
+
+			PriceOracles::<T>::insert(
+				currency_id,
+				PriceOracle { twap_window, max_deviation, ..Default::default() },
+			);
+
+			Self::deposit_event(Event::PriceOracleSet(currency_id, twap_window, max_deviation));
+
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::remove_price_oracle())]
+		pub fn remove_price_oracle(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(PriceOracles::<T>::contains_key(currency_id), Error::<T>::PriceOracleNotExist);
+			PriceOracles::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::PriceOracleRemoved(currency_id));
+
+			Ok(())
+		}
+
+		/// Resume a bancor pool paused by the circuit breaker.
+		#[pallet::weight(T::WeightInfo::resume_bancor_pool())]
+		pub fn resume_bancor_pool(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::is_paused(currency_id), Error::<T>::BancorPoolNotPaused);
+			PausedPools::<T>::remove(currency_id);
+			PriceOracles::<T>::mutate(currency_id, |maybe_oracle| {
+				if let Some(oracle) = maybe_oracle {
+					oracle.deviations = 0;
+				}
+			});
+
+			Self::deposit_event(Event::BancorPoolResumed(currency_id));
+
+			Ok(())
+		}
 	}
 }
 
@@ -333,7 +569,7 @@ impl<T: Config> Pallet<T> {
 	/// Formula: Supply * ((1 + vsDOT/Balance) ^CW -1)
 	/// Supply: The total amount of DOT currently Sent in plus initiated virtual amount of DOT
 	/// Balance: The total amount of vsDOT currently Sent in plus initiated virtual amount of vsDOT
-	/// CW: Constant, here is 1/2
+	/// CW: The reserve ratio of the pool
 	pub fn calculate_price_for_token(
 		token_id: CurrencyId,
 		vstoken_amount: BalanceOf<T>,
@@ -345,47 +581,26 @@ impl<T: Config> Pallet<T> {
 		// Only if token_ceiling is not zero, then exchangers can exchange vstokens for tokens.
 		ensure!(pool_info.token_ceiling > Zero::zero(), Error::<T>::TokenSupplyNotEnough);
 
-		let (token_supply, vstoken_supply) = (
-			pool_info.token_base_supply.saturating_add(pool_info.token_pool),
-			pool_info.vstoken_base_supply.saturating_add(pool_info.vstoken_pool),
-		);
+		let (token_supply, vstoken_supply) = Self::pool_supplies(&pool_info);
 		ensure!(vstoken_supply > Zero::zero(), Error::<T>::AmountNotGreaterThanZero);
 
-		// To avoid overflow, we introduce num-bigint package.
-		let vstoken_amount = {
-			let temp: u128 = vstoken_amount.saturated_into();
-			BigUint::from(temp)
-		};
-		let vstoken_supply = {
-			let temp: u128 = vstoken_supply.saturated_into();
-			BigUint::from(temp)
-		};
-		let token_supply = {
-			let temp: u128 = token_supply.saturated_into();
-			BigUint::from(temp)
-		};
-		let token_supply_square =
-			token_supply.checked_mul(&token_supply).ok_or(Error::<T>::CalculationOverflow)?;
-
-		let nominator_lhs = token_supply_square
-			.checked_mul(&vstoken_supply)
-			.ok_or(Error::<T>::CalculationOverflow)?;
-		let nominator_rhs = token_supply_square
-			.checked_mul(&vstoken_amount)
-			.ok_or(Error::<T>::CalculationOverflow)?;
-		let nominator = nominator_lhs
-			.checked_add(&nominator_rhs)
-			.ok_or(Error::<T>::CalculationOverflow)?;
-
-		let inside =
-			nominator.checked_div(&vstoken_supply).ok_or(Error::<T>::CalculationOverflow)?;
-		let square_root = inside.nth_root(2);
-		let result =
-			square_root.checked_sub(&token_supply).ok_or(Error::<T>::CalculationOverflow)?;
-		let result_convert: u128 = u128::from_str_radix(&result.to_str_radix(10), 10)
-			.map_err(|_| Error::<T>::ConversionError)?;
-
-		let price = BalanceOf::<T>::saturated_from(result_convert);
+		// 1 + vsDOT/Balance
+		let base = FixedU128::checked_from_rational(
+			vstoken_supply.saturating_add(vstoken_amount).saturated_into::<u128>(),
+			vstoken_supply.saturated_into::<u128>(),
+		)
+		.ok_or(Error::<T>::CalculationOverflow)?;
+		let exp = FixedU128::saturating_from_rational(
+			pool_info.reserve_ratio.deconstruct(),
+			Perbill::ACCURACY,
+		);
+		let power = math::pow(base, exp).ok_or(Error::<T>::CalculationOverflow)?;
+
+		let price = BalanceOf::<T>::saturated_from(
+			power
+				.saturating_sub(FixedU128::one())
+				.saturating_mul_int(token_supply.saturated_into::<u128>()),
+		);
 
 		// We can not exchange for more than that the the pool has
 		ensure!(price <= pool_info.token_ceiling, Error::<T>::TokenSupplyNotEnough);
@@ -396,7 +611,7 @@ impl<T: Config> Pallet<T> {
 	/// Formula: Balance * (1 - (1 - DOT/Supply)^ (1/CW))
 	/// Supply: The total amount of DOT currently Sent in plus initiated virtual amount of DOT
 	/// Balance: The total amount of vsDOT currently Sent in plus initiated virtual amount of vsDOT
-	/// CW: Constant, here is 1/2
+	/// CW: The reserve ratio of the pool
 	pub fn calculate_price_for_vstoken(
 		token_id: CurrencyId,
 		token_amount: BalanceOf<T>,
@@ -407,24 +622,30 @@ impl<T: Config> Pallet<T> {
 		let pool_info = Self::get_bancor_pool(token_id).ok_or(Error::<T>::BancorPoolNotExist)?;
 		ensure!(pool_info.vstoken_pool > Zero::zero(), Error::<T>::VSTokenSupplyNotEnough);
 
-		let (token_supply, vstoken_supply) = (
-			pool_info.token_base_supply.saturating_add(pool_info.token_pool),
-			pool_info.vstoken_base_supply.saturating_add(pool_info.vstoken_pool),
-		);
+		let (token_supply, vstoken_supply) = Self::pool_supplies(&pool_info);
 
 		// Since token_amount will be deducted from the total token_supply, token_amount should be
 		// less than or equal to token_supply.
 		ensure!(token_amount <= token_supply, Error::<T>::TokenSupplyNotEnough);
-		let mid_item: Perbill =
-			PerThing::from_rational(token_supply.saturating_sub(token_amount), token_supply);
-		let square_item: Perbill = mid_item.square();
 
-		// Destruct the nominator from permill and divide the result by the denominator of a
-		// million.
-		let rhs = Perbill::one().saturating_sub(square_item);
-		let rhs_nominator = BalanceOf::<T>::saturated_from(rhs.deconstruct());
-		let price =
-			rhs_nominator.saturating_mul(vstoken_supply) / BalanceOf::<T>::saturated_from(BILLION);
+		// 1 - DOT/Supply
+		let base = FixedU128::checked_from_rational(
+			token_supply.saturating_sub(token_amount).saturated_into::<u128>(),
+			token_supply.saturated_into::<u128>(),
+		)
+		.ok_or(Error::<T>::CalculationOverflow)?;
+		let exp = FixedU128::checked_from_rational(
+			Perbill::ACCURACY,
+			pool_info.reserve_ratio.deconstruct(),
+		)
+		.ok_or(Error::<T>::CalculationOverflow)?;
+		let power = math::pow(base, exp).ok_or(Error::<T>::CalculationOverflow)?;
+
+		let price = BalanceOf::<T>::saturated_from(
+			FixedU128::one()
+				.saturating_sub(power)
+				.saturating_mul_int(vstoken_supply.saturated_into::<u128>()),
+		);
 
 		// We can not exchange for more than that the the pool has
 		ensure!(price <= pool_info.vstoken_pool, Error::<T>::VSTokenSupplyNotEnough);
@@ -433,18 +654,15 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// one vstoken worths how many tokens
-	// formula: token_supply/ (vstoken_balance/ cw). Note: cw = 1/2
+	// formula: token_supply * cw / vstoken_balance.
 	// return value: (nominator, denominator)
 	pub fn get_instant_vstoken_price(
 		currency_id: CurrencyId,
 	) -> Result<(BalanceOf<T>, BalanceOf<T>), Error<T>> {
 		let pool_info = Self::get_bancor_pool(currency_id).ok_or(Error::<T>::BancorPoolNotExist)?;
-		let (token_supply, vstoken_supply) = (
-			pool_info.token_base_supply.saturating_add(pool_info.token_pool),
-			pool_info.vstoken_base_supply.saturating_add(pool_info.vstoken_pool),
-		);
+		let (token_supply, vstoken_supply) = Self::pool_supplies(&pool_info);
 
-		Ok((token_supply, BalanceOf::<T>::saturated_from(2u128).saturating_mul(vstoken_supply)))
+		Ok((pool_info.reserve_ratio.mul_floor(token_supply), vstoken_supply))
 	}
 
 	// one token worths how many vstokens
@@ -452,12 +670,286 @@ impl<T: Config> Pallet<T> {
 		currency_id: CurrencyId,
 	) -> Result<(BalanceOf<T>, BalanceOf<T>), Error<T>> {
 		let pool_info = Self::get_bancor_pool(currency_id).ok_or(Error::<T>::BancorPoolNotExist)?;
-		let (token_supply, vstoken_supply) = (
+		let (token_supply, vstoken_supply) = Self::pool_supplies(&pool_info);
+
+		Ok((vstoken_supply, pool_info.reserve_ratio.mul_floor(token_supply)))
+	}
+
+	/// The virtual supplies of the pool: (token_supply, vstoken_supply)
+	fn pool_supplies(pool_info: &BancorPool<BalanceOf<T>>) -> (BalanceOf<T>, BalanceOf<T>) {
+		(
 			pool_info.token_base_supply.saturating_add(pool_info.token_pool),
 			pool_info.vstoken_base_supply.saturating_add(pool_info.vstoken_pool),
+		)
+	}
+
+	/// The virtual token supply is set so that the price of vstoken starts at 1 token.
+	pub(crate) fn inner_create_bancor_pool(
+		currency_id: CurrencyId,
+		vstoken_base_supply: BalanceOf<T>,
+		reserve_ratio: Perbill,
+	) -> BancorPool<BalanceOf<T>> {
+		let pool = BancorPool {
+			currency_id,
+			token_pool: Zero::zero(),
+			vstoken_pool: Zero::zero(),
+			token_ceiling: Zero::zero(),
+			token_base_supply: reserve_ratio.saturating_reciprocal_mul_floor(vstoken_base_supply),
+			vstoken_base_supply,
+			reserve_ratio,
+		};
+
+		BancorPools::<T>::insert(currency_id, pool.clone());
+		BancorReserve::<T>::insert(currency_id, BalanceOf::<T>::from(0u32));
+
+		pool
+	}
+
+	/// Called whenever a pool is touched: release the reserve.
+	///
+	/// Returns the oracle price of vstoken if the pool is guarded by one and a window has been
+	/// completed.
+	pub(crate) fn touch_pool(currency_id: CurrencyId) -> Option<FixedU128> {
+		let oracle_price = Self::price_oracle(currency_id)
+			.map(|oracle| oracle.price)
+			.filter(|price| !price.is_zero());
+		Self::release_reserve(currency_id, oracle_price);
+
+		oracle_price
+	}
+
+	/// Release the reserve to the pool for the epochs elapsed since the last release.
+	///
+	/// If the price of vstoken (token/vstoken) is lower than `InterventionPercentage` of the
+	/// oracle price, or of 1 token if there is no oracle, half of the released token is used to
+	/// buy back vstoken, so that the price of vstoken will increase. The rest is put on the
+	/// ceiling to indicate exchange availability.
+	///
+	/// Nothing is released while the pool is paused, the release catches up once it resumes.
+	pub(crate) fn release_reserve(currency_id: CurrencyId, oracle_price: Option<FixedU128>) {
+		let now = T::UnixTime::now().as_secs();
+		// The time is not available at genesis.
+		if now.is_zero() ||
+			!BancorPools::<T>::contains_key(currency_id) ||
+			Self::is_paused(currency_id)
+		{
+			return;
+		}
+
+		let current_epoch = now / Self::release_epoch();
+		let last_epoch = match Self::last_release_epoch(currency_id) {
+			Some(epoch) => epoch,
+			None => {
+				LastReleaseEpoch::<T>::insert(currency_id, current_epoch);
+				return;
+			},
+		};
+		if current_epoch <= last_epoch {
+			return;
+		}
+		LastReleaseEpoch::<T>::insert(currency_id, current_epoch);
+
+		let reserve = Self::get_bancor_reserve(currency_id).unwrap_or_else(Zero::zero);
+		let token_amount = Self::release_amount(reserve, current_epoch - last_epoch);
+		if token_amount.is_zero() {
+			return;
+		}
+
+		let mut amount_kept = Self::amount_kept_in_ceiling(currency_id, token_amount, oracle_price);
+		let mut sell_amount = token_amount.saturating_sub(amount_kept);
+		if !sell_amount.is_zero() {
+			let sell_result = Self::calculate_price_for_vstoken(currency_id, sell_amount).and_then(
+				|vstoken_amount| {
+					Self::revise_bancor_pool_token_buy_vstoken(
+						currency_id,
+						sell_amount,
+						vstoken_amount,
+					)
+				},
+			);
+			// if somehow not able to sell token, then add the amount to ceiling.
+			if sell_result.is_err() {
+				amount_kept = token_amount;
+				sell_amount = Zero::zero();
+			}
+		}
+
+		if Self::increase_bancor_pool_ceiling(currency_id, amount_kept).is_err() {
+			return;
+		}
+		BancorReserve::<T>::mutate(currency_id, |reserve_option| {
+			if let Some(reserve) = reserve_option {
+				*reserve = reserve.saturating_sub(token_amount);
+			}
+		});
+
+		Self::deposit_event(Event::ReserveReleased(currency_id, amount_kept, sell_amount));
+	}
+
+	/// Forecast the token ceiling of the pool at the end of each of the next `days` days,
+	/// assuming no exchange happens and the price of vstoken stays where it is now. At most
+	/// `MAX_FORECAST_DAYS` days can be forecast.
+	pub fn forecast_token_ceiling(
+		currency_id: CurrencyId,
+		days: u32,
+	) -> Result<Vec<BalanceOf<T>>, Error<T>> {
+		ensure!(days <= MAX_FORECAST_DAYS, Error::<T>::ForecastTooLong);
+		let pool_info = Self::get_bancor_pool(currency_id).ok_or(Error::<T>::BancorPoolNotExist)?;
+		let reserve = Self::get_bancor_reserve(currency_id).unwrap_or_else(Zero::zero);
+		let oracle_price = Self::price_oracle(currency_id)
+			.map(|oracle| oracle.price)
+			.filter(|price| !price.is_zero());
+
+		let now = T::UnixTime::now().as_secs();
+		let epoch_length = Self::release_epoch();
+		let last_epoch = Self::last_release_epoch(currency_id).unwrap_or(now / epoch_length);
+
+		Ok((1..=days)
+			.map(|day| {
+				let epoch =
+					now.saturating_add(SECONDS_PER_DAY.saturating_mul(day as u64)) / epoch_length;
+				let released = Self::release_amount(reserve, epoch.saturating_sub(last_epoch));
+
+				pool_info.token_ceiling.saturating_add(Self::amount_kept_in_ceiling(
+					currency_id,
+					released,
+					oracle_price,
+				))
+			})
+			.collect())
+	}
+
+	fn release_epoch() -> u64 {
+		T::ReleaseEpoch::get().max(1)
+	}
+
+	/// Formula: Reserve * (1 - (1 - DailyReleasePercentage * ReleaseEpoch / Day) ^ Epochs)
+	fn release_amount(reserve: BalanceOf<T>, epochs: u64) -> BalanceOf<T> {
+		let release_per_epoch = FixedU128::saturating_from_rational(
+			(T::DailyReleasePercentage::get().deconstruct() as u128)
+				.saturating_mul(Self::release_epoch() as u128),
+			(Percent::ACCURACY as u128).saturating_mul(SECONDS_PER_DAY as u128),
+		);
+		let remaining = FixedU128::one()
+			.saturating_sub(release_per_epoch)
+			.saturating_pow(epochs.saturated_into::<u32>() as usize);
+
+		reserve.saturating_sub(BalanceOf::<T>::saturated_from(
+			remaining.saturating_mul_int(reserve.saturated_into::<u128>()),
+		))
+	}
+
+	/// The part of the released token that should be put on the ceiling, the rest is sold.
+	fn amount_kept_in_ceiling(
+		currency_id: CurrencyId,
+		token_amount: BalanceOf<T>,
+		oracle_price: Option<FixedU128>,
+	) -> BalanceOf<T> {
+		let intervention_price = oracle_price.unwrap_or_else(FixedU128::one).saturating_mul(
+			FixedU128::saturating_from_rational(
+				T::InterventionPercentage::get().deconstruct(),
+				Percent::ACCURACY,
+			),
 		);
 
-		Ok((BalanceOf::<T>::saturated_from(2u128).saturating_mul(vstoken_supply), token_supply))
+		match Self::instant_vstoken_price(currency_id) {
+			// if vstoken price is lower than 75% of the reference price
+			Some(price) if price <= intervention_price =>
+				token_amount / BalanceOf::<T>::saturated_from(2u128),
+			_ => token_amount,
+		}
+	}
+
+	/// Accumulate the market price held since the last sample, and sample the market price. The
+	/// oracle price is updated once a window is completed, and checked by the circuit breaker.
+	pub(crate) fn sample_market_price(currency_id: CurrencyId) {
+		let mut oracle = match Self::price_oracle(currency_id) {
+			Some(oracle) => oracle,
+			None => return,
+		};
+		let spot_price = T::MarketPrice::spot_price(currency_id);
+		let now = T::UnixTime::now().as_secs();
+
+		if oracle.spot_price.is_zero() {
+			// The window starts with the first sample.
+			if let Some(spot_price) = spot_price {
+				oracle.spot_price = spot_price;
+				oracle.updated_at = now;
+				oracle.window_start = now;
+				PriceOracles::<T>::insert(currency_id, &oracle);
+			}
+			return;
+		}
+
+		let elapsed = now.saturating_sub(oracle.updated_at);
+		oracle.cumulative = oracle.cumulative.saturating_add(
+			oracle.spot_price.saturating_mul(FixedU128::saturating_from_integer(elapsed)),
+		);
+		oracle.updated_at = now;
+		if let Some(spot_price) = spot_price {
+			oracle.spot_price = spot_price;
+		}
+
+		let window = now.saturating_sub(oracle.window_start);
+		if !window.is_zero() && window >= oracle.twap_window {
+			if let Some(price) = oracle
+				.cumulative
+				.saturating_sub(oracle.window_cumulative)
+				.checked_div(&FixedU128::saturating_from_integer(window))
+			{
+				oracle.price = price;
+			}
+			oracle.window_cumulative = oracle.cumulative;
+			oracle.window_start = now;
+		}
+
+		Self::check_circuit_breaker(currency_id, &mut oracle);
+
+		PriceOracles::<T>::insert(currency_id, &oracle);
+	}
+
+	/// Pause the pool if its price deviates more than allowed from the oracle price at
+	/// `CircuitBreakerSamples` consecutive samples.
+	fn check_circuit_breaker(currency_id: CurrencyId, oracle: &mut PriceOracle) {
+		if oracle.price.is_zero() || Self::is_paused(currency_id) {
+			return;
+		}
+
+		let pool_price = match Self::instant_vstoken_price(currency_id) {
+			Some(pool_price) => pool_price,
+			None => return,
+		};
+		let deviation = if pool_price >= oracle.price {
+			pool_price.saturating_sub(oracle.price)
+		} else {
+			oracle.price.saturating_sub(pool_price)
+		};
+		let max_deviation = oracle.price.saturating_mul(FixedU128::saturating_from_rational(
+			oracle.max_deviation.deconstruct(),
+			Perbill::ACCURACY,
+		));
+
+		if deviation > max_deviation {
+			oracle.deviations = oracle.deviations.saturating_add(1);
+		} else {
+			oracle.deviations = 0;
+		}
+
+		if oracle.deviations >= T::CircuitBreakerSamples::get() {
+			oracle.deviations = 0;
+			PausedPools::<T>::insert(currency_id, true);
+			Self::deposit_event(Event::BancorPoolPaused(currency_id, pool_price, oracle.price));
+		}
+	}
+
+	/// The instant price of vstoken, in token.
+	fn instant_vstoken_price(currency_id: CurrencyId) -> Option<FixedU128> {
+		let (nominator, denominator) = Self::get_instant_vstoken_price(currency_id).ok()?;
+
+		FixedU128::checked_from_rational(
+			nominator.saturated_into::<u128>(),
+			denominator.saturated_into::<u128>(),
+		)
 	}
 
 	pub(crate) fn increase_bancor_pool_ceiling(
@@ -531,6 +1023,9 @@ impl<T: Config> BancorHandler<BalanceOf<T>> for Pallet<T> {
 	fn add_token(currency_id: CurrencyId, token_amount: BalanceOf<T>) -> Result<(), DispatchError> {
 		ensure!(token_amount >= Zero::zero(), Error::<T>::AmountNotGreaterThanZero);
 
+		// Release for the elapsed epochs first, so that the newly added token waits for the next.
+		Self::touch_pool(currency_id);
+
 		if token_amount != Zero::zero() {
 			BancorReserve::<T>::mutate(currency_id, |reserve_option| -> Result<(), Error<T>> {
 				match reserve_option {
@@ -0,0 +1,99 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fixed point helpers for the bancor power formula.
+//!
+//! `base ^ exp = 2 ^ (exp * log2(base))`, where `log2` is calculated bit by bit and `2 ^ frac` is
+//! expanded as the taylor series of `e ^ (frac * ln2)`.
+
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+const ONE: u128 = 1_000_000_000_000_000_000;
+/// ln(2) in the accuracy of `FixedU128`.
+const LN_2: u128 = 693_147_180_559_945_309;
+
+/// Returns `(is_negative, |log2(x)|)` of the fixed point `x`.
+fn log2(x: u128) -> Option<(bool, u128)> {
+	if x == 0 {
+		return None;
+	}
+
+	// Work on the reciprocal when x < 1, since log2(x) = -log2(1 / x).
+	let (is_negative, mut y) = if x < ONE { (true, ONE.checked_mul(ONE)? / x) } else { (false, x) };
+
+	let mut result: u128 = 0;
+	while y >= 2 * ONE {
+		y /= 2;
+		result += ONE;
+	}
+
+	// Now y is in [1, 2), square it to get the fractional bits one by one.
+	let mut bit = ONE / 2;
+	while bit > 0 {
+		y = y * y / ONE;
+		if y >= 2 * ONE {
+			y /= 2;
+			result += bit;
+		}
+		bit /= 2;
+	}
+
+	Some((is_negative, result))
+}
+
+/// Returns `2 ^ y` or `2 ^ -y` of the fixed point `y`.
+fn exp2(is_negative: bool, y: u128) -> Option<u128> {
+	let integer = y / ONE;
+	let fraction = y % ONE;
+
+	// 2 ^ fraction = e ^ (fraction * ln2), where fraction * ln2 < ln2 so that the series
+	// converges quickly.
+	let z = fraction * LN_2 / ONE;
+	let mut sum = ONE;
+	let mut term = ONE;
+	let mut k: u128 = 1;
+	while term > 0 {
+		term = term * z / ONE / k;
+		sum += term;
+		k += 1;
+	}
+
+	if is_negative {
+		if integer >= 128 {
+			return Some(0);
+		}
+		Some((ONE * ONE / sum) >> integer)
+	} else {
+		if integer >= 128 {
+			return None;
+		}
+		sum.checked_mul(1u128 << integer)
+	}
+}
+
+/// Returns `base ^ exp`, or `None` if the result overflows.
+pub fn pow(base: FixedU128, exp: FixedU128) -> Option<FixedU128> {
+	if base.is_zero() {
+		return Some(if exp.is_zero() { FixedU128::one() } else { FixedU128::zero() });
+	}
+
+	let (is_negative, log) = log2(base.into_inner())?;
+	let product = FixedU128::from_inner(log).checked_mul(&exp)?;
+
+	exp2(is_negative, product.into_inner()).map(FixedU128::from_inner)
+}
@@ -18,18 +18,21 @@
 
 #![cfg(test)]
 
+use std::cell::RefCell;
+
 #[cfg(feature = "runtime-benchmarks")]
 use frame_benchmarking::whitelisted_caller;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{GenesisBuild, Nothing, OnFinalize, OnInitialize},
+	traits::{GenesisBuild, Hooks, Nothing},
 };
+use frame_system::EnsureRoot;
 pub use node_primitives::{Balance, CurrencyId, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	AccountId32, Percent,
+	AccountId32, FixedU128, Percent,
 };
 
 use crate as bancor;
@@ -41,8 +44,13 @@ pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const VSDOT: CurrencyId = CurrencyId::VSToken(TokenSymbol::DOT);
 pub const KSM: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
 pub const VSKSM: CurrencyId = CurrencyId::VSToken(TokenSymbol::KSM);
+pub const ETH: CurrencyId = CurrencyId::Token(TokenSymbol::ETH);
+pub const VSETH: CurrencyId = CurrencyId::VSToken(TokenSymbol::ETH);
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
+// One hour in milliseconds, which is also the length of a release epoch.
+pub const SECOND: u64 = 1_000;
+pub const HOUR: u64 = 60 * 60 * SECOND;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -54,6 +62,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Tokens: orml_tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Bancor: bancor::{Pallet, Call, Config<T>, Storage, Event<T>},
 	}
@@ -91,6 +100,17 @@ impl frame_system::Config for Test {
 	type Version = ();
 }
 
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+	type MinimumPeriod = MinimumPeriod;
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type WeightInfo = ();
+}
+
 orml_traits::parameter_type_with_key! {
 	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
 		0
@@ -116,6 +136,24 @@ impl orml_tokens::Config for Test {
 parameter_types! {
 	pub const InterventionPercentage: Percent = Percent::from_percent(75);
 	pub const DailyReleasePercentage: Percent = Percent::from_percent(5);
+	pub const ReleaseEpoch: u64 = 60 * 60;
+	pub const CircuitBreakerSamples: u32 = 3;
+}
+
+thread_local! {
+	static MARKET_PRICE: RefCell<Option<FixedU128>> = RefCell::new(None);
+}
+
+pub struct MockMarketPrice;
+impl MockMarketPrice {
+	pub fn set(price: Option<FixedU128>) {
+		MARKET_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl bancor::MarketPriceProvider for MockMarketPrice {
+	fn spot_price(_currency_id: CurrencyId) -> Option<FixedU128> {
+		MARKET_PRICE.with(|v| *v.borrow())
+	}
 }
 
 impl bancor::Config for Test {
@@ -123,6 +161,11 @@ impl bancor::Config for Test {
 	type InterventionPercentage = InterventionPercentage;
 	type DailyReleasePercentage = DailyReleasePercentage;
 	type MultiCurrency = Tokens;
+	type ControlOrigin = EnsureRoot<AccountId>;
+	type ReleaseEpoch = ReleaseEpoch;
+	type UnixTime = Timestamp;
+	type MarketPrice = MockMarketPrice;
+	type CircuitBreakerSamples = CircuitBreakerSamples;
 	type WeightInfo = ();
 }
 
@@ -200,13 +243,8 @@ impl ExtBuilder {
 	}
 }
 
-// simulate block production
-pub(crate) fn run_to_block(n: u64) {
-	while System::block_number() < n {
-		Bancor::on_finalize(System::block_number());
-		System::on_finalize(System::block_number());
-		System::set_block_number(System::block_number() + 1);
-		System::on_initialize(System::block_number());
-		Bancor::on_initialize(System::block_number());
-	}
+/// Sample the market price at the moment, as the start of a block does.
+pub(crate) fn sample_at(moment: u64) {
+	Timestamp::set_timestamp(moment);
+	Bancor::on_initialize(System::block_number());
 }
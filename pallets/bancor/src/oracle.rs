@@ -0,0 +1,53 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! The market price of vsToken, which the bancor pools may be guarded by.
+
+use node_primitives::{CurrencyId, TokenInfo};
+use sp_runtime::{FixedPointNumber, FixedU128};
+use sp_std::{convert::TryFrom, marker::PhantomData};
+use zenlink_protocol::{AssetId, ExportZenlink};
+
+/// The source of the market price of vsToken.
+pub trait MarketPriceProvider {
+	/// The spot price of the vsToken of `currency_id`, in token.
+	fn spot_price(currency_id: CurrencyId) -> Option<FixedU128>;
+}
+
+impl MarketPriceProvider for () {
+	fn spot_price(_currency_id: CurrencyId) -> Option<FixedU128> {
+		None
+	}
+}
+
+/// Quotes the price of vsToken by selling one unit of it in the Zenlink pair of vsToken and token.
+pub struct ZenlinkMarketPrice<AccountId, Dex>(PhantomData<(AccountId, Dex)>);
+
+impl<AccountId, Dex> MarketPriceProvider for ZenlinkMarketPrice<AccountId, Dex>
+where
+	Dex: ExportZenlink<AccountId>,
+{
+	fn spot_price(currency_id: CurrencyId) -> Option<FixedU128> {
+		let vstoken_id = currency_id.to_vstoken().ok()?;
+		let path = [AssetId::try_from(vstoken_id).ok()?, AssetId::try_from(currency_id).ok()?];
+
+		let unit = 10u128.checked_pow(currency_id.decimals() as u32)?;
+		let amount_out = Dex::get_amount_out_by_path(unit, &path).ok()?.last().copied()?;
+
+		FixedU128::checked_from_rational(amount_out, unit)
+	}
+}
@@ -18,9 +18,16 @@
 
 #![cfg(test)]
 
-use frame_support::{assert_noop, assert_ok};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchError,
+	storage::unhashed,
+	traits::{Get, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::{FixedPointNumber, FixedU128};
 
-use crate::{mock::*, *};
+use crate::{math, mock::*, *};
 
 #[test]
 fn add_token_to_pool_should_work() {
@@ -48,7 +55,8 @@ fn exchange_for_token_should_work() {
 				vstoken_pool: 0,
 				token_ceiling: 0,
 				token_base_supply: 2 * VSKSM_BASE_SUPPLY,
-				vstoken_base_supply: VSKSM_BASE_SUPPLY
+				vstoken_base_supply: VSKSM_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 		assert_eq!(
@@ -59,7 +67,8 @@ fn exchange_for_token_should_work() {
 				vstoken_pool: 0,
 				token_ceiling: 0,
 				token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-				vstoken_base_supply: VSDOT_BASE_SUPPLY
+				vstoken_base_supply: VSDOT_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 
@@ -76,6 +85,7 @@ fn exchange_for_token_should_work() {
 			token_ceiling: 100,
 			token_base_supply: dot_pool.token_base_supply,
 			vstoken_base_supply: dot_pool.vstoken_base_supply,
+			reserve_ratio: dot_pool.reserve_ratio,
 		};
 
 		// add some DOTs to the pool
@@ -99,7 +109,8 @@ fn exchange_for_token_should_work() {
 				vstoken_pool: 50,
 				token_ceiling: 51,
 				token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-				vstoken_base_supply: VSDOT_BASE_SUPPLY
+				vstoken_base_supply: VSDOT_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 
@@ -124,7 +135,8 @@ fn exchange_for_vstoken_should_work() {
 				vstoken_pool: 0,
 				token_ceiling: 0,
 				token_base_supply: 2 * VSKSM_BASE_SUPPLY,
-				vstoken_base_supply: VSKSM_BASE_SUPPLY
+				vstoken_base_supply: VSKSM_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 		assert_eq!(
@@ -135,7 +147,8 @@ fn exchange_for_vstoken_should_work() {
 				vstoken_pool: 0,
 				token_ceiling: 0,
 				token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-				vstoken_base_supply: VSDOT_BASE_SUPPLY
+				vstoken_base_supply: VSDOT_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 
@@ -152,6 +165,7 @@ fn exchange_for_vstoken_should_work() {
 			token_ceiling: 0,
 			token_base_supply: dot_pool.token_base_supply,
 			vstoken_base_supply: dot_pool.vstoken_base_supply,
+			reserve_ratio: dot_pool.reserve_ratio,
 		};
 
 		// add some VSDOTs to the pool
@@ -165,7 +179,8 @@ fn exchange_for_vstoken_should_work() {
 				vstoken_pool: 50,
 				token_ceiling: 0,
 				token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-				vstoken_base_supply: VSDOT_BASE_SUPPLY
+				vstoken_base_supply: VSDOT_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 
@@ -187,7 +202,8 @@ fn exchange_for_vstoken_should_work() {
 				vstoken_pool: 1,
 				token_ceiling: 0,
 				token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-				vstoken_base_supply: VSDOT_BASE_SUPPLY
+				vstoken_base_supply: VSDOT_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
 			}
 		);
 
@@ -203,13 +219,14 @@ fn add_token_should_work() {
 		.thousand_thousand_for_alice_n_bob()
 		.build()
 		.execute_with(|| {
-			// At the beginning, the price is 1:1, so all the released token should be put into
-			// ceiling.
-			run_to_block(10);
+			System::set_block_number(1);
+
+			// The first touch of the pool only starts the release schedule.
+			Timestamp::set_timestamp(1_000 * HOUR);
 			assert_ok!(Bancor::add_token(DOT, 20000000000));
 
-			let mut dot_reserve = Bancor::get_bancor_reserve(DOT).unwrap();
-			assert_eq!(dot_reserve, 20000000000);
+			assert_eq!(Bancor::get_bancor_reserve(DOT), Some(20000000000));
+			assert_eq!(Bancor::last_release_epoch(DOT), Some(1_000));
 
 			let dot_pool = Bancor::get_bancor_pool(DOT).unwrap();
 			assert_eq!(
@@ -220,18 +237,29 @@ fn add_token_should_work() {
 					vstoken_pool: 0,
 					token_ceiling: 0,
 					token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-					vstoken_base_supply: VSDOT_BASE_SUPPLY
+					vstoken_base_supply: VSDOT_BASE_SUPPLY,
+					reserve_ratio: Perbill::from_percent(50)
 				}
 			);
 
-			run_to_block(11);
-			// price is not lower than 75%, so the released DOT will be put into the ceiling
-			// variable
-			dot_reserve = Bancor::get_bancor_reserve(DOT).unwrap();
-			assert_eq!(dot_reserve, 19999861112);
+			// Nothing is released within the same epoch.
+			Timestamp::set_timestamp(1_000 * HOUR + HOUR / 2);
+			assert_ok!(Bancor::add_token(DOT, 0));
+			assert_eq!(Bancor::get_bancor_reserve(DOT), Some(20000000000));
+			assert_eq!(Bancor::get_bancor_pool(DOT).unwrap().token_ceiling, 0);
+
+			// 5% / 24 of the reserve is released per epoch. At the beginning, the price is 1:1, so
+			// all the released token should be put into ceiling.
+			Timestamp::set_timestamp(1_001 * HOUR);
+			assert_ok!(Bancor::add_token(DOT, 0));
+			assert_eq!(Bancor::get_bancor_reserve(DOT), Some(19958333333));
+			assert_eq!(Bancor::last_release_epoch(DOT), Some(1_001));
 
 			let dot_pool_ceiling = Bancor::get_bancor_pool(DOT).unwrap().token_ceiling;
-			assert_eq!(dot_pool_ceiling, 138888);
+			assert_eq!(dot_pool_ceiling, 41666667);
+			System::assert_last_event(mock::Event::Bancor(crate::Event::ReserveReleased(
+				DOT, 41666667, 0,
+			)));
 
 			// if someone buys a lot of tokens, the price of token will dramatically increase and
 			// the price of vstoken will decrease. Here 120000 vsDOT can only exchange for 52111
@@ -248,16 +276,18 @@ fn add_token_should_work() {
 					currency_id: CurrencyId::Token(TokenSymbol::DOT),
 					token_pool: price,
 					vstoken_pool: 120000,
-					token_ceiling: 138888 - price,
+					token_ceiling: 41666667 - price,
 					token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-					vstoken_base_supply: VSDOT_BASE_SUPPLY
+					vstoken_base_supply: VSDOT_BASE_SUPPLY,
+					reserve_ratio: Perbill::from_percent(50)
 				}
 			);
 
 			// revise the reserve so that 100 DOT can be released for the convinience.
-			BancorReserve::<Test>::insert(DOT, 14400000);
+			BancorReserve::<Test>::insert(DOT, 48000);
 
-			run_to_block(12);
+			Timestamp::set_timestamp(1_002 * HOUR);
+			Bancor::touch_pool(DOT);
 			// half of the released 100 DOT will be put into ceiling, while the other half will be
 			// sold within the bancor pool.
 			let price = Bancor::calculate_price_for_vstoken(DOT, 50).unwrap();
@@ -268,10 +298,427 @@ fn add_token_should_work() {
 					currency_id: CurrencyId::Token(TokenSymbol::DOT),
 					token_pool: 52111 - 50,
 					vstoken_pool: 120000 - price,
-					token_ceiling: 86777 + 50,
+					token_ceiling: 41666667 - 52111 + 50,
 					token_base_supply: 2 * VSDOT_BASE_SUPPLY,
-					vstoken_base_supply: VSDOT_BASE_SUPPLY
+					vstoken_base_supply: VSDOT_BASE_SUPPLY,
+					reserve_ratio: Perbill::from_percent(50)
 				}
 			);
+			assert_eq!(Bancor::get_bancor_reserve(DOT), Some(47900));
+		});
+}
+
+#[test]
+fn release_reserve_should_catch_up_with_elapsed_epochs() {
+	ExtBuilder::default().build().execute_with(|| {
+		// The time is not available at genesis, nothing happens.
+		assert_ok!(Bancor::add_token(KSM, 1_000_000));
+		assert_eq!(Bancor::last_release_epoch(KSM), None);
+
+		Timestamp::set_timestamp(10 * HOUR);
+		Bancor::touch_pool(KSM);
+		assert_eq!(Bancor::last_release_epoch(KSM), Some(10));
+
+		// Releasing lazily after a day releases for all the 24 epochs elapsed.
+		let forecast = Bancor::forecast_token_ceiling(KSM, 2).unwrap();
+		assert_eq!(forecast, vec![48821, 95257]);
+
+		Timestamp::set_timestamp(34 * HOUR);
+		Bancor::touch_pool(KSM);
+
+		let ceiling = Bancor::get_bancor_pool(KSM).unwrap().token_ceiling;
+		assert_eq!(ceiling, 48821);
+		assert_eq!(Bancor::get_bancor_reserve(KSM), Some(1_000_000 - 48821));
+		assert_eq!(Bancor::last_release_epoch(KSM), Some(34));
+	});
+}
+
+#[test]
+fn forecast_token_ceiling_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(Bancor::forecast_token_ceiling(ETH, 1).is_err());
+		assert!(matches!(
+			Bancor::forecast_token_ceiling(KSM, MAX_FORECAST_DAYS + 1),
+			Err(Error::<Test>::ForecastTooLong)
+		));
+
+		Timestamp::set_timestamp(10 * HOUR);
+		assert_eq!(Bancor::forecast_token_ceiling(KSM, 3).unwrap(), vec![0, 0, 0]);
+
+		assert_ok!(Bancor::add_token(KSM, 1_000_000));
+		// Roughly 5% of the reserve is released per day.
+		assert_eq!(Bancor::forecast_token_ceiling(KSM, 3).unwrap(), vec![48821, 95257, 139427]);
+	});
+}
+
+#[test]
+fn create_bancor_pool_should_work() {
+	ExtBuilder::default().one_thousand_for_alice_n_bob().build().execute_with(|| {
+		assert_noop!(
+			Bancor::create_bancor_pool(
+				Origin::signed(ALICE),
+				ETH,
+				10_000,
+				Perbill::from_percent(20)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Bancor::create_bancor_pool(Origin::root(), DOT, 10_000, Perbill::from_percent(20)),
+			Error::<Test>::BancorPoolAlreadyExist
+		);
+		assert_noop!(
+			Bancor::create_bancor_pool(Origin::root(), VSETH, 10_000, Perbill::from_percent(20)),
+			Error::<Test>::NotSupportTokenType
+		);
+		assert_noop!(
+			Bancor::create_bancor_pool(Origin::root(), ETH, 10_000, Perbill::zero()),
+			Error::<Test>::InvalidReserveRatio
+		);
+
+		assert_ok!(Bancor::create_bancor_pool(
+			Origin::root(),
+			ETH,
+			10_000,
+			Perbill::from_percent(20)
+		));
+
+		assert_eq!(
+			Bancor::get_bancor_pool(ETH).unwrap(),
+			BancorPool {
+				currency_id: ETH,
+				token_pool: 0,
+				vstoken_pool: 0,
+				token_ceiling: 0,
+				token_base_supply: 50_000,
+				vstoken_base_supply: 10_000,
+				reserve_ratio: Perbill::from_percent(20)
+			}
+		);
+		assert_eq!(Bancor::get_bancor_reserve(ETH), Some(0));
+
+		// The price of vstoken starts at 1 token.
+		assert_eq!(Bancor::get_instant_vstoken_price(ETH).unwrap(), (10_000, 10_000));
+	});
+}
+
+#[test]
+fn exchange_with_configured_reserve_ratio_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, ETH, 1_000), (ALICE, VSETH, 1_000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Bancor::create_bancor_pool(
+				Origin::root(),
+				ETH,
+				10_000,
+				Perbill::from_percent(20)
+			));
+			assert_ok!(Bancor::increase_bancor_pool_ceiling(ETH, 2_000));
+
+			// 50000 * ((1 + 1000 / 10000) ^ 0.2 - 1) = 962.24
+			assert_ok!(Bancor::exchange_for_token(Origin::signed(ALICE), ETH, 1_000, 962));
+			let eth_pool = Bancor::get_bancor_pool(ETH).unwrap();
+			assert_eq!(eth_pool.token_pool, 962);
+			assert_eq!(eth_pool.vstoken_pool, 1_000);
+			assert_eq!(eth_pool.token_ceiling, 1_038);
+
+			// 11000 * (1 - (1 - 500 / 50962) ^ 5) = 529.13
+			assert_ok!(Bancor::exchange_for_vstoken(Origin::signed(ALICE), ETH, 500, 529));
+			let eth_pool = Bancor::get_bancor_pool(ETH).unwrap();
+			assert_eq!(eth_pool.token_pool, 462);
+			assert_eq!(eth_pool.vstoken_pool, 471);
+
+			assert_eq!(Tokens::free_balance(ETH, &ALICE), 1_462);
+			assert_eq!(Tokens::free_balance(VSETH, &ALICE), 529);
 		});
 }
+
+#[test]
+fn retire_bancor_pool_should_work() {
+	ExtBuilder::default().one_thousand_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(Bancor::add_token_to_pool(Origin::signed(ALICE), DOT, 500));
+		assert_ok!(Bancor::increase_bancor_pool_ceiling(DOT, 100));
+		assert_ok!(Bancor::exchange_for_token(Origin::signed(ALICE), DOT, 50, 48));
+
+		assert_noop!(
+			Bancor::retire_bancor_pool(Origin::signed(ALICE), DOT, BOB),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Bancor::retire_bancor_pool(Origin::root(), ETH, BOB),
+			Error::<Test>::BancorPoolNotExist
+		);
+
+		assert_ok!(Bancor::retire_bancor_pool(Origin::root(), DOT, BOB));
+
+		assert!(Bancor::get_bancor_pool(DOT).is_none());
+		assert!(Bancor::get_bancor_reserve(DOT).is_none());
+
+		// reserve(500) + ceiling(100 - 49) DOT, and the 50 VSDOT in the pool
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_551);
+		assert_eq!(Tokens::free_balance(VSDOT, &BOB), 1_050);
+
+		assert_noop!(Bancor::add_token(DOT, 100), Error::<Test>::BancorPoolNotExist);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_work() {
+	#[derive(Encode)]
+	struct OldBancorPool {
+		currency_id: CurrencyId,
+		token_pool: Balance,
+		vstoken_pool: Balance,
+		token_ceiling: Balance,
+		token_base_supply: Balance,
+		vstoken_base_supply: Balance,
+	}
+
+	ExtBuilder::default().build().execute_with(|| {
+		let old_pool = OldBancorPool {
+			currency_id: KSM,
+			token_pool: 1,
+			vstoken_pool: 2,
+			token_ceiling: 3,
+			token_base_supply: 2 * VSKSM_BASE_SUPPLY,
+			vstoken_base_supply: VSKSM_BASE_SUPPLY,
+		};
+		unhashed::put(&BancorPools::<Test>::hashed_key_for(KSM), &old_pool);
+		StorageVersion::new(0).put::<Bancor>();
+
+		migration::migrate_to_v1::<Test>();
+
+		assert_eq!(StorageVersion::get::<Bancor>(), 1);
+		assert_eq!(
+			Bancor::get_bancor_pool(KSM).unwrap(),
+			BancorPool {
+				currency_id: KSM,
+				token_pool: 1,
+				vstoken_pool: 2,
+				token_ceiling: 3,
+				token_base_supply: 2 * VSKSM_BASE_SUPPLY,
+				vstoken_base_supply: VSKSM_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
+			}
+		);
+	});
+}
+
+#[test]
+fn create_bancor_pools_should_only_create_the_missing_ones() {
+	struct Pools;
+	impl Get<Vec<(CurrencyId, Balance)>> for Pools {
+		fn get() -> Vec<(CurrencyId, Balance)> {
+			vec![(KSM, 2 * VSKSM_BASE_SUPPLY), (DOT, VSDOT_BASE_SUPPLY)]
+		}
+	}
+
+	ExtBuilder::default().build().execute_with(|| {
+		let ksm_pool = Bancor::get_bancor_pool(KSM).unwrap();
+		BancorPools::<Test>::remove(DOT);
+		BancorReserve::<Test>::remove(DOT);
+
+		migration::CreateBancorPools::<Test, Pools>::on_runtime_upgrade();
+
+		assert_eq!(Bancor::get_bancor_pool(KSM).unwrap(), ksm_pool);
+		assert_eq!(
+			Bancor::get_bancor_pool(DOT).unwrap(),
+			BancorPool {
+				currency_id: DOT,
+				token_pool: 0,
+				vstoken_pool: 0,
+				token_ceiling: 0,
+				token_base_supply: 2 * VSDOT_BASE_SUPPLY,
+				vstoken_base_supply: VSDOT_BASE_SUPPLY,
+				reserve_ratio: Perbill::from_percent(50)
+			}
+		);
+		assert_eq!(Bancor::get_bancor_reserve(DOT), Some(0));
+	});
+}
+
+#[test]
+fn pow_should_work() {
+	let sqrt_2 = math::pow(FixedU128::from(2), FixedU128::saturating_from_rational(1, 2)).unwrap();
+	assert_eq!(sqrt_2.into_inner() / 1_000_000, 1_414_213_562_373);
+
+	let cube = math::pow(FixedU128::saturating_from_rational(1, 3), FixedU128::from(3)).unwrap();
+	assert_eq!(cube.into_inner() / 1_000_000, 37_037_037_037);
+
+	assert_eq!(math::pow(FixedU128::zero(), FixedU128::one()), Some(FixedU128::zero()));
+	assert_eq!(math::pow(FixedU128::from(u64::MAX as u128), FixedU128::from(2)), None);
+}
+
+#[test]
+fn set_price_oracle_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Bancor::set_price_oracle(Origin::signed(ALICE), DOT, 3_600, Perbill::from_percent(20)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Bancor::set_price_oracle(Origin::root(), ETH, 3_600, Perbill::from_percent(20)),
+			Error::<Test>::BancorPoolNotExist
+		);
+		assert_noop!(
+			Bancor::set_price_oracle(Origin::root(), DOT, 3_600, Perbill::zero()),
+			Error::<Test>::InvalidMaxDeviation
+		);
+
+		assert_ok!(Bancor::set_price_oracle(Origin::root(), DOT, 3_600, Perbill::from_percent(20)));
+		assert_eq!(
+			Bancor::price_oracle(DOT),
+			Some(PriceOracle {
+				twap_window: 3_600,
+				max_deviation: Perbill::from_percent(20),
+				..Default::default()
+			})
+		);
+
+		assert_ok!(Bancor::remove_price_oracle(Origin::root(), DOT));
+		assert_eq!(Bancor::price_oracle(DOT), None);
+		assert_noop!(
+			Bancor::remove_price_oracle(Origin::root(), DOT),
+			Error::<Test>::PriceOracleNotExist
+		);
+	});
+}
+
+#[test]
+fn oracle_price_should_be_time_weighted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Bancor::set_price_oracle(Origin::root(), DOT, 3_600, Perbill::one()));
+
+		// There is no oracle price until the first window is completed.
+		MockMarketPrice::set(Some(FixedU128::one()));
+		sample_at(10 * HOUR);
+		assert_eq!(Bancor::touch_pool(DOT), None);
+
+		MockMarketPrice::set(Some(FixedU128::saturating_from_integer(2)));
+		sample_at(10 * HOUR + HOUR / 2);
+		assert_eq!(Bancor::touch_pool(DOT), None);
+
+		// Each sample is weighted by the time it held for, the last sample is kept if the market
+		// is not available.
+		MockMarketPrice::set(None);
+		sample_at(11 * HOUR);
+		assert_eq!(Bancor::touch_pool(DOT), Some(FixedU128::saturating_from_rational(3, 2)));
+		assert_eq!(Bancor::price_oracle(DOT).unwrap().updated_at, 39_600);
+
+		// A new sample doesn't move the average until it has held for a while.
+		MockMarketPrice::set(Some(FixedU128::saturating_from_rational(1, 2)));
+		sample_at(12 * HOUR);
+		assert_eq!(Bancor::touch_pool(DOT), Some(FixedU128::saturating_from_integer(2)));
+
+		// Without an oracle, there is no oracle price.
+		assert_eq!(Bancor::touch_pool(KSM), None);
+	});
+}
+
+#[test]
+fn circuit_breaker_should_pause_and_resume_pool() {
+	ExtBuilder::default().one_thousand_for_alice_n_bob().build().execute_with(|| {
+		System::set_block_number(1);
+		BancorPools::<Test>::mutate(DOT, |pool| pool.as_mut().unwrap().token_ceiling = 1_000);
+		assert_ok!(Bancor::set_price_oracle(Origin::root(), DOT, 0, Perbill::from_percent(20)));
+
+		MockMarketPrice::set(Some(FixedU128::one()));
+		sample_at(10 * HOUR);
+
+		// A deviation which doesn't hold for 3 samples doesn't trip the circuit breaker.
+		MockMarketPrice::set(Some(FixedU128::saturating_from_rational(1, 2)));
+		sample_at(10 * HOUR + SECOND);
+		MockMarketPrice::set(Some(FixedU128::one()));
+		sample_at(10 * HOUR + 2 * SECOND);
+		assert_eq!(Bancor::price_oracle(DOT).unwrap().deviations, 1);
+		sample_at(10 * HOUR + 3 * SECOND);
+		assert_eq!(Bancor::price_oracle(DOT).unwrap().deviations, 0);
+		assert!(!Bancor::is_paused(DOT));
+
+		// The price of the pool is 1 token, which deviates 50% from the market for 3 samples.
+		MockMarketPrice::set(Some(FixedU128::saturating_from_rational(1, 2)));
+		sample_at(10 * HOUR + 4 * SECOND);
+		sample_at(10 * HOUR + 5 * SECOND);
+		sample_at(10 * HOUR + 6 * SECOND);
+		assert!(!Bancor::is_paused(DOT));
+		sample_at(10 * HOUR + 7 * SECOND);
+		assert!(Bancor::is_paused(DOT));
+		System::assert_last_event(mock::Event::Bancor(crate::Event::BancorPoolPaused(
+			DOT,
+			FixedU128::one(),
+			FixedU128::saturating_from_rational(1, 2),
+		)));
+		assert_eq!(
+			Bancor::exchange_for_token(Origin::signed(ALICE), DOT, 100, 0),
+			Err(Error::<Test>::BancorPoolPaused.into())
+		);
+
+		// The pool stays paused even though the market recovers.
+		MockMarketPrice::set(Some(FixedU128::one()));
+		sample_at(10 * HOUR + 8 * SECOND);
+		sample_at(10 * HOUR + 9 * SECOND);
+		assert_eq!(
+			Bancor::exchange_for_vstoken(Origin::signed(ALICE), DOT, 100, 0),
+			Err(Error::<Test>::BancorPoolPaused.into())
+		);
+
+		assert_noop!(
+			Bancor::resume_bancor_pool(Origin::signed(ALICE), DOT),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Bancor::resume_bancor_pool(Origin::root(), DOT));
+		assert!(!Bancor::is_paused(DOT));
+		assert_noop!(
+			Bancor::resume_bancor_pool(Origin::root(), DOT),
+			Error::<Test>::BancorPoolNotPaused
+		);
+
+		assert_ok!(Bancor::exchange_for_token(Origin::signed(ALICE), DOT, 100, 0));
+	});
+}
+
+#[test]
+fn exchange_for_token_should_be_bounded_by_market() {
+	ExtBuilder::default().one_thousand_for_alice_n_bob().build().execute_with(|| {
+		BancorPools::<Test>::mutate(DOT, |pool| pool.as_mut().unwrap().token_ceiling = 1_000);
+		assert_ok!(Bancor::set_price_oracle(Origin::root(), DOT, 0, Perbill::from_percent(20)));
+
+		// 100 vsDOT is worth 90 DOT on the market, while the pool pays 99 DOT.
+		MockMarketPrice::set(Some(FixedU128::saturating_from_rational(9, 10)));
+		sample_at(10 * HOUR);
+		sample_at(10 * HOUR + SECOND);
+		assert_eq!(Bancor::calculate_price_for_token(DOT, 100).unwrap(), 99);
+		assert_eq!(
+			Bancor::exchange_for_token(Origin::signed(ALICE), DOT, 100, 0),
+			Err(Error::<Test>::ExchangePriceAboveMarket.into())
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000);
+		assert!(!Bancor::is_paused(DOT));
+
+		MockMarketPrice::set(Some(FixedU128::one()));
+		sample_at(10 * HOUR + 2 * SECOND);
+		sample_at(10 * HOUR + 3 * SECOND);
+		assert_ok!(Bancor::exchange_for_token(Origin::signed(ALICE), DOT, 100, 0));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_099);
+	});
+}
+
+#[test]
+fn intervention_should_refer_to_oracle_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		Timestamp::set_timestamp(10 * HOUR);
+		assert_ok!(Bancor::add_token(KSM, 1_000_000));
+
+		// The price of the pool is lower than 75% of the market, half of the released token will
+		// be used to buy back vstoken.
+		assert_ok!(Bancor::set_price_oracle(Origin::root(), KSM, 0, Perbill::from_percent(50)));
+		MockMarketPrice::set(Some(FixedU128::saturating_from_rational(3, 2)));
+		sample_at(10 * HOUR);
+		sample_at(10 * HOUR + SECOND);
+		assert_eq!(Bancor::touch_pool(KSM), Some(FixedU128::saturating_from_rational(3, 2)));
+		assert!(!Bancor::is_paused(KSM));
+
+		assert_eq!(Bancor::forecast_token_ceiling(KSM, 2).unwrap(), vec![24410, 47628]);
+	});
+}
@@ -30,10 +30,16 @@ pub trait WeightInfo {
 	fn add_token_to_pool() -> Weight;
 	fn exchange_for_token() -> Weight;
 	fn exchange_for_vstoken() -> Weight;
-	fn on_initialize() -> Weight;
+	fn create_bancor_pool() -> Weight;
+	fn retire_bancor_pool() -> Weight;
+	fn set_price_oracle() -> Weight;
+	fn remove_price_oracle() -> Weight;
+	fn resume_bancor_pool() -> Weight;
+	fn on_initialize(n: u32) -> Weight;
 }
 
-// For backwards compatibility and tests
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl WeightInfo for () {
 	fn add_token_to_pool() -> Weight {
 		(50_000_000 as Weight)
@@ -47,7 +53,41 @@ impl WeightInfo for () {
 		(50_000_000 as Weight)
 	}
 
-	fn on_initialize() -> Weight {
+	fn create_bancor_pool() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn retire_bancor_pool() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+
+	fn set_price_oracle() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn remove_price_oracle() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn resume_bancor_pool() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+
+	fn on_initialize(n: u32) -> Weight {
 		(50_000_000 as Weight)
+			.saturating_add((100_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
 	}
 }
@@ -19,7 +19,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use node_primitives::{Balance, CurrencyId};
+use node_primitives::{Balance, CurrencyId, FeeQuote};
 use sp_api::decl_runtime_apis;
 
 decl_runtime_apis! {
@@ -28,5 +28,12 @@ decl_runtime_apis! {
 	{
 		/// get flexible fee token and amount to be deducted
 		fn get_fee_token_and_amount(who: AccountId, fee: Balance) -> (CurrencyId, Balance);
+
+		/// dry-run every fee charged on the extrinsic if `who` signs it
+		fn query_fee_quote(
+			who: AccountId,
+			uxt: Block::Extrinsic,
+			len: u32,
+		) -> FeeQuote<AccountId, CurrencyId, Balance>;
 	}
 }
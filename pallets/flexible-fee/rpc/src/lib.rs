@@ -22,8 +22,9 @@ pub use bifrost_flexible_fee_rpc_runtime_api::FlexibleFeeRuntimeApi as FeeRuntim
 use codec::{Codec, Decode};
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result as JsonRpcResult};
 use jsonrpc_derive::rpc;
-use node_primitives::{Balance, CurrencyId};
+use node_primitives::{Balance, CurrencyId, FeeQuote};
 pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
+use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
@@ -45,6 +46,19 @@ impl<C, Block> FlexibleFeeStruct<C, Block> {
 	}
 }
 
+/// The fee quote of an extrinsic, with the balances in the RPC representation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeQuote<AccountId> {
+	pub base_fee: NumberOrHex,
+	pub tip: NumberOrHex,
+	pub fee: Option<(CurrencyId, NumberOrHex)>,
+	pub sponsor: Option<AccountId>,
+	pub extra_fee: Option<(CurrencyId, NumberOrHex)>,
+	pub balances_after: Vec<(CurrencyId, NumberOrHex)>,
+	pub payable: bool,
+}
+
 #[rpc]
 pub trait FeeRpcApi<BlockHash, AccountId> {
 	/// rpc method get balances by account id
@@ -56,6 +70,16 @@ pub trait FeeRpcApi<BlockHash, AccountId> {
 		encoded_xt: Bytes,
 		at: Option<BlockHash>,
 	) -> JsonRpcResult<(CurrencyId, NumberOrHex)>;
+
+	/// Dry-run the extrinsic signed by `who`, and report the base fee, the tip, the extra fee,
+	/// the currency each is paid in, and the balances of `who` afterwards.
+	#[rpc(name = "flexibleFee_queryFeeQuote")]
+	fn query_fee_quote(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<RpcFeeQuote<AccountId>>;
 }
 
 /// Error type of this RPC api.
@@ -81,7 +105,7 @@ where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 	C::Api: FeeRuntimeApi<Block, AccountId> + TransactionPaymentRuntimeApi<Block, Balance>,
-	AccountId: Codec,
+	AccountId: Codec + Serialize,
 	Balance: Codec + std::fmt::Display + std::ops::Add<Output = Balance> + sp_runtime::traits::Zero,
 {
 	fn get_fee_token_and_amount(
@@ -124,14 +148,6 @@ where
 
 		let rs = api.get_fee_token_and_amount(&at, who, total_inclusion_fee);
 
-		let try_into_rpc_balance = |value: Balance| {
-			value.try_into().map_err(|_| RpcError {
-				code: ErrorCode::InvalidParams,
-				message: format!("{} doesn't fit in NumberOrHex representation", value),
-				data: None,
-			})
-		};
-
 		match rs {
 			Ok((id, val)) => match try_into_rpc_balance(val) {
 				Ok(value) => Ok((id, value)),
@@ -144,4 +160,53 @@ where
 			}),
 		}
 	}
+
+	fn query_fee_quote(
+		&self,
+		who: AccountId,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<RpcFeeQuote<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let encoded_len = encoded_xt.len() as u32;
+
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to query fee quote.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let quote: FeeQuote<AccountId, CurrencyId, Balance> =
+			api.query_fee_quote(&at, who, uxt, encoded_len).map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to query fee quote.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		let with_rpc_balance = |(currency_id, value): (CurrencyId, Balance)| {
+			try_into_rpc_balance(value).map(|value| (currency_id, value))
+		};
+
+		Ok(RpcFeeQuote {
+			base_fee: try_into_rpc_balance(quote.base_fee)?,
+			tip: try_into_rpc_balance(quote.tip)?,
+			fee: quote.fee.map(with_rpc_balance).transpose()?,
+			sponsor: quote.sponsor,
+			extra_fee: quote.extra_fee.map(with_rpc_balance).transpose()?,
+			balances_after: quote
+				.balances_after
+				.into_iter()
+				.map(with_rpc_balance)
+				.collect::<JsonRpcResult<_>>()?,
+			payable: quote.payable,
+		})
+	}
+}
+
+fn try_into_rpc_balance(value: Balance) -> JsonRpcResult<NumberOrHex> {
+	value.try_into().map_err(|_| RpcError {
+		code: ErrorCode::InvalidParams,
+		message: format!("{} doesn't fit in NumberOrHex representation", value),
+		data: None,
+	})
 }
@@ -18,11 +18,14 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
 use frame_system::RawOrigin;
 use node_primitives::{CurrencyId, TokenSymbol};
+use sp_std::convert::TryInto;
 
 use super::*;
+use crate::misc_fees::{ExtraFeeAmount, ExtraFeeInfo};
 #[allow(unused_imports)]
 use crate::Pallet as FlexibleFee;
 
@@ -31,6 +34,145 @@ benchmarks! {
 		let order_vec = vec![CurrencyId::Token(TokenSymbol::try_from(0u8).unwrap_or_default())];
 		let caller: T::AccountId = whitelisted_caller();
 	}: _(RawOrigin::Signed(caller), Some(order_vec))
+
+	set_fee_currency_exchange_rate {
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::set_fee_currency_exchange_rate {
+			currency_id: CurrencyId::Token(TokenSymbol::KSM),
+			exchange_rate: (1, 100),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	remove_fee_currency_exchange_rate {
+		FeeCurrencyExchangeRates::<T>::insert(CurrencyId::Token(TokenSymbol::KSM), (1, 100));
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::remove_fee_currency_exchange_rate {
+			currency_id: CurrencyId::Token(TokenSymbol::KSM),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_extra_fee {
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::set_extra_fee {
+			pallet_name: b"Salp".to_vec(),
+			function_name: b"contribute".to_vec(),
+			info: ExtraFeeInfo {
+				currency_id: CurrencyId::Token(TokenSymbol::KSM),
+				amount: ExtraFeeAmount::Fixed(100u32.into()),
+				receiver: None,
+			},
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	remove_extra_fee {
+		ExtraFees::<T>::insert(
+			(b"Salp".to_vec(), b"contribute".to_vec()),
+			ExtraFeeInfo {
+				currency_id: CurrencyId::Token(TokenSymbol::KSM),
+				amount: ExtraFeeAmount::Fixed(100u32.into()),
+				receiver: None,
+			},
+		);
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::remove_extra_fee {
+			pallet_name: b"Salp".to_vec(),
+			function_name: b"contribute".to_vec(),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_sponsorship {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), 1_000u32.into(), 100u32.into())
+
+	fund_sponsorship {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 1_000_000u32.into());
+		FlexibleFee::<T>::set_sponsorship(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000u32.into(),
+			100u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller), 1_000u32.into())
+
+	close_sponsorship {
+		let n in 0 .. T::MaxSponsoredCalls::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 1_000_000u32.into());
+		FlexibleFee::<T>::set_sponsorship(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000u32.into(),
+			100u32.into(),
+		)?;
+		FlexibleFee::<T>::fund_sponsorship(RawOrigin::Signed(caller.clone()).into(), 1_000u32.into())?;
+		FlexibleFee::<T>::update_sponsored_calls(
+			RawOrigin::Signed(caller.clone()).into(),
+			sponsored_calls::<T>(n),
+			true,
+		)?;
+	}: _(RawOrigin::Signed(caller))
+
+	update_sponsored_calls {
+		let n in 1 .. T::MaxSponsoredCalls::get();
+		let caller: T::AccountId = whitelisted_caller();
+		FlexibleFee::<T>::set_sponsorship(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000u32.into(),
+			100u32.into(),
+		)?;
+		let calls = sponsored_calls::<T>(n);
+	}: _(RawOrigin::Signed(caller), calls, true)
+
+	update_sponsored_users {
+		let n in 1 .. T::MaxSponsoredUsers::get().min(50);
+		let caller: T::AccountId = whitelisted_caller();
+		FlexibleFee::<T>::set_sponsorship(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000u32.into(),
+			100u32.into(),
+		)?;
+		let users: Vec<T::AccountId> = (0..n).map(|i| account("user", i, 0)).collect();
+	}: _(RawOrigin::Signed(caller), users.try_into().unwrap(), true)
+
+	accept_sponsorship {
+		let sponsor: T::AccountId = account("sponsor", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		FlexibleFee::<T>::set_sponsorship(
+			RawOrigin::Signed(sponsor.clone()).into(),
+			1_000u32.into(),
+			100u32.into(),
+		)?;
+		FlexibleFee::<T>::update_sponsored_users(
+			RawOrigin::Signed(sponsor.clone()).into(),
+			vec![caller.clone()].try_into().unwrap(),
+			true,
+		)?;
+	}: _(RawOrigin::Signed(caller), sponsor)
+
+	leave_sponsorship {
+		let sponsor: T::AccountId = account("sponsor", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		FlexibleFee::<T>::set_sponsorship(
+			RawOrigin::Signed(sponsor.clone()).into(),
+			1_000u32.into(),
+			100u32.into(),
+		)?;
+		FlexibleFee::<T>::update_sponsored_users(
+			RawOrigin::Signed(sponsor.clone()).into(),
+			vec![caller.clone()].try_into().unwrap(),
+			true,
+		)?;
+		FlexibleFee::<T>::accept_sponsorship(RawOrigin::Signed(caller.clone()).into(), sponsor)?;
+	}: _(RawOrigin::Signed(caller))
+}
+
+fn sponsored_calls<T: Config>(
+	n: u32,
+) -> BoundedVec<(CallNameOf<T>, CallNameOf<T>), T::MaxSponsoredCalls> {
+	(0..n)
+		.map(|i| (b"Tokens".to_vec().try_into().unwrap(), vec![i as u8].try_into().unwrap()))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
 }
 
 impl_benchmark_test_suite!(
@@ -23,7 +23,10 @@ use super::*;
 use crate::Config;
 
 pub trait FeeDealer<AccountId, Balance, CurrencyId> {
-	fn ensure_can_charge_fee(
+	/// Make the fee payable by the user, which may swap the other currencies of the user for the
+	/// native currency. Returns whether the fee is charged in the alternative currency, and the
+	/// amount to charge.
+	fn make_fee_payable(
 		who: &AccountId,
 		fee: Balance,
 		reason: WithdrawReasons,
@@ -42,28 +45,12 @@ impl<T: Config> FeeDealer<T::AccountId, PalletBalanceOf<T>, CurrencyIdOf<T>>
 {
 	/// Make sure there is enough BNC to be deducted if the user has assets in other form of tokens
 	/// rather than BNC.
-	fn ensure_can_charge_fee(
+	fn make_fee_payable(
 		who: &T::AccountId,
 		fee: PalletBalanceOf<T>,
 		reason: WithdrawReasons,
 	) -> Result<(bool, PalletBalanceOf<T>), DispatchError> {
-		// First, check if the user has enough BNC balance to be deducted.assert_eq!
-		let native_existential_deposit = <<T as Config>::Currency as Currency<
-			<T as frame_system::Config>::AccountId,
-		>>::minimum_balance();
-		// check native balance if is enough
-		let native_is_enough = <<T as Config>::Currency as Currency<
-			<T as frame_system::Config>::AccountId,
-		>>::free_balance(who)
-		.checked_sub(&(fee + native_existential_deposit.into()))
-		.map_or(false, |new_free_balance| {
-			<<T as Config>::Currency as Currency<
-										<T as frame_system::Config>::AccountId,
-									>>::ensure_can_withdraw(
-										who, fee, reason, new_free_balance
-									)
-									.is_ok()
-		});
+		let native_is_enough = Pallet::<T>::native_is_enough(who, fee, reason);
 
 		if !native_is_enough {
 			// If the user doesn't have enough BNC, and he has enough KSM (converted KSM + KSM
@@ -74,7 +61,8 @@ impl<T: Config> FeeDealer<T::AccountId, PalletBalanceOf<T>, CurrencyIdOf<T>>
 					<T as frame_system::Config>::AccountId,
 				>>::minimum_balance(fee_currency_id);
 			let (fee_currency_base, native_currency_base): (u32, u32) =
-				T::AltFeeCurrencyExchangeRate::get();
+				Pallet::<T>::fee_currency_exchange_rate(fee_currency_id)
+					.ok_or(Error::<T>::ExchangeRateNotExist)?;
 
 			let fee_currency_balance = T::MultiCurrency::free_balance(fee_currency_id, who);
 
@@ -100,8 +88,7 @@ impl<T: Config> FeeDealer<T::AccountId, PalletBalanceOf<T>, CurrencyIdOf<T>>
 		// Make sure there are enough BNC to be deducted if the user has assets in other form of
 		// tokens rather than BNC.
 		let withdraw_reason = WithdrawReasons::TRANSACTION_PAYMENT;
-		let (fee_sign, fee_amount) =
-			T::FeeDealer::ensure_can_charge_fee(who, fee, withdraw_reason)?;
+		let (fee_sign, fee_amount) = Self::make_fee_payable(who, fee, withdraw_reason)?;
 
 		match fee_sign {
 			true => Ok((T::AlternativeFeeCurrencyId::get(), fee_amount.into())),
@@ -21,14 +21,17 @@
 use core::convert::{Into, TryFrom};
 
 use frame_support::{
+	dispatch::{CallMetadata, GetCallMetadata},
 	pallet_prelude::*,
 	traits::{
-		Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, ReservableCurrency,
-		WithdrawReasons,
+		Currency, ExistenceRequirement, Get, Imbalance, OnRuntimeUpgrade, OnUnbalanced,
+		ReservableCurrency, StorageVersion, WithdrawReasons,
 	},
+	transactional,
+	weights::WeightToFeePolynomial,
 };
 use frame_system::pallet_prelude::*;
-use node_primitives::{CurrencyId, ExtraFeeName, TokenSymbol};
+use node_primitives::{CurrencyId, FeeQuote, TokenSymbol};
 use orml_traits::MultiCurrency;
 pub use pallet::*;
 use pallet_transaction_payment::OnChargeTransaction;
@@ -36,24 +39,74 @@ use sp_arithmetic::traits::SaturatedConversion;
 use sp_runtime::{
 	traits::{CheckedSub, DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
 	transaction_validity::TransactionValidityError,
+	PerThing, Perbill,
 };
 use sp_std::{vec, vec::Vec};
 pub use weights::WeightInfo;
 use zenlink_protocol::{AssetBalance, AssetId, ExportZenlink};
 
-use crate::{
-	fee_dealer::FeeDealer,
-	misc_fees::{FeeDeductor, FeeGetter},
-};
+use crate::{fee_dealer::FeeDealer, misc_fees::ExtraFeeInfoOf, sponsorship::Sponsorship};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 pub mod fee_dealer;
 pub mod misc_fees;
 mod mock;
+pub mod sponsorship;
 mod tests;
 mod weights;
 
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+pub mod migration {
+	use super::*;
+
+	// The compile-time exchange rate of the alternative fee currency before it became
+	// governance-updatable.
+	const LEGACY_ALT_FEE_CURRENCY_EXCHANGE_RATE: (u32, u32) = (1, 100);
+
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return 0;
+		}
+
+		FeeCurrencyExchangeRates::<T>::insert(
+			T::AlternativeFeeCurrencyId::get(),
+			LEGACY_ALT_FEE_CURRENCY_EXCHANGE_RATE,
+		);
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+
+	/// Move the extra fees, which used to be hard-coded in the runtime, into the extra fee table.
+	pub struct MigrateToV2<T, LegacyExtraFees>(PhantomData<(T, LegacyExtraFees)>);
+
+	impl<T, LegacyExtraFees> OnRuntimeUpgrade for MigrateToV2<T, LegacyExtraFees>
+	where
+		T: Config,
+		LegacyExtraFees: Get<Vec<(Vec<u8>, Vec<u8>, ExtraFeeInfoOf<T>)>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let weight = migrate_to_v1::<T>();
+			if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+				return weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+
+			let extra_fees = LegacyExtraFees::get();
+			let count = extra_fees.len() as Weight;
+			for (pallet_name, function_name, info) in extra_fees {
+				ExtraFees::<T>::insert((pallet_name, function_name), info);
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, count.saturating_add(1)))
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -76,16 +129,8 @@ pub mod pallet {
 		type OnUnbalanced: OnUnbalanced<NegativeImbalanceOf<Self>>;
 		type DexOperator: ExportZenlink<Self::AccountId>;
 		type FeeDealer: FeeDealer<Self::AccountId, PalletBalanceOf<Self>, CurrencyIdOf<Self>>;
-		/// Filter if this transaction needs to be deducted extra fee besides basic transaction fee,
-		/// and get the name of the fee
-		type ExtraFeeMatcher: FeeGetter<CallOf<Self>>;
-		/// In charge of deducting extra fees
-		type MiscFeeHandler: FeeDeductor<
-			Self::AccountId,
-			CurrencyIdOf<Self>,
-			PalletBalanceOf<Self>,
-			CallOf<Self>,
-		>;
+		/// Convert the weight of the weight-based extra fees to the amount of the fee currency.
+		type ExtraFeeWeightToFee: WeightToFeePolynomial<Balance = PalletBalanceOf<Self>>;
 
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
@@ -96,9 +141,26 @@ pub mod pallet {
 		#[pallet::constant]
 		type AlternativeFeeCurrencyId: Get<CurrencyIdOf<Self>>;
 
-		/// Alternative Fee currency exchange rate: ?x Fee currency: ?y Native currency
+		/// The origin that can update the exchange rates of the fee currencies, either governance
+		/// or an oracle.
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum slippage tolerated when swapping a fee currency for the native currency
+		/// in the DEX, relative to the configured exchange rate of the fee currency.
 		#[pallet::constant]
-		type AltFeeCurrencyExchangeRate: Get<(u32, u32)>;
+		type FeeSwapSlippage: Get<Perbill>;
+
+		/// The maximum length of the pallet name or the function name of a sponsored call.
+		#[pallet::constant]
+		type MaxCallNameLength: Get<u32>;
+
+		/// The maximum number of calls covered by a sponsor.
+		#[pallet::constant]
+		type MaxSponsoredCalls: Get<u32>;
+
+		/// The maximum number of users covered by a sponsor.
+		#[pallet::constant]
+		type MaxSponsoredUsers: Get<u32>;
 	}
 
 	pub type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<
@@ -113,16 +175,48 @@ pub mod pallet {
 		<T as frame_system::Config>::AccountId,
 	>>::PositiveImbalance;
 	pub type CallOf<T> = <T as frame_system::Config>::Call;
+	pub type CallNameOf<T> = BoundedVec<u8, <T as Config>::MaxCallNameLength>;
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
+		}
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		FlexibleFeeExchanged(CurrencyIdOf<T>, u128), // token and amount
 		FixedRateFeeExchanged(CurrencyIdOf<T>, PalletBalanceOf<T>),
-		ExtraFeeDeducted(ExtraFeeName, CurrencyIdOf<T>, PalletBalanceOf<T>),
+		/// [pallet_name, function_name, currency_id, amount]
+		ExtraFeeDeducted(Vec<u8>, Vec<u8>, CurrencyIdOf<T>, PalletBalanceOf<T>),
+		/// [pallet_name, function_name, extra_fee_info]
+		ExtraFeeSet(Vec<u8>, Vec<u8>, ExtraFeeInfoOf<T>),
+		/// [pallet_name, function_name]
+		ExtraFeeRemoved(Vec<u8>, Vec<u8>),
+		/// [currency_id, (fee_currency_base, native_currency_base)]
+		FeeCurrencyExchangeRateSet(CurrencyIdOf<T>, (u32, u32)),
+		FeeCurrencyExchangeRateRemoved(CurrencyIdOf<T>),
+		/// The unused part of the fee paid in alternative currency is refunded.
+		/// [who, currency_id, charged, refunded]
+		FixedRateFeeRefunded(T::AccountId, CurrencyIdOf<T>, PalletBalanceOf<T>, PalletBalanceOf<T>),
+		/// [sponsor, per_user_limit, period]
+		SponsorshipSet(T::AccountId, PalletBalanceOf<T>, T::BlockNumber),
+		/// [sponsor, amount]
+		SponsorshipFunded(T::AccountId, PalletBalanceOf<T>),
+		/// [sponsor, budget returned]
+		SponsorshipClosed(T::AccountId, PalletBalanceOf<T>),
+		/// [sponsor, calls, sponsored]
+		SponsoredCallsUpdated(T::AccountId, Vec<(CallNameOf<T>, CallNameOf<T>)>, bool),
+		/// [sponsor, users, sponsored]
+		SponsoredUsersUpdated(T::AccountId, Vec<T::AccountId>, bool),
+		/// [who, sponsor]
+		SponsorshipAccepted(T::AccountId, T::AccountId),
+		/// [who, sponsor]
+		SponsorshipLeft(T::AccountId, T::AccountId),
+		/// [sponsor, who, fee]
+		FeeSponsored(T::AccountId, T::AccountId, PalletBalanceOf<T>),
 	}
 
 	#[pallet::type_value]
@@ -140,13 +234,102 @@ pub mod pallet {
 	pub type DefaultFeeChargeOrderList<T: Config> =
 		StorageValue<_, Vec<CurrencyIdOf<T>>, ValueQuery, DefaultFeeChargeOrder<T>>;
 
+	/// Exchange rates of the fee currencies: ?x Fee currency: ?y Native currency
+	#[pallet::storage]
+	#[pallet::getter(fn fee_currency_exchange_rate)]
+	pub type FeeCurrencyExchangeRates<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (u32, u32)>;
+
+	/// The fee sponsorships, keyed by the sponsor.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorship)]
+	pub type Sponsorships<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Sponsorship<PalletBalanceOf<T>, T::BlockNumber>>;
+
+	/// The calls covered by a sponsor: (pallet_name, function_name)
+	#[pallet::storage]
+	pub type SponsoredCalls<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(CallNameOf<T>, CallNameOf<T>),
+		(),
+	>;
+
+	/// The users a sponsor offers to cover.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_user)]
+	pub type SponsoredUsers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, ()>;
+
+	/// The sponsor a user accepted to be covered by, among the ones offering to cover it.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsor_of_user)]
+	pub type AcceptedSponsors<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+	/// The fee a sponsor has paid for a user within a period: (period, spent)
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_usage)]
+	pub type SponsoredUsage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(T::BlockNumber, PalletBalanceOf<T>),
+		ValueQuery,
+	>;
+
+	/// The extra fees charged besides the basic transaction fee: (pallet_name, function_name)
+	#[pallet::storage]
+	#[pallet::getter(fn extra_fee)]
+	pub type ExtraFees<T: Config> =
+		StorageMap<_, Twox64Concat, (Vec<u8>, Vec<u8>), ExtraFeeInfoOf<T>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub fee_currency_exchange_rates: Vec<(CurrencyIdOf<T>, (u32, u32))>,
+		pub extra_fees: Vec<(Vec<u8>, Vec<u8>, ExtraFeeInfoOf<T>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { fee_currency_exchange_rates: vec![], extra_fees: vec![] }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (currency_id, exchange_rate) in self.fee_currency_exchange_rates.iter() {
+				FeeCurrencyExchangeRates::<T>::insert(currency_id, exchange_rate);
+			}
+			for (pallet_name, function_name, info) in self.extra_fees.iter() {
+				ExtraFees::<T>::insert((pallet_name, function_name), info);
+			}
+		}
+	}
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::error]
 	pub enum Error<T> {
 		NotEnoughBalance,
+		ExchangeRateNotExist,
+		InvalidExchangeRate,
+		SponsorshipNotExist,
+		InvalidSponsorshipPeriod,
+		SponsorshipLimitExceeded,
+		SponsoredUsersNotEmpty,
+		UserNotSponsored,
+		TooManySponsoredCalls,
+		TooManySponsoredUsers,
+		ExtraFeeNotExist,
 	}
 
 	#[pallet::call]
@@ -169,6 +352,263 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Set the exchange rate of a fee currency: ?x Fee currency: ?y Native currency
+		#[pallet::weight(<T as Config>::WeightInfo::set_fee_currency_exchange_rate())]
+		pub fn set_fee_currency_exchange_rate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			exchange_rate: (u32, u32),
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let (fee_currency_base, native_currency_base) = exchange_rate;
+			ensure!(
+				currency_id != T::NativeCurrencyId::get() &&
+					!fee_currency_base.is_zero() &&
+					!native_currency_base.is_zero(),
+				Error::<T>::InvalidExchangeRate
+			);
+
+			FeeCurrencyExchangeRates::<T>::insert(currency_id, exchange_rate);
+
+			Self::deposit_event(Event::FeeCurrencyExchangeRateSet(currency_id, exchange_rate));
+
+			Ok(())
+		}
+
+		/// Remove a fee currency, so that fees can no longer be charged in it.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_fee_currency_exchange_rate())]
+		pub fn remove_fee_currency_exchange_rate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				FeeCurrencyExchangeRates::<T>::contains_key(currency_id),
+				Error::<T>::ExchangeRateNotExist
+			);
+			FeeCurrencyExchangeRates::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::FeeCurrencyExchangeRateRemoved(currency_id));
+
+			Ok(())
+		}
+
+		/// Charge an extra fee on the call, besides the basic transaction fee. The call is
+		/// identified by (pallet_name, function_name).
+		#[pallet::weight(<T as Config>::WeightInfo::set_extra_fee())]
+		pub fn set_extra_fee(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+			info: ExtraFeeInfoOf<T>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ExtraFees::<T>::insert((&pallet_name, &function_name), &info);
+
+			Self::deposit_event(Event::ExtraFeeSet(pallet_name, function_name, info));
+
+			Ok(())
+		}
+
+		/// Stop charging the extra fee on the call.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_extra_fee())]
+		pub fn remove_extra_fee(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let key = (&pallet_name, &function_name);
+			ensure!(ExtraFees::<T>::contains_key(key), Error::<T>::ExtraFeeNotExist);
+			ExtraFees::<T>::remove(key);
+
+			Self::deposit_event(Event::ExtraFeeRemoved(pallet_name, function_name));
+
+			Ok(())
+		}
+
+		/// Create a fee sponsorship, or update the limits of it.
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsorship())]
+		pub fn set_sponsorship(
+			origin: OriginFor<T>,
+			per_user_limit: PalletBalanceOf<T>,
+			period: T::BlockNumber,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(!period.is_zero(), Error::<T>::InvalidSponsorshipPeriod);
+
+			Sponsorships::<T>::mutate(&sponsor, |maybe_sponsorship| match maybe_sponsorship {
+				Some(sponsorship) => {
+					sponsorship.per_user_limit = per_user_limit;
+					sponsorship.period = period;
+				},
+				None => {
+					*maybe_sponsorship = Some(Sponsorship {
+						budget: Zero::zero(),
+						per_user_limit,
+						period,
+						users: 0,
+						calls: 0,
+					});
+				},
+			});
+
+			Self::deposit_event(Event::SponsorshipSet(sponsor, per_user_limit, period));
+
+			Ok(())
+		}
+
+		/// Reserve native currency to the budget of the sponsorship.
+		#[pallet::weight(<T as Config>::WeightInfo::fund_sponsorship())]
+		pub fn fund_sponsorship(
+			origin: OriginFor<T>,
+			amount: PalletBalanceOf<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			Sponsorships::<T>::try_mutate(&sponsor, |maybe_sponsorship| -> DispatchResult {
+				let sponsorship =
+					maybe_sponsorship.as_mut().ok_or(Error::<T>::SponsorshipNotExist)?;
+				T::Currency::reserve(&sponsor, amount)?;
+				sponsorship.budget = sponsorship.budget.saturating_add(amount);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::SponsorshipFunded(sponsor, amount));
+
+			Ok(())
+		}
+
+		/// Close the sponsorship and get the rest of the budget back. All the sponsored users must
+		/// be removed beforehand.
+		#[pallet::weight(<T as Config>::WeightInfo::close_sponsorship(T::MaxSponsoredCalls::get()))]
+		pub fn close_sponsorship(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+
+			let sponsorship = Self::sponsorship(&sponsor).ok_or(Error::<T>::SponsorshipNotExist)?;
+			ensure!(sponsorship.users.is_zero(), Error::<T>::SponsoredUsersNotEmpty);
+
+			// The usages of the sponsor are removed along with the users.
+			T::Currency::unreserve(&sponsor, sponsorship.budget);
+			Sponsorships::<T>::remove(&sponsor);
+			let _ = SponsoredCalls::<T>::remove_prefix(&sponsor, Some(sponsorship.calls));
+
+			Self::deposit_event(Event::SponsorshipClosed(sponsor, sponsorship.budget));
+
+			Ok(Some(<T as Config>::WeightInfo::close_sponsorship(sponsorship.calls)).into())
+		}
+
+		/// Add or remove the calls covered by the sponsorship, by (pallet_name, function_name).
+		#[pallet::weight(<T as Config>::WeightInfo::update_sponsored_calls(calls.len() as u32))]
+		#[transactional]
+		pub fn update_sponsored_calls(
+			origin: OriginFor<T>,
+			calls: BoundedVec<(CallNameOf<T>, CallNameOf<T>), T::MaxSponsoredCalls>,
+			sponsored: bool,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let mut sponsorship =
+				Self::sponsorship(&sponsor).ok_or(Error::<T>::SponsorshipNotExist)?;
+
+			for call in calls.iter() {
+				let is_sponsored = SponsoredCalls::<T>::contains_key(&sponsor, call);
+				if sponsored && !is_sponsored {
+					ensure!(
+						sponsorship.calls < T::MaxSponsoredCalls::get(),
+						Error::<T>::TooManySponsoredCalls
+					);
+					SponsoredCalls::<T>::insert(&sponsor, call, ());
+					sponsorship.calls = sponsorship.calls.saturating_add(1);
+				} else if !sponsored && is_sponsored {
+					SponsoredCalls::<T>::remove(&sponsor, call);
+					sponsorship.calls = sponsorship.calls.saturating_sub(1);
+				}
+			}
+			Sponsorships::<T>::insert(&sponsor, sponsorship);
+
+			Self::deposit_event(Event::SponsoredCallsUpdated(
+				sponsor,
+				calls.into_inner(),
+				sponsored,
+			));
+
+			Ok(())
+		}
+
+		/// Add or remove the users the sponsorship offers to cover. A user is only covered once it
+		/// accepts the sponsorship.
+		#[pallet::weight(<T as Config>::WeightInfo::update_sponsored_users(users.len() as u32))]
+		#[transactional]
+		pub fn update_sponsored_users(
+			origin: OriginFor<T>,
+			users: BoundedVec<T::AccountId, T::MaxSponsoredUsers>,
+			sponsored: bool,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let mut sponsorship =
+				Self::sponsorship(&sponsor).ok_or(Error::<T>::SponsorshipNotExist)?;
+
+			for user in users.iter() {
+				let is_sponsored = SponsoredUsers::<T>::contains_key(&sponsor, user);
+				if sponsored && !is_sponsored {
+					ensure!(
+						sponsorship.users < T::MaxSponsoredUsers::get(),
+						Error::<T>::TooManySponsoredUsers
+					);
+					SponsoredUsers::<T>::insert(&sponsor, user, ());
+					sponsorship.users = sponsorship.users.saturating_add(1);
+				} else if !sponsored {
+					ensure!(is_sponsored, Error::<T>::UserNotSponsored);
+					SponsoredUsers::<T>::remove(&sponsor, user);
+					SponsoredUsage::<T>::remove(&sponsor, user);
+					if Self::sponsor_of_user(user).as_ref() == Some(&sponsor) {
+						AcceptedSponsors::<T>::remove(user);
+					}
+					sponsorship.users = sponsorship.users.saturating_sub(1);
+				}
+			}
+			Sponsorships::<T>::insert(&sponsor, sponsorship);
+
+			Self::deposit_event(Event::SponsoredUsersUpdated(
+				sponsor,
+				users.into_inner(),
+				sponsored,
+			));
+
+			Ok(())
+		}
+
+		/// Accept to be covered by a sponsor offering it, in place of the sponsor accepted before.
+		#[pallet::weight(<T as Config>::WeightInfo::accept_sponsorship())]
+		pub fn accept_sponsorship(origin: OriginFor<T>, sponsor: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				SponsoredUsers::<T>::contains_key(&sponsor, &who),
+				Error::<T>::UserNotSponsored
+			);
+
+			AcceptedSponsors::<T>::insert(&who, &sponsor);
+
+			Self::deposit_event(Event::SponsorshipAccepted(who, sponsor));
+
+			Ok(())
+		}
+
+		/// Stop being covered by the accepted sponsor, the fees are paid by the user again.
+		#[pallet::weight(<T as Config>::WeightInfo::leave_sponsorship())]
+		pub fn leave_sponsorship(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let sponsor = AcceptedSponsors::<T>::take(&who).ok_or(Error::<T>::UserNotSponsored)?;
+
+			Self::deposit_event(Event::SponsorshipLeft(who, sponsor));
+
+			Ok(())
+		}
 	}
 }
 
@@ -181,6 +621,132 @@ impl<T: Config> Pallet<T> {
 		}
 		charge_order_list
 	}
+
+	/// Whether the user has enough native currency to pay the fee and stay alive.
+	pub(crate) fn native_is_enough(
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+		reason: WithdrawReasons,
+	) -> bool {
+		let existential_deposit = T::Currency::minimum_balance();
+
+		T::Currency::free_balance(who)
+			.checked_sub(&fee.saturating_add(existential_deposit))
+			.map_or(false, |new_free_balance| {
+				T::Currency::ensure_can_withdraw(who, fee, reason, new_free_balance).is_ok()
+			})
+	}
+
+	/// Quote how much of the first asset of the path is needed to get `amount_out` of the last.
+	fn quote_fee_amount_in(amount_out: AssetBalance, path: &[AssetId]) -> Option<AssetBalance> {
+		T::DexOperator::get_amount_in_by_path(amount_out, path)
+			.ok()
+			.and_then(|amounts| amounts.first().copied())
+			.filter(|amount_in| !amount_in.is_zero())
+	}
+
+	/// The most of the fee currency that can be swapped for `amount_out` of the native currency:
+	/// the amount by the configured exchange rate, plus the tolerated slippage.
+	///
+	/// Returns `None` if the fee currency has no exchange rate.
+	fn fee_amount_in_max(
+		currency_id: CurrencyIdOf<T>,
+		amount_out: AssetBalance,
+	) -> Option<AssetBalance> {
+		let (fee_currency_base, native_currency_base) =
+			Self::fee_currency_exchange_rate(currency_id)?;
+		let amount_in = amount_out
+			.saturating_mul(fee_currency_base.into())
+			.checked_div(native_currency_base.into())?;
+
+		Some(amount_in.saturating_add(T::FeeSwapSlippage::get().mul_ceil(amount_in)))
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	CallOf<T>: GetCallMetadata,
+{
+	/// Dry-run the fees charged on the call, the same way as `withdraw_fee` does, without
+	/// changing any storage. This function is for runtime-api to call.
+	pub fn quote_fee(
+		who: &T::AccountId,
+		call: &CallOf<T>,
+		base_fee: PalletBalanceOf<T>,
+		tip: PalletBalanceOf<T>,
+	) -> FeeQuote<T::AccountId, CurrencyIdOf<T>, PalletBalanceOf<T>> {
+		let total_fee = base_fee.saturating_add(tip);
+		let mut balances_after = Vec::new();
+		let mut payable = true;
+
+		let mut fee = None;
+		let mut sponsor = None;
+		if !total_fee.is_zero() {
+			sponsor = Self::sponsor_of(who, call, total_fee, tip);
+			if sponsor.is_some() {
+				fee = Some((T::NativeCurrencyId::get(), total_fee));
+			} else if let Ok((currency_id, amount)) =
+				T::FeeDealer::cal_fee_token_and_amount(who, total_fee)
+			{
+				payable &= Self::quote_charge(who, &mut balances_after, currency_id, amount);
+				fee = Some((currency_id, amount));
+			} else {
+				payable = false;
+			}
+		}
+
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+		let extra_fee = Self::extra_fee_of(pallet_name.as_bytes(), function_name.as_bytes()).map(
+			|(currency_id, amount, _)| {
+				payable &= Self::quote_charge(who, &mut balances_after, currency_id, amount);
+				(currency_id, amount)
+			},
+		);
+
+		FeeQuote { base_fee, tip, fee, sponsor, extra_fee, balances_after, payable }
+	}
+
+	/// Deduct the amount from the quoted balance of the currency, and tell if it is enough.
+	fn quote_charge(
+		who: &T::AccountId,
+		balances: &mut Vec<(CurrencyIdOf<T>, PalletBalanceOf<T>)>,
+		currency_id: CurrencyIdOf<T>,
+		amount: PalletBalanceOf<T>,
+	) -> bool {
+		let index = match balances.iter().position(|(id, _)| *id == currency_id) {
+			Some(index) => index,
+			None => {
+				balances.push((currency_id, T::MultiCurrency::free_balance(currency_id, who)));
+				balances.len() - 1
+			},
+		};
+
+		let balance = &mut balances[index].1;
+		let enough = *balance >= amount;
+		*balance = balance.saturating_sub(amount);
+
+		enough
+	}
+}
+
+/// A swap of a fee currency for the native currency in the DEX, to pay the fee.
+pub(crate) struct FeeSwap<CurrencyId> {
+	currency_id: CurrencyId,
+	path: Vec<AssetId>,
+	amount_out: AssetBalance,
+	amount_in: AssetBalance,
+	/// The most to swap, by the exchange rate of the currency and the balance of the user.
+	amount_in_max: AssetBalance,
+}
+
+/// The fee withdrawn from the transaction origin before dispatch.
+pub enum FeeWithdrawn<T: Config> {
+	/// The fee is paid in native currency.
+	Native(NegativeImbalanceOf<T>),
+	/// The fee is paid in alternative currency: (currency_id, amount paid, fee in native currency)
+	Alternative(CurrencyIdOf<T>, PalletBalanceOf<T>, PalletBalanceOf<T>),
+	/// The fee is paid by the sponsor in native currency.
+	Sponsored(T::AccountId, NegativeImbalanceOf<T>),
 }
 
 /// Default implementation for a Currency and an OnUnbalanced handler.
@@ -188,12 +754,13 @@ impl<T> OnChargeTransaction<T> for Pallet<T>
 where
 	T: Config,
 	T::TransactionByteFee: Get<PalletBalanceOf<T>>,
+	CallOf<T>: GetCallMetadata,
 	T::Currency: Currency<<T as frame_system::Config>::AccountId>,
 	PositiveImbalanceOf<T>: Imbalance<PalletBalanceOf<T>, Opposite = NegativeImbalanceOf<T>>,
 	NegativeImbalanceOf<T>: Imbalance<PalletBalanceOf<T>, Opposite = PositiveImbalanceOf<T>>,
 {
 	type Balance = PalletBalanceOf<T>;
-	type LiquidityInfo = Option<NegativeImbalanceOf<T>>;
+	type LiquidityInfo = Option<FeeWithdrawn<T>>;
 
 	/// Withdraw the predicted fee from the transaction origin.
 	///
@@ -215,57 +782,53 @@ where
 			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
 		};
 
-		// Make sure there are enough BNC to be deducted if the user has assets in other form of
-		// tokens rather than BNC.
-		let (fee_sign, fee_amount) = T::FeeDealer::ensure_can_charge_fee(who, fee, withdraw_reason)
-			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-
 		let rs;
-		// if the user has enough BNC for fee
-		if fee_sign == false {
-			rs = match T::Currency::withdraw(
-				who,
-				fee,
-				withdraw_reason,
-				ExistenceRequirement::AllowDeath,
-			) {
-				Ok(imbalance) => Ok(Some(imbalance)),
-				Err(_msg) => Err(InvalidTransaction::Payment.into()),
-			};
-		// if the user donsn't enough BNC but has enough KSM
-		} else {
-			// This withdraw operation allows death. So it will succeed given the remaining amount
-			// less than the existential deposit.
-			let fee_currency_id = T::AlternativeFeeCurrencyId::get();
-			T::MultiCurrency::withdraw(fee_currency_id, who, fee_amount)
+		// if the call of the user is sponsored, the sponsor pays the fee
+		if let Some(sponsor) = Self::sponsor_of(who, call, fee, tip) {
+			let imbalance = Self::charge_sponsor(&sponsor, who, fee, withdraw_reason)
 				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-			// deposit the fee_currency amount to Treasury
-			T::MultiCurrency::deposit(fee_currency_id, &T::TreasuryAccount::get(), fee_amount)
+			rs = Ok(Some(FeeWithdrawn::Sponsored(sponsor, imbalance)));
+		} else {
+			// Make sure there are enough BNC to be deducted if the user has assets in other form of
+			// tokens rather than BNC.
+			let (fee_sign, fee_amount) = T::FeeDealer::make_fee_payable(who, fee, withdraw_reason)
 				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
 
-			Self::deposit_event(Event::FixedRateFeeExchanged(fee_currency_id, fee_amount));
+			// if the user has enough BNC for fee
+			if fee_sign == false {
+				rs = match T::Currency::withdraw(
+					who,
+					fee,
+					withdraw_reason,
+					ExistenceRequirement::AllowDeath,
+				) {
+					Ok(imbalance) => Ok(Some(FeeWithdrawn::Native(imbalance))),
+					Err(_msg) => Err(InvalidTransaction::Payment.into()),
+				};
+			// if the user donsn't enough BNC but has enough KSM
+			} else {
+				// This withdraw operation allows death. So it will succeed given the remaining
+				// amount less than the existential deposit.
+				let fee_currency_id = T::AlternativeFeeCurrencyId::get();
+				T::MultiCurrency::withdraw(fee_currency_id, who, fee_amount)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				// deposit the fee_currency amount to Treasury
+				T::MultiCurrency::deposit(fee_currency_id, &T::TreasuryAccount::get(), fee_amount)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
 
-			// need to return 0 value of imbalance type
-			// this value will be used to see post dispatch refund in BNC
-			// if charge less than actual payment, no refund will be paid
-			rs = Ok(Some(NegativeImbalanceOf::<T>::zero()));
-		}
+				Self::deposit_event(Event::FixedRateFeeExchanged(fee_currency_id, fee_amount));
 
-		// See if the this Call needs to pay extra fee
-		let (fee_name, if_extra_fee) = T::ExtraFeeMatcher::get_fee_info(call.clone());
-		if if_extra_fee {
-			// We define 77 as the error of extra fee deduction failure.
-			let (extra_fee_currency, extra_fee_amount) =
-				T::MiscFeeHandler::deduct_fee(who, &T::TreasuryAccount::get(), call).map_err(
-					|_| TransactionValidityError::Invalid(InvalidTransaction::Custom(77u8)),
-				)?;
-			Self::deposit_event(Event::ExtraFeeDeducted(
-				fee_name,
-				extra_fee_currency,
-				extra_fee_amount,
-			));
+				// keep track of the fee currency amount, so that the unused part of it can be
+				// refunded after dispatch.
+				rs = Ok(Some(FeeWithdrawn::Alternative(fee_currency_id, fee_amount, fee)));
+			}
 		}
 
+		// See if the this Call needs to pay extra fee.
+		// We define 77 as the error of extra fee deduction failure.
+		Self::deduct_extra_fee(who, call)
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Custom(77u8)))?;
+
 		rs
 	}
 
@@ -282,110 +845,178 @@ where
 		tip: Self::Balance,
 		already_withdrawn: Self::LiquidityInfo,
 	) -> Result<(), TransactionValidityError> {
-		if let Some(paid) = already_withdrawn {
-			// Calculate how much refund we should return
-			let refund_amount = paid.peek().saturating_sub(corrected_fee);
-
-			// refund to the the account that paid the fees. If this fails, the
-			// account might have dropped below the existential balance. In
-			// that case we don't refund anything.
-			let refund_imbalance = T::Currency::deposit_into_existing(&who, refund_amount)
-				.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
-			// merge the imbalance caused by paying the fees and refunding parts of it again.
-			let adjusted_paid = paid
-				.offset(refund_imbalance)
-				.same()
-				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
-			// Call someone else to handle the imbalance (fee and tip separately)
-			let imbalances = adjusted_paid.split(tip);
-			T::OnUnbalanced::on_unbalanceds(
-				Some(imbalances.0).into_iter().chain(Some(imbalances.1)),
-			);
+		match already_withdrawn {
+			Some(FeeWithdrawn::Native(paid)) => {
+				Self::deposit_native_fee(who, paid, corrected_fee, tip)?;
+			},
+			Some(FeeWithdrawn::Sponsored(sponsor, paid)) => {
+				let refund_amount = Self::deposit_native_fee(&sponsor, paid, corrected_fee, tip)?;
+				Self::restore_sponsorship_budget(&sponsor, who, refund_amount);
+			},
+			Some(FeeWithdrawn::Alternative(currency_id, paid, fee)) => {
+				// Refund the same proportion of the fee currency as of the native fee.
+				let refund_amount = if corrected_fee < fee {
+					paid.saturating_sub(Perbill::from_rational(corrected_fee, fee).mul_ceil(paid))
+				} else {
+					Zero::zero()
+				};
+
+				// The fee currency has been deposited to the treasury. If the refund fails, we
+				// don't refund anything.
+				if !refund_amount.is_zero() &&
+					T::MultiCurrency::transfer(
+						currency_id,
+						&T::TreasuryAccount::get(),
+						who,
+						refund_amount,
+					)
+					.is_ok()
+				{
+					Self::deposit_event(Event::FixedRateFeeRefunded(
+						who.clone(),
+						currency_id,
+						paid,
+						refund_amount,
+					));
+				}
+			},
+			None => {},
 		}
 		Ok(())
 	}
 }
 
-impl<T: Config> FeeDealer<T::AccountId, PalletBalanceOf<T>, CurrencyIdOf<T>> for Pallet<T> {
-	/// Make sure there are enough BNC to be deducted if the user has assets in other form of tokens
-	/// rather than BNC.
-	fn ensure_can_charge_fee(
+impl<T> Pallet<T>
+where
+	T: Config,
+	PositiveImbalanceOf<T>: Imbalance<PalletBalanceOf<T>, Opposite = NegativeImbalanceOf<T>>,
+	NegativeImbalanceOf<T>: Imbalance<PalletBalanceOf<T>, Opposite = PositiveImbalanceOf<T>>,
+{
+	/// Refund the unused part of the native fee to the payer, and hand the rest over to the
+	/// `[OnUnbalanced]` implementation. Returns the refunded amount.
+	fn deposit_native_fee(
+		payer: &T::AccountId,
+		paid: NegativeImbalanceOf<T>,
+		corrected_fee: PalletBalanceOf<T>,
+		tip: PalletBalanceOf<T>,
+	) -> Result<PalletBalanceOf<T>, TransactionValidityError> {
+		// Calculate how much refund we should return
+		let refund_amount = paid.peek().saturating_sub(corrected_fee);
+
+		// refund to the the account that paid the fees. If this fails, the
+		// account might have dropped below the existential balance. In
+		// that case we don't refund anything.
+		let refund_imbalance = T::Currency::deposit_into_existing(payer, refund_amount)
+			.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
+		let refunded = refund_imbalance.peek();
+		// merge the imbalance caused by paying the fees and refunding parts of it again.
+		let adjusted_paid = paid
+			.offset(refund_imbalance)
+			.same()
+			.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+		// Call someone else to handle the imbalance (fee and tip separately)
+		let imbalances = adjusted_paid.split(tip);
+		T::OnUnbalanced::on_unbalanceds(Some(imbalances.0).into_iter().chain(Some(imbalances.1)));
+
+		Ok(refunded)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Find the currency the user pays the fee with, the same way for charging the fee and for
+	/// quoting it: `None` if the native currency is enough, otherwise the swap in the DEX for the
+	/// native currency.
+	///
+	/// The currencies are tried in the order of the user fee charge order list. The first one
+	/// that can be swapped for the fee in the DEX, within the slippage limit of its configured
+	/// exchange rate, is used. If the native balance is below the existential deposit, the swap
+	/// covers it as well.
+	fn fee_swap_of(
 		who: &T::AccountId,
 		fee: PalletBalanceOf<T>,
 		reason: WithdrawReasons,
-	) -> Result<(bool, PalletBalanceOf<T>), DispatchError> {
-		// get the user defined fee charge order list.
-		let user_fee_charge_order_list = Self::inner_get_user_fee_charge_order_list(who);
-		let existential_deposit = <<T as Config>::Currency as Currency<
-			<T as frame_system::Config>::AccountId,
-		>>::minimum_balance();
-
-		// charge the fee by the order of the above order list.
-		// first to check whether the user has the asset. If no, pass it. If yes, try to make
-		// transaction in the DEX in exchange for BNC
-		for currency_id in user_fee_charge_order_list {
-			let native_asset_id: AssetId = AssetId::try_from(T::NativeCurrencyId::get())
-				.map_err(|_| DispatchError::Other("Conversion Error."))?;
-			// If it is mainnet currency
-			if currency_id == T::NativeCurrencyId::get() {
-				// check native balance if is enough
-				let native_is_enough = <<T as Config>::Currency as Currency<
-					<T as frame_system::Config>::AccountId,
-				>>::free_balance(who)
-				.checked_sub(&(fee + existential_deposit.into()))
-				.map_or(false, |new_free_balance| {
-					<<T as Config>::Currency as Currency<
-												<T as frame_system::Config>::AccountId,
-											>>::ensure_can_withdraw(
-												who, fee, reason, new_free_balance
-											)
-											.is_ok()
-				});
-
-				if native_is_enough {
-					// native balance is enough, break iteration
-					break;
-				}
-			} else {
-				// If it is other assets
-				let native_balance =
-					T::MultiCurrency::free_balance(T::NativeCurrencyId::get(), who);
-
-				// If native token balance is below existential deposit requirement,
-				// go exchange fee + existential deposit. Else to exchange fee amount.
-				let amount_out: AssetBalance;
-				if native_balance > existential_deposit {
-					amount_out = fee.saturated_into();
-				} else {
-					amount_out = (fee + existential_deposit).saturated_into();
-				}
+	) -> Result<Option<FeeSwap<CurrencyIdOf<T>>>, DispatchError> {
+		let native_currency_id = T::NativeCurrencyId::get();
+		let native_asset_id: AssetId = AssetId::try_from(native_currency_id)
+			.map_err(|_| DispatchError::Other("Conversion Error."))?;
+		let existential_deposit = T::Currency::minimum_balance();
 
-				let asset_balance = T::MultiCurrency::free_balance(currency_id, who);
-				let asset_id: AssetId = AssetId::try_from(currency_id)
-					.map_err(|_| DispatchError::Other("Conversion Error."))?;
-
-				let path = vec![asset_id, native_asset_id];
-				let amount_in_max: AssetBalance = asset_balance.saturated_into();
-
-				// query for amount in
-				let amounts =
-					T::DexOperator::get_amount_in_by_path(amount_out, &path).map_or(vec![0], |v| v);
-
-				if T::DexOperator::inner_swap_assets_for_exact_assets(
-					&who,
-					amount_out,
-					amount_in_max,
-					&path,
-					&who,
-				)
-				.is_ok()
-				{
-					Self::deposit_event(Event::FlexibleFeeExchanged(currency_id, amounts[0]));
-					// successfully swap, break iteration
-					break;
+		// If native token balance is below existential deposit requirement,
+		// go exchange fee + existential deposit. Else to exchange fee amount.
+		let amount_out: AssetBalance = if T::Currency::free_balance(who) > existential_deposit {
+			fee.saturated_into()
+		} else {
+			fee.saturating_add(existential_deposit).saturated_into()
+		};
+
+		for currency_id in Self::inner_get_user_fee_charge_order_list(who) {
+			if currency_id == native_currency_id {
+				if Self::native_is_enough(who, fee, reason) {
+					return Ok(None);
 				}
+				continue;
+			}
+
+			let asset_id = match AssetId::try_from(currency_id) {
+				Ok(asset_id) => asset_id,
+				Err(_) => continue,
+			};
+			let path = vec![asset_id, native_asset_id];
+
+			let amount_in = match Self::quote_fee_amount_in(amount_out, &path) {
+				Some(amount_in) => amount_in,
+				None => continue,
+			};
+			let amount_in_max = match Self::fee_amount_in_max(currency_id, amount_out) {
+				Some(amount_in_max) => amount_in_max,
+				None => continue,
+			};
+			let asset_balance: AssetBalance =
+				T::MultiCurrency::free_balance(currency_id, who).saturated_into();
+			if amount_in > amount_in_max || amount_in > asset_balance {
+				continue;
 			}
+
+			return Ok(Some(FeeSwap {
+				currency_id,
+				path,
+				amount_out,
+				amount_in,
+				amount_in_max: amount_in_max.min(asset_balance),
+			}));
+		}
+
+		Err(Error::<T>::NotEnoughBalance.into())
+	}
+
+	/// Swap the fee currency of the user for the native currency in the DEX.
+	fn swap_for_fee(who: &T::AccountId, swap: FeeSwap<CurrencyIdOf<T>>) -> DispatchResult {
+		T::DexOperator::inner_swap_assets_for_exact_assets(
+			who,
+			swap.amount_out,
+			swap.amount_in_max,
+			&swap.path,
+			who,
+		)?;
+
+		Self::deposit_event(Event::FlexibleFeeExchanged(swap.currency_id, swap.amount_in));
+
+		Ok(())
+	}
+}
+
+impl<T: Config> FeeDealer<T::AccountId, PalletBalanceOf<T>, CurrencyIdOf<T>> for Pallet<T> {
+	/// Make sure there are enough BNC to be deducted if the user has assets in other form of tokens
+	/// rather than BNC, by swapping them for BNC in the DEX.
+	fn make_fee_payable(
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+		reason: WithdrawReasons,
+	) -> Result<(bool, PalletBalanceOf<T>), DispatchError> {
+		if let Some(swap) = Self::fee_swap_of(who, fee, reason)? {
+			Self::swap_for_fee(who, swap)?;
 		}
+
 		Ok((false, fee))
 	}
 
@@ -394,48 +1025,9 @@ impl<T: Config> FeeDealer<T::AccountId, PalletBalanceOf<T>, CurrencyIdOf<T>> for
 		who: &T::AccountId,
 		fee: PalletBalanceOf<T>,
 	) -> Result<(CurrencyIdOf<T>, PalletBalanceOf<T>), DispatchError> {
-		let mut fee_token_id_out: CurrencyIdOf<T> = T::NativeCurrencyId::get();
-		let mut fee_token_amount_out = Zero::zero();
-
-		// get the user defined fee charge order list.
-		let user_fee_charge_order_list = Self::inner_get_user_fee_charge_order_list(who);
-		let amount_out: AssetBalance = fee.saturated_into();
-		let native_asset_id: AssetId = AssetId::try_from(T::NativeCurrencyId::get())
-			.map_err(|_| DispatchError::Other("Conversion Error"))?;
-
-		// charge the fee by the order of the above order list.
-		// first to check whether the user has the asset. If no, pass it. If yes, try to make
-		// transaction in the DEX in exchange for BNC
-		for currency_id in user_fee_charge_order_list {
-			// If it is mainnet currency
-			if currency_id == T::NativeCurrencyId::get() {
-				// check native balance if is enough
-				let native_balance = <<T as Config>::Currency as Currency<
-					<T as frame_system::Config>::AccountId,
-				>>::free_balance(who);
-
-				if native_balance >= fee.into() {
-					fee_token_amount_out = fee.into();
-					break;
-				}
-			} else {
-				// If it is other assets
-				let asset_balance = T::MultiCurrency::total_balance(currency_id, who);
-				let token_asset_id: AssetId = AssetId::try_from(currency_id)
-					.map_err(|_| DispatchError::Other("Conversion Error"))?;
-				let path = vec![native_asset_id.clone(), token_asset_id];
-
-				let amount_vec = T::DexOperator::get_amount_in_by_path(amount_out, &path)?;
-				let amount_in = amount_vec[0];
-				let amount_in_balance = amount_in.saturated_into();
-
-				if asset_balance >= amount_in_balance {
-					fee_token_id_out = currency_id;
-					fee_token_amount_out = amount_in_balance;
-					break;
-				}
-			}
+		match Self::fee_swap_of(who, fee, WithdrawReasons::TRANSACTION_PAYMENT)? {
+			Some(swap) => Ok((swap.currency_id, swap.amount_in.saturated_into())),
+			None => Ok((T::NativeCurrencyId::get(), fee)),
 		}
-		Ok((fee_token_id_out, fee_token_amount_out.into()))
 	}
 }
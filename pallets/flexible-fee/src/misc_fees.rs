@@ -16,99 +16,79 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-// The swap pool algorithm implements Balancer protocol
-// For more details, refer to https://balancer.finance/whitepaper/
+//! Extra fees charged besides the basic transaction fee, looked up by the call metadata in the
+//! extra fee table.
 
 use frame_support::{
-	traits::Contains,
-	weights::{Weight, WeightToFeePolynomial},
+	dispatch::{CallMetadata, GetCallMetadata},
+	weights::WeightToFeePolynomial,
 };
-use node_primitives::ExtraFeeName;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 use super::*;
 use crate::Config;
 
-pub struct MiscFeeHandler<T, FeeCurrency, WeightToFee, WeightHolder, FeeFilter>(
-	PhantomData<(T, FeeCurrency, WeightToFee, WeightHolder, FeeFilter)>,
-);
-
-impl<T: Config, FeeCurrency, WeightToFee, WeightHolder, FeeFilter>
-	FeeDeductor<T::AccountId, CurrencyIdOf<T>, PalletBalanceOf<T>, T::Call>
-	for MiscFeeHandler<T, FeeCurrency, WeightToFee, WeightHolder, FeeFilter>
-where
-	FeeCurrency: Get<CurrencyIdOf<T>>,
-	WeightToFee: WeightToFeePolynomial<Balance = PalletBalanceOf<T>>,
-	WeightHolder: Get<Weight>,
-	FeeFilter: Contains<CallOf<T>>,
-{
-	fn deduct_fee(
-		who: &T::AccountId,
-		receiver: &T::AccountId,
-		call: &T::Call,
-	) -> Result<(CurrencyIdOf<T>, PalletBalanceOf<T>), DispatchError> {
-		// If this call matches a specific extra-fee call
-		if FeeFilter::contains(call) {
-			let total_fee = WeightToFee::calc(&WeightHolder::get());
-			let fee_currency = FeeCurrency::get();
-
-			<T as pallet::Config>::MultiCurrency::transfer(fee_currency, who, receiver, total_fee)?;
-			Ok((fee_currency, total_fee))
-		} else {
-			Err(DispatchError::Other("Failed to deduct extra fee."))
-		}
-	}
+/// How the amount of an extra fee is determined.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ExtraFeeAmount<Balance> {
+	/// A fixed amount of the fee currency.
+	Fixed(Balance),
+	/// The fee of the weight, converted by `ExtraFeeWeightToFee`.
+	Weight(Weight),
 }
 
-pub trait FeeDeductor<AccountId, CurrencyId, Balance, Call> {
-	fn deduct_fee(
-		who: &AccountId,
-		receiver: &AccountId,
-		call: &Call,
-	) -> Result<(CurrencyId, Balance), DispatchError>;
+/// The extra fee of a call, besides the basic transaction fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ExtraFeeInfo<CurrencyId, Balance, AccountId> {
+	pub currency_id: CurrencyId,
+	pub amount: ExtraFeeAmount<Balance>,
+	/// The account receiving the fee, the treasury if `None`.
+	pub receiver: Option<AccountId>,
 }
 
-#[impl_trait_for_tuples::impl_for_tuples(30)]
-impl<AccountId, CurrencyId, Balance, Call> FeeDeductor<AccountId, CurrencyId, Balance, Call>
-	for Tuple
-{
-	fn deduct_fee(
-		who: &AccountId,
-		receiver: &AccountId,
-		call: &Call,
-	) -> Result<(CurrencyId, Balance), DispatchError> {
-		for_tuples!(
-			#(
-				if let Ok(result) = Tuple::deduct_fee(who, receiver, call) {
-					return Ok(result);
-				}
-			)*
-		);
-
-		return Err(DispatchError::Other("Failed to deduct extra fee."));
+pub type ExtraFeeInfoOf<T> =
+	ExtraFeeInfo<CurrencyIdOf<T>, PalletBalanceOf<T>, <T as frame_system::Config>::AccountId>;
+
+impl<T: Config> Pallet<T> {
+	/// Get the extra fee of a call: (currency_id, amount, receiver)
+	pub fn extra_fee_of(
+		pallet_name: &[u8],
+		function_name: &[u8],
+	) -> Option<(CurrencyIdOf<T>, PalletBalanceOf<T>, T::AccountId)> {
+		let info = ExtraFees::<T>::get((pallet_name, function_name))?;
+		let amount = match info.amount {
+			ExtraFeeAmount::Fixed(amount) => amount,
+			ExtraFeeAmount::Weight(weight) => T::ExtraFeeWeightToFee::calc(&weight),
+		};
+		let receiver = info.receiver.unwrap_or_else(T::TreasuryAccount::get);
+
+		Some((info.currency_id, amount, receiver))
 	}
-}
 
-pub trait NameGetter<Call> {
-	fn get_name(call: &Call) -> ExtraFeeName;
-}
-
-pub trait FeeGetter<Call> {
-	fn get_fee_info(call: &Call) -> (ExtraFeeName, bool);
-}
+	/// Deduct the extra fee of the call if there is one in the extra fee table.
+	pub(crate) fn deduct_extra_fee(who: &T::AccountId, call: &CallOf<T>) -> DispatchResult
+	where
+		CallOf<T>: GetCallMetadata,
+	{
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+
+		if let Some((currency_id, amount, receiver)) =
+			Self::extra_fee_of(pallet_name.as_bytes(), function_name.as_bytes())
+		{
+			T::MultiCurrency::transfer(currency_id, who, &receiver, amount)?;
+
+			Self::deposit_event(Event::ExtraFeeDeducted(
+				pallet_name.as_bytes().to_vec(),
+				function_name.as_bytes().to_vec(),
+				currency_id,
+				amount,
+			));
+		}
 
-pub struct ExtraFeeMatcher<T, FeeNameGetter, AggregateExtraFeeFilter>(
-	PhantomData<(T, FeeNameGetter, AggregateExtraFeeFilter)>,
-);
-impl<T: Config, FeeNameGetter, AggregateExtraFeeFilter> FeeGetter<CallOf<T>>
-	for ExtraFeeMatcher<T, FeeNameGetter, AggregateExtraFeeFilter>
-where
-	FeeNameGetter: NameGetter<CallOf<T>>,
-	AggregateExtraFeeFilter: Contains<CallOf<T>>,
-{
-	fn get_fee_info(call: &CallOf<T>) -> (ExtraFeeName, bool) {
-		let fee_name = FeeNameGetter::get_name(call.clone());
-		let if_extra_fee = AggregateExtraFeeFilter::contains(call);
-
-		(fee_name, if_extra_fee)
+		Ok(())
 	}
 }
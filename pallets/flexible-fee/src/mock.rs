@@ -28,7 +28,7 @@ use frame_support::{
 	parameter_types,
 	sp_runtime::{DispatchError, DispatchResult},
 	sp_std::marker::PhantomData,
-	traits::{Contains, EnsureOrigin, Nothing},
+	traits::{EnsureOrigin, Nothing},
 	weights::{
 		constants::ExtrinsicBaseWeight, IdentityFee, Weight, WeightToFeeCoefficients,
 		WeightToFeePolynomial,
@@ -61,7 +61,7 @@ use crate as flexible_fee;
 // use node_primitives::Balance;
 use crate::{
 	fee_dealer::FixedCurrencyFeeRate,
-	misc_fees::{ExtraFeeMatcher, MiscFeeHandler, NameGetter},
+	misc_fees::{ExtraFeeAmount, ExtraFeeInfo, ExtraFeeInfoOf},
 };
 
 pub type AccountId = AccountId32;
@@ -85,7 +85,7 @@ frame_support::construct_runtime!(
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>},
 		Balances: balances::{Pallet, Call, Storage, Event<T>},
 		// TransactionPayment: pallet_transaction_payment::{Module, Storage},
-		FlexibleFee: flexible_fee::{Pallet, Call, Storage,Event<T>},
+		FlexibleFee: flexible_fee::{Pallet, Call, Storage, Event<T>, Config<T>},
 		ZenlinkProtocol: zenlink_protocol::{Pallet, Call, Storage, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Storage, Event<T>},
 		Salp: bifrost_salp::{Pallet, Call, Storage, Event<T>},
@@ -193,45 +193,13 @@ impl orml_tokens::Config for Test {
 	type WeightInfo = ();
 }
 
-// Aggregate name getter to get fee names if the call needs to pay extra fees.
-// If any call need to pay extra fees, it should be added as an item here.
-// Used together with AggregateExtraFeeFilter below.
-pub struct FeeNameGetter;
-impl NameGetter<Call> for FeeNameGetter {
-	fn get_name(c: &Call) -> ExtraFeeName {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => ExtraFeeName::SalpContribute,
-			_ => ExtraFeeName::NoExtraFee,
-		}
-	}
-}
-
-// Aggregate filter to filter if the call needs to pay extra fees
-// If any call need to pay extra fees, it should be added as an item here.
-pub struct AggregateExtraFeeFilter;
-impl Contains<Call> for AggregateExtraFeeFilter {
-	fn contains(c: &Call) -> bool {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => true,
-			_ => false,
-		}
-	}
-}
-
-pub struct ContributeFeeFilter;
-impl Contains<Call> for ContributeFeeFilter {
-	fn contains(c: &Call) -> bool {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => true,
-			_ => false,
-		}
-	}
-}
-
 parameter_types! {
 	pub const NativeCurrencyId: CurrencyId = CurrencyId::Native(TokenSymbol::ASG);
 	pub const AlternativeFeeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
-	pub const AltFeeCurrencyExchangeRate: (u32, u32) = (1, 100);
+	pub const FeeSwapSlippage: Perbill = Perbill::from_percent(5);
+	pub const MaxCallNameLength: u32 = 32;
+	pub const MaxSponsoredCalls: u32 = 2;
+	pub const MaxSponsoredUsers: u32 = 2;
 	pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
 	pub SalpWeightHolder: XcmBaseWeight = XcmBaseWeight::from(4 * XCM_WEIGHT + ContributionWeight::get()) + u64::pow(2, 24).into();
 }
@@ -246,17 +214,14 @@ impl crate::Config for Test {
 	type TreasuryAccount = TreasuryAccount;
 	type NativeCurrencyId = NativeCurrencyId;
 	type AlternativeFeeCurrencyId = AlternativeFeeCurrencyId;
-	type AltFeeCurrencyExchangeRate = AltFeeCurrencyExchangeRate;
+	type ControlOrigin = frame_system::EnsureRoot<AccountId>;
+	type FeeSwapSlippage = FeeSwapSlippage;
+	type MaxCallNameLength = MaxCallNameLength;
+	type MaxSponsoredCalls = MaxSponsoredCalls;
+	type MaxSponsoredUsers = MaxSponsoredUsers;
 	type OnUnbalanced = ();
 	type WeightInfo = ();
-	type ExtraFeeMatcher = ExtraFeeMatcher<Test, FeeNameGetter, AggregateExtraFeeFilter>;
-	type MiscFeeHandler = MiscFeeHandler<
-		Test,
-		AlternativeFeeCurrencyId,
-		WeightToFee,
-		SalpWeightHolder,
-		ContributeFeeFilter,
-	>;
+	type ExtraFeeWeightToFee = WeightToFee;
 }
 
 parameter_types! {
@@ -398,13 +363,42 @@ impl ExtBuilder {
 			.assimilate_storage(&mut t)
 			.unwrap();
 
+		flexible_fee::GenesisConfig::<Test> {
+			fee_currency_exchange_rates: vec![(AlternativeFeeCurrencyId::get(), (1, 100))],
+			extra_fees: vec![salp_contribute_extra_fee()],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
 
+// Salp contributions pay the XCM fee in relay chain token.
+pub(crate) fn salp_contribute_extra_fee() -> (Vec<u8>, Vec<u8>, ExtraFeeInfoOf<Test>) {
+	(
+		b"Salp".to_vec(),
+		b"contribute".to_vec(),
+		ExtraFeeInfo {
+			currency_id: AlternativeFeeCurrencyId::get(),
+			amount: ExtraFeeAmount::Weight(SalpWeightHolder::get().into()),
+			receiver: None,
+		},
+	)
+}
+
 // Build genesis storage according to the mock runtime.
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	flexible_fee::GenesisConfig::<Test> {
+		fee_currency_exchange_rates: vec![(AlternativeFeeCurrencyId::get(), (1, 100))],
+		extra_fees: vec![salp_contribute_extra_fee()],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	t.into()
 }
 
 //************** Salp mock start *****************
@@ -0,0 +1,157 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fee sponsorship: a sponsor reserves a budget of native currency to pay the transaction fees of
+//! the users it covers, for the calls it covers. A user is covered once it accepts the sponsorship
+//! offered to it, and can leave it at any time.
+
+use frame_support::dispatch::{CallMetadata, GetCallMetadata};
+use scale_info::TypeInfo;
+
+use super::*;
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
+pub struct Sponsorship<Balance, BlockNumber> {
+	/// The native currency reserved from the sponsor to pay fees.
+	pub budget: Balance,
+	/// The most fee a sponsored user can be charged within a period.
+	pub per_user_limit: Balance,
+	/// The length of a period in blocks.
+	pub period: BlockNumber,
+	/// The number of sponsored users.
+	pub users: u32,
+	/// The number of sponsored calls.
+	pub calls: u32,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Find the sponsor who pays the fee of the call for the user, if any.
+	///
+	/// NOTE: A tipped transaction is never sponsored, the user pays the fee with its tip.
+	pub(crate) fn sponsor_of(
+		who: &T::AccountId,
+		call: &CallOf<T>,
+		fee: PalletBalanceOf<T>,
+		tip: PalletBalanceOf<T>,
+	) -> Option<T::AccountId>
+	where
+		CallOf<T>: GetCallMetadata,
+	{
+		if !tip.is_zero() {
+			return None;
+		}
+
+		let sponsor = Self::sponsor_of_user(who)?;
+		if !SponsoredUsers::<T>::contains_key(&sponsor, who) {
+			return None;
+		}
+		let sponsorship = Self::sponsorship(&sponsor)?;
+
+		let CallMetadata { function_name, pallet_name } = call.get_call_metadata();
+		let sponsored_call = (
+			CallNameOf::<T>::try_from(pallet_name.as_bytes().to_vec()).ok()?,
+			CallNameOf::<T>::try_from(function_name.as_bytes().to_vec()).ok()?,
+		);
+		if !SponsoredCalls::<T>::contains_key(&sponsor, sponsored_call) {
+			return None;
+		}
+
+		if sponsorship.budget < fee ||
+			Self::spent_in_period(&sponsor, who, &sponsorship).saturating_add(fee) >
+				sponsorship.per_user_limit
+		{
+			return None;
+		}
+
+		Some(sponsor)
+	}
+
+	/// Withdraw the fee of the user from the budget of the sponsor.
+	pub(crate) fn charge_sponsor(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		fee: PalletBalanceOf<T>,
+		reason: WithdrawReasons,
+	) -> Result<NegativeImbalanceOf<T>, DispatchError> {
+		let mut sponsorship = Self::sponsorship(sponsor).ok_or(Error::<T>::SponsorshipNotExist)?;
+		let period = Self::current_period(&sponsorship);
+		let spent = Self::spent_in_period(sponsor, who, &sponsorship).saturating_add(fee);
+		ensure!(spent <= sponsorship.per_user_limit, Error::<T>::SponsorshipLimitExceeded);
+		sponsorship.budget =
+			sponsorship.budget.checked_sub(&fee).ok_or(Error::<T>::NotEnoughBalance)?;
+
+		T::Currency::unreserve(sponsor, fee);
+		let imbalance =
+			T::Currency::withdraw(sponsor, fee, reason, ExistenceRequirement::AllowDeath).map_err(
+				|e| {
+					T::Currency::reserve(sponsor, fee).ok();
+					e
+				},
+			)?;
+
+		Sponsorships::<T>::insert(sponsor, sponsorship);
+		SponsoredUsage::<T>::insert(sponsor, who, (period, spent));
+
+		Self::deposit_event(Event::FeeSponsored(sponsor.clone(), who.clone(), fee));
+
+		Ok(imbalance)
+	}
+
+	/// Put the refunded fee back to the budget of the sponsor.
+	pub(crate) fn restore_sponsorship_budget(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		refund: PalletBalanceOf<T>,
+	) {
+		if refund.is_zero() || T::Currency::reserve(sponsor, refund).is_err() {
+			return;
+		}
+
+		Sponsorships::<T>::mutate(sponsor, |maybe_sponsorship| {
+			if let Some(sponsorship) = maybe_sponsorship {
+				sponsorship.budget = sponsorship.budget.saturating_add(refund);
+			}
+		});
+		// The usage is gone if the user is no longer sponsored.
+		SponsoredUsage::<T>::mutate_exists(sponsor, who, |maybe_usage| {
+			if let Some((_, spent)) = maybe_usage {
+				*spent = spent.saturating_sub(refund);
+			}
+		});
+	}
+
+	fn spent_in_period(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		sponsorship: &Sponsorship<PalletBalanceOf<T>, T::BlockNumber>,
+	) -> PalletBalanceOf<T> {
+		let (period, spent) = Self::sponsored_usage(sponsor, who);
+
+		if period == Self::current_period(sponsorship) {
+			spent
+		} else {
+			Zero::zero()
+		}
+	}
+
+	fn current_period(
+		sponsorship: &Sponsorship<PalletBalanceOf<T>, T::BlockNumber>,
+	) -> T::BlockNumber {
+		frame_system::Pallet::<T>::block_number() / sponsorship.period
+	}
+}
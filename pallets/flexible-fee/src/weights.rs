@@ -20,14 +20,96 @@
 #![allow(unused_imports)]
 #![allow(clippy::unnecessary_cast)]
 
-use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight as DbWeight, Weight},
+};
 
 pub trait WeightInfo {
 	fn set_user_fee_charge_order() -> Weight;
+	fn set_fee_currency_exchange_rate() -> Weight;
+	fn remove_fee_currency_exchange_rate() -> Weight;
+	fn set_extra_fee() -> Weight;
+	fn remove_extra_fee() -> Weight;
+	fn set_sponsorship() -> Weight;
+	fn fund_sponsorship() -> Weight;
+	fn close_sponsorship(n: u32) -> Weight;
+	fn update_sponsored_calls(n: u32) -> Weight;
+	fn update_sponsored_users(n: u32) -> Weight;
+	fn accept_sponsorship() -> Weight;
+	fn leave_sponsorship() -> Weight;
 }
 
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl crate::WeightInfo for () {
 	fn set_user_fee_charge_order() -> Weight {
 		50_000_000 as Weight
 	}
+
+	fn set_fee_currency_exchange_rate() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+
+	fn remove_fee_currency_exchange_rate() -> Weight {
+		(50_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_extra_fee() -> Weight {
+		(50_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+
+	fn remove_extra_fee() -> Weight {
+		(50_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_sponsorship() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+
+	fn fund_sponsorship() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+
+	fn close_sponsorship(n: u32) -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn update_sponsored_calls(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn update_sponsored_users(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn accept_sponsorship() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+
+	fn leave_sponsorship() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,40 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_api::decl_runtime_apis;
+
+decl_runtime_apis! {
+	pub trait LighteningRedeemRuntimeApi<PoolId, BlockNumber, Balance> where
+		PoolId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec
+	{
+		/// get the amount of token which can be exchanged for in the pool now
+		fn get_exchangeable_amount(pool_id: PoolId) -> Balance;
+
+		/// get the block of the next release in the pool and the amount released then
+		fn get_next_release(pool_id: PoolId) -> Option<(BlockNumber, Balance)>;
+
+		/// get the amount of vstoken and vsbond each needed to exchange for `token_amount` in the
+		/// pool, or `None` if the pool can't be exchanged for it
+		fn get_amount_needed(pool_id: PoolId, token_amount: Balance) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,159 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as JsonRpcResult};
+use jsonrpc_derive::rpc;
+pub use lightening_redeem_rpc_runtime_api::{
+	self as runtime_api, LighteningRedeemRuntimeApi as LighteningRedeemRuntimeApiT,
+};
+use node_primitives::Balance;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, sp_std::convert::TryInto, traits::Block as BlockT};
+
+pub use self::gen_client::Client as LighteningRedeemClient;
+
+#[derive(Clone, Debug)]
+pub struct LighteningRedeem<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> LighteningRedeem<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: PhantomData }
+	}
+}
+
+#[rpc]
+pub trait LighteningRedeemApi<BlockHash, PoolId, BlockNumber> {
+	/// rpc method for getting the amount of token which can be exchanged for in the pool now
+	#[rpc(name = "lighteningRedeem_getExchangeableAmount")]
+	fn get_exchangeable_amount(
+		&self,
+		pool_id: PoolId,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<NumberOrHex>;
+
+	/// rpc method for getting the block of the next release in the pool and the amount released
+	/// then
+	#[rpc(name = "lighteningRedeem_getNextRelease")]
+	fn get_next_release(
+		&self,
+		pool_id: PoolId,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<Option<(BlockNumber, NumberOrHex)>>;
+
+	/// rpc method for quoting the amount of vstoken and vsbond each needed to exchange for
+	/// `token_amount` in the pool, or none if the pool can't be exchanged for it
+	#[rpc(name = "lighteningRedeem_getAmountNeeded")]
+	fn get_amount_needed(
+		&self,
+		pool_id: PoolId,
+		token_amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<Option<NumberOrHex>>;
+}
+
+impl<C, Block> LighteningRedeem<C, Block> {
+	fn convert_rpc_params(value: Balance) -> Result<NumberOrHex, RpcError> {
+		value.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", value),
+			data: None,
+		})
+	}
+
+	fn parse_rpc_params(value: NumberOrHex) -> Result<Balance, RpcError> {
+		value.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{:?} doesn't fit in Balance representation", value),
+			data: None,
+		})
+	}
+
+	fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+		RpcError {
+			code: ErrorCode::InternalError,
+			message: message.to_owned(),
+			data: Some(format!("{:?}", e).into()),
+		}
+	}
+}
+
+impl<C, Block, PoolId, BlockNumber>
+	LighteningRedeemApi<<Block as BlockT>::Hash, PoolId, BlockNumber> for LighteningRedeem<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: LighteningRedeemRuntimeApiT<Block, PoolId, BlockNumber, Balance>,
+	PoolId: Codec,
+	BlockNumber: Codec,
+{
+	fn get_exchangeable_amount(
+		&self,
+		pool_id: PoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<NumberOrHex> {
+		let lightening_redeem_rpc_api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		match lightening_redeem_rpc_api.get_exchangeable_amount(&at, pool_id) {
+			Ok(amount) => Self::convert_rpc_params(amount),
+			Err(e) => Err(Self::runtime_error("Failed to get exchangeable amount of the pool.", e)),
+		}
+	}
+
+	fn get_next_release(
+		&self,
+		pool_id: PoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<Option<(BlockNumber, NumberOrHex)>> {
+		let lightening_redeem_rpc_api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		match lightening_redeem_rpc_api.get_next_release(&at, pool_id) {
+			Ok(Some((block_number, amount))) =>
+				Ok(Some((block_number, Self::convert_rpc_params(amount)?))),
+			Ok(None) => Ok(None),
+			Err(e) => Err(Self::runtime_error("Failed to get next release of the pool.", e)),
+		}
+	}
+
+	fn get_amount_needed(
+		&self,
+		pool_id: PoolId,
+		token_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<Option<NumberOrHex>> {
+		let lightening_redeem_rpc_api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let token_amount = Self::parse_rpc_params(token_amount)?;
+
+		match lightening_redeem_rpc_api.get_amount_needed(&at, pool_id, token_amount) {
+			Ok(Some(amount)) => Ok(Some(Self::convert_rpc_params(amount)?)),
+			Ok(None) => Ok(None),
+			Err(e) => Err(Self::runtime_error("Failed to quote amount needed of the pool.", e)),
+		}
+	}
+}
@@ -25,53 +25,86 @@ use frame_system::RawOrigin;
 use super::*;
 #[allow(unused_imports)]
 use crate::Pallet as LighteningRedeem;
-use crate::{PoolAmount, StartEndReleaseBlock};
+use crate::Pools;
+
+const POOL_ID: RedeemPoolId = (TokenSymbol::KSM, 2001, 13, 20);
+
+fn create_pool<T: Config>(pool_id: RedeemPoolId) -> Result<(), &'static str> {
+	let (token_symbol, index, first_slot, last_slot) = pool_id;
+	let call = Call::<T>::create_pool { token_symbol, index, first_slot, last_slot };
+	call.dispatch_bypass_filter(T::ControlOrigin::successful_origin())?;
+	Ok(())
+}
+
+fn fund_pool<T: Config>(pool_id: RedeemPoolId, amount: BalanceOf<T>) -> Result<(), &'static str> {
+	T::MultiCurrency::deposit(CurrencyId::Token(pool_id.0), &T::TreasuryAccount::get(), amount)?;
+	let call = Call::<T>::add_token_to_pool { pool_id, token_amount: amount };
+	call.dispatch_bypass_filter(T::ControlOrigin::successful_origin())?;
+	Ok(())
+}
 
 benchmarks! {
-	add_ksm_to_pool {
-		let caller: T::AccountId = whitelisted_caller();
+	create_pool {
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::create_pool {
+			token_symbol: TokenSymbol::KSM,
+			index: 2001,
+			first_slot: 13,
+			last_slot: 20,
+		};
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	add_token_to_pool {
+		create_pool::<T>(POOL_ID)?;
 		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u32 as u128);
-	}: _(RawOrigin::Signed(caller), token_amount)
+		T::MultiCurrency::deposit(CurrencyId::Token(TokenSymbol::KSM), &T::TreasuryAccount::get(), token_amount)?;
+
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::add_token_to_pool { pool_id: POOL_ID, token_amount };
+	}: {call.dispatch_bypass_filter(origin)?}
 
-	exchange_for_ksm {
+	exchange_for_token {
 		let caller: T::AccountId = whitelisted_caller();
 		// add 1000 ksm to the pool
 		let amount = BalanceOf::<T>::unique_saturated_from(1_000u128);
-		LighteningRedeem::<T>::add_ksm_to_pool(RawOrigin::Signed(caller.clone()).into(), BalanceOf::<T>::unique_saturated_from(amount))?;
+		create_pool::<T>(POOL_ID)?;
+		fund_pool::<T>(POOL_ID, amount)?;
 
-		PoolAmount::<T>::mutate(|amt| *amt += amount);
+		Pools::<T>::mutate(POOL_ID, |pool| {
+			if let Some(pool) = pool {
+				pool.pool_amount += amount;
+			}
+		});
+
+		let (vstoken, vsbond) = LighteningRedeem::<T>::vs_assets(POOL_ID);
+		T::MultiCurrency::deposit(vstoken, &caller, amount)?;
+		T::MultiCurrency::deposit(vsbond, &caller, amount)?;
 
 		let exchange_amount: u128 = 900;
 		let token_amount = BalanceOf::<T>::unique_saturated_from(exchange_amount);
-	}: _(RawOrigin::Signed(caller), token_amount)
+	}: _(RawOrigin::Signed(caller), POOL_ID, token_amount, amount)
 
 	edit_exchange_price {
+		create_pool::<T>(POOL_ID)?;
 		let origin = T::ControlOrigin::successful_origin();
 		let price = BalanceOf::<T>::unique_saturated_from(50u128);
-		let call = Call::<T>::edit_exchange_price { price };
+		let call = Call::<T>::edit_exchange_price { pool_id: POOL_ID, price };
 	}: {call.dispatch_bypass_filter(origin)?}
 
 	edit_release_per_day {
+		create_pool::<T>(POOL_ID)?;
 		let origin = T::ControlOrigin::successful_origin();
 		let amount_per_day = BalanceOf::<T>::unique_saturated_from(50u128);
-		let call = Call::<T>::edit_release_per_day { amount_per_day };
+		let call = Call::<T>::edit_release_per_day { pool_id: POOL_ID, amount_per_day };
 	}: {call.dispatch_bypass_filter(origin)?}
 
 	edit_release_start_and_end_block {
+		create_pool::<T>(POOL_ID)?;
 		let origin = T::ControlOrigin::successful_origin();
 		let start = BlockNumberFor::<T>::from(50u32);
 		let end = BlockNumberFor::<T>::from(100u32);
-		let call = Call::<T>::edit_release_start_and_end_block { start, end };
+		let call = Call::<T>::edit_release_start_and_end_block { pool_id: POOL_ID, start, end };
 	}: {call.dispatch_bypass_filter(origin)?}
-
-	on_initialize {
-		let caller: T::AccountId = whitelisted_caller();
-		let amount: u128 = 1_000;
-		LighteningRedeem::<T>::add_ksm_to_pool(RawOrigin::Signed(caller.clone()).into(), BalanceOf::<T>::unique_saturated_from(amount))?;
-		StartEndReleaseBlock::<T>::mutate(|interval| *interval = (T::BlockNumber::from(0u32), T::BlockNumber::from(100u32)));
-
-		let block_num = T::BlockNumber::from(10u32);
-	}:{LighteningRedeem::<T>::on_initialize(block_num);}
 }
 
 impl_benchmark_test_suite!(
@@ -15,15 +15,23 @@
 
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
-
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{pallet_prelude::*, PalletId};
+use codec::{Decode, Encode};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{PalletInfoAccess, StorageVersion},
+	transactional, PalletId,
+};
 use frame_system::pallet_prelude::*;
-use node_primitives::{CurrencyId, TokenSymbol};
+use node_primitives::{CurrencyId, LeasePeriod, ParaId, TokenSymbol};
 use orml_traits::MultiCurrency;
+use scale_info::TypeInfo;
 use sp_arithmetic::per_things::Percent;
-use sp_runtime::traits::{AccountIdConversion, Saturating, UniqueSaturatedFrom, Zero};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+	RuntimeDebug,
+};
 pub use weights::WeightInfo;
 
 mod mock;
@@ -37,11 +45,100 @@ pub use pallet::*;
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
+pub type PoolInfoOf<T> = PoolInfo<BalanceOf<T>, BlockNumberFor<T>>;
+
+/// A pool is keyed by the relay token and the crowdloan its vsToken/vsBond come from:
+/// `(relay token, ParaId, first_slot, last_slot)`.
+pub type RedeemPoolId = (TokenSymbol, ParaId, LeasePeriod, LeasePeriod);
 
 const TRILLION: u128 = 1_000_000_000_000;
 // These time units are defined in number of blocks.
 const BLOCKS_PER_DAY: u32 = 60 / 12 * 60 * 24;
 
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// The pool that existed before pools were keyed by crowdloan.
+const LEGACY_POOL_ID: RedeemPoolId = (TokenSymbol::KSM, 2001, 13, 20);
+
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct PoolInfo<Balance, BlockNumber> {
+	/// The remaining amount which can be exchanged for
+	pub pool_amount: Balance,
+	/// Exchange price discount: vsbond + vstoken => token
+	pub exchange_price_discount: Percent,
+	/// token amount that is released everyday.
+	pub token_release_per_day: Balance,
+	/// Token release start and end block
+	pub start_end_release_block: (BlockNumber, BlockNumber),
+	/// The last day of the release window whose release has been accounted for
+	pub released_until: BlockNumber,
+	/// Released amount which the pool account doesn't hold enough token for yet
+	pub pending_release: Balance,
+}
+
+impl<Balance: Zero, BlockNumber: Zero> PoolInfo<Balance, BlockNumber> {
+	fn new(token_release_per_day: Balance, exchange_price_discount: Percent) -> Self {
+		Self {
+			pool_amount: Zero::zero(),
+			exchange_price_discount,
+			token_release_per_day,
+			start_end_release_block: (Zero::zero(), Zero::zero()),
+			released_until: Zero::zero(),
+			pending_release: Zero::zero(),
+		}
+	}
+}
+
+pub mod migration {
+	use frame_support::storage::migration::take_storage_value;
+
+	use super::*;
+
+	/// Move the single KSM pool into `Pools` under `(KSM, 2001, 13, 20)`, together with the
+	/// tokens held by the pallet account. The days already released by the former hook are
+	/// marked as released.
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+			return 0;
+		}
+
+		let prefix = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+		let mut pool = PoolInfoOf::<T>::new(DefaultReleaseAmount::<T>(), DefaultPrice::<T>());
+		if let Some(amount) = take_storage_value(prefix, b"PoolAmount", &[]) {
+			pool.pool_amount = amount;
+		}
+		if let Some(amount) = take_storage_value(prefix, b"TokenReleasePerDay", &[]) {
+			pool.token_release_per_day = amount;
+		}
+		if let Some(interval) = take_storage_value(prefix, b"StartEndReleaseBlock", &[]) {
+			pool.start_end_release_block = interval;
+		}
+		let (start, end) = pool.start_end_release_block;
+		let now = frame_system::Pallet::<T>::block_number().min(end);
+		pool.released_until = start;
+		if now > start {
+			let day = BlockNumberFor::<T>::from(BLOCKS_PER_DAY);
+			pool.released_until = start.saturating_add((now - start) / day * day);
+		}
+		if let Some(price) = take_storage_value(prefix, b"ExchangePriceDiscount", &[]) {
+			pool.exchange_price_discount = price;
+		}
+		Pools::<T>::insert(LEGACY_POOL_ID, pool);
+
+		let pallet_account: AccountIdOf<T> = T::PalletId::get().into_account();
+		let pool_account = Pallet::<T>::pool_account(LEGACY_POOL_ID);
+		let (vstoken, vsbond) = Pallet::<T>::vs_assets(LEGACY_POOL_ID);
+		for currency_id in [CurrencyId::Token(TokenSymbol::KSM), vstoken, vsbond] {
+			let amount = T::MultiCurrency::free_balance(currency_id, &pallet_account);
+			let _ = T::MultiCurrency::transfer(currency_id, &pallet_account, &pool_account, amount);
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(11, 12)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -59,6 +156,10 @@ pub mod pallet {
 		/// ModuleID for creating sub account
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
+
+		/// The account pools are funded from
+		#[pallet::constant]
+		type TreasuryAccount: Get<AccountIdOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -70,33 +171,32 @@ pub mod pallet {
 		NotEnoughBalance,
 		InvalidReleaseInterval,
 		Overflow,
+		PoolNotExist,
+		PoolAlreadyExist,
+		ExceedMaxAmountNeeded,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// [exchanger, ksm_amount]
-		KSMExchanged(AccountIdOf<T>, BalanceOf<T>),
-		/// [adder, ksm_amount]
-		KSMAdded(AccountIdOf<T>, BalanceOf<T>),
-		/// [original_prce, new_price]
-		PriceEdited(BalanceOf<T>, BalanceOf<T>),
-		/// [start, end]
-		BlockIntervalEdited(BlockNumberFor<T>, BlockNumberFor<T>),
-		/// [originla_amount_per_day, amount_per_day]
-		ReleasedPerDayEdited(BalanceOf<T>, BalanceOf<T>),
+		/// [pool_id]
+		PoolCreated(RedeemPoolId),
+		/// [exchanger, pool_id, token_amount]
+		TokenExchanged(AccountIdOf<T>, RedeemPoolId, BalanceOf<T>),
+		/// [pool_id, token_amount]
+		TokenAdded(RedeemPoolId, BalanceOf<T>),
+		/// [pool_id, original_prce, new_price]
+		PriceEdited(RedeemPoolId, BalanceOf<T>, BalanceOf<T>),
+		/// [pool_id, start, end]
+		BlockIntervalEdited(RedeemPoolId, BlockNumberFor<T>, BlockNumberFor<T>),
+		/// [pool_id, originla_amount_per_day, amount_per_day]
+		ReleasedPerDayEdited(RedeemPoolId, BalanceOf<T>, BalanceOf<T>),
 	}
 
-	/// The remaining amount which can be exchanged for
+	/// The pools which vsToken and vsBond can be exchanged for the relay token in
 	#[pallet::storage]
-	#[pallet::getter(fn get_pool_amount)]
-	pub type PoolAmount<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
-
-	/// token amount that is released everyday.
-	#[pallet::storage]
-	#[pallet::getter(fn get_token_release_per_round)]
-	pub type TokenReleasePerDay<T: Config> =
-		StorageValue<_, BalanceOf<T>, ValueQuery, DefaultReleaseAmount<T>>;
+	#[pallet::getter(fn pools)]
+	pub type Pools<T: Config> = StorageMap<_, Twox64Concat, RedeemPoolId, PoolInfoOf<T>>;
 
 	// Defult release amount is 30 KSM
 	#[pallet::type_value]
@@ -104,18 +204,6 @@ pub mod pallet {
 		BalanceOf::<T>::unique_saturated_from(TRILLION.saturating_mul(30))
 	}
 
-	/// Token release start block
-	#[pallet::storage]
-	#[pallet::getter(fn get_start_and_end_release_block)]
-	pub type StartEndReleaseBlock<T: Config> =
-		StorageValue<_, (BlockNumberFor<T>, BlockNumberFor<T>), ValueQuery>;
-
-	/// Exchange price discount: vsbond + vstoken => token
-	#[pallet::storage]
-	#[pallet::getter(fn get_exchange_price_discount)]
-	pub type ExchangePriceDiscount<T: Config> =
-		StorageValue<_, Percent, ValueQuery, DefaultPrice<T>>;
-
 	// Defult price is 90%
 	#[pallet::type_value]
 	pub fn DefaultPrice<T: Config>() -> Percent {
@@ -123,88 +211,116 @@ pub mod pallet {
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		fn on_initialize(n: T::BlockNumber) -> Weight {
-			let (start, end) = Self::get_start_and_end_release_block();
-			// relsease fixed amount every day if within release interval and has enough balance in
-			// the pool account
-			if (n <= end) & (n > start) {
-				if (n - start) % BLOCKS_PER_DAY.into() == Zero::zero() {
-					let ksm = CurrencyId::Token(TokenSymbol::KSM);
-					let pool_account: AccountIdOf<T> = T::PalletId::get().into_account();
-					let releae_per_day = Self::get_token_release_per_round();
-					let total_amount = Self::get_pool_amount().saturating_add(releae_per_day);
-
-					if T::MultiCurrency::ensure_can_withdraw(ksm, &pool_account, total_amount)
-						.is_ok()
-					{
-						PoolAmount::<T>::mutate(|amt| *amt = total_amount);
-					}
-				}
-			}
-			T::WeightInfo::on_initialize()
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Anyone can add KSM to the pool.
-		#[pallet::weight(T::WeightInfo::add_ksm_to_pool())]
-		pub fn add_ksm_to_pool(origin: OriginFor<T>, token_amount: BalanceOf<T>) -> DispatchResult {
-			let adder = ensure_signed(origin)?;
-			let ksm_id = CurrencyId::Token(TokenSymbol::KSM);
+		/// Open a pool for the vsToken and vsBond of a crowdloan.
+		#[pallet::weight(T::WeightInfo::create_pool())]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			token_symbol: TokenSymbol,
+			#[pallet::compact] index: ParaId,
+			#[pallet::compact] first_slot: LeasePeriod,
+			#[pallet::compact] last_slot: LeasePeriod,
+		) -> DispatchResult {
+			// Check origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(first_slot <= last_slot, Error::<T>::InvalidReleaseInterval);
+
+			let pool_id = (token_symbol, index, first_slot, last_slot);
+			ensure!(!Pools::<T>::contains_key(pool_id), Error::<T>::PoolAlreadyExist);
+
+			Pools::<T>::insert(
+				pool_id,
+				PoolInfoOf::<T>::new(DefaultReleaseAmount::<T>(), DefaultPrice::<T>()),
+			);
+
+			Self::deposit_event(Event::PoolCreated(pool_id));
+
+			Ok(())
+		}
 
-			let token_balance = T::MultiCurrency::free_balance(ksm_id, &adder);
+		/// Fund a pool with relay token from the treasury.
+		#[pallet::weight(T::WeightInfo::add_token_to_pool())]
+		pub fn add_token_to_pool(
+			origin: OriginFor<T>,
+			pool_id: RedeemPoolId,
+			token_amount: BalanceOf<T>,
+		) -> DispatchResult {
+			// Check origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(Pools::<T>::contains_key(pool_id), Error::<T>::PoolNotExist);
+
+			let token = CurrencyId::Token(pool_id.0);
+			let treasury = T::TreasuryAccount::get();
+			let token_balance = T::MultiCurrency::free_balance(token, &treasury);
 			ensure!(token_balance >= token_amount, Error::<T>::NotEnoughBalance);
 
-			let pool_account: AccountIdOf<T> = T::PalletId::get().into_account();
-			T::MultiCurrency::transfer(ksm_id, &adder, &pool_account, token_amount)?;
+			T::MultiCurrency::transfer(
+				token,
+				&treasury,
+				&Self::pool_account(pool_id),
+				token_amount,
+			)?;
+			// back the released amount which was pending on funds
+			Pools::<T>::mutate(pool_id, |maybe_pool| {
+				if let Some(pool) = maybe_pool {
+					Self::release(pool_id, pool);
+				}
+			});
 
-			Self::deposit_event(Event::KSMAdded(adder, token_amount));
+			Self::deposit_event(Event::TokenAdded(pool_id, token_amount));
 
 			Ok(())
 		}
 
-		// exchange vsksm and vsbond for ksm
-		#[pallet::weight(T::WeightInfo::exchange_for_ksm())]
-		pub fn exchange_for_ksm(
+		// exchange vstoken and vsbond for token
+		#[pallet::weight(T::WeightInfo::exchange_for_token())]
+		#[transactional]
+		pub fn exchange_for_token(
 			origin: OriginFor<T>,
-			token_amount: BalanceOf<T>, // The KSM amount the user exchanges for
+			pool_id: RedeemPoolId,
+			token_amount: BalanceOf<T>,      // The token amount the user exchanges for
+			max_amount_needed: BalanceOf<T>, // The most vstoken and vsbond each the user gives out
 		) -> DispatchResult {
 			// Check origin
 			let exchanger = ensure_signed(origin)?;
-			ensure!(token_amount <= Self::get_pool_amount(), Error::<T>::ExceedPoolAmount);
+			let mut pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotExist)?;
+			Self::release(pool_id, &mut pool);
+			ensure!(token_amount <= pool.pool_amount, Error::<T>::ExceedPoolAmount);
 
-			// Check exchanger's vsksm and vsbond balance
-			let vsksm = CurrencyId::VSToken(TokenSymbol::KSM);
-			let vsbond = CurrencyId::VSBond(TokenSymbol::BNC, 2001, 13, 20);
-			let ksm = CurrencyId::Token(TokenSymbol::KSM);
+			// Check exchanger's vstoken and vsbond balance
+			let (vstoken, vsbond) = Self::vs_assets(pool_id);
+			let token = CurrencyId::Token(pool_id.0);
 
-			ensure!(
-				Self::get_exchange_price_discount() != Percent::zero(),
-				Error::<T>::DenominatorZero
-			);
-			let amount_needed =
-				Self::get_exchange_price_discount().saturating_reciprocal_mul(token_amount);
+			let amount_needed = Self::amount_needed_of(&pool, token_amount)?;
+			ensure!(amount_needed <= max_amount_needed, Error::<T>::ExceedMaxAmountNeeded);
 
-			let vsksm_balance = T::MultiCurrency::free_balance(vsksm, &exchanger);
+			let vstoken_balance = T::MultiCurrency::free_balance(vstoken, &exchanger);
 			let vsbond_balance = T::MultiCurrency::free_balance(vsbond, &exchanger);
-			ensure!(vsksm_balance >= amount_needed, Error::<T>::NotEnoughBalance);
+			ensure!(vstoken_balance >= amount_needed, Error::<T>::NotEnoughBalance);
 			ensure!(vsbond_balance >= amount_needed, Error::<T>::NotEnoughBalance);
 
 			// Make changes to account token balances
-			let pool_account: AccountIdOf<T> = T::PalletId::get().into_account();
-			T::MultiCurrency::ensure_can_withdraw(ksm, &pool_account, token_amount)?;
-			PoolAmount::<T>::mutate(|amt| *amt = amt.saturating_sub(token_amount));
-
-			T::MultiCurrency::transfer(vsksm, &exchanger, &pool_account, amount_needed)?;
+			let pool_account = Self::pool_account(pool_id);
+			T::MultiCurrency::ensure_can_withdraw(token, &pool_account, token_amount)?;
+			T::MultiCurrency::transfer(vstoken, &exchanger, &pool_account, amount_needed)?;
 			T::MultiCurrency::transfer(vsbond, &exchanger, &pool_account, amount_needed)?;
-			T::MultiCurrency::transfer(ksm, &pool_account, &exchanger, token_amount)?;
+			T::MultiCurrency::transfer(token, &pool_account, &exchanger, token_amount)?;
 
-			Self::deposit_event(Event::KSMExchanged(exchanger, token_amount));
+			pool.pool_amount = pool.pool_amount.saturating_sub(token_amount);
+			Pools::<T>::insert(pool_id, pool);
+
+			Self::deposit_event(Event::TokenExchanged(exchanger, pool_id, token_amount));
 
 			Ok(())
 		}
@@ -213,8 +329,9 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::edit_exchange_price())]
 		pub fn edit_exchange_price(
 			origin: OriginFor<T>,
-			price: BalanceOf<T>, /* the mumber of ksm we can get by giving out 100 vsksm and 100
-			                      * vsbond */
+			pool_id: RedeemPoolId,
+			price: BalanceOf<T>, /* the mumber of token we can get by giving out 100 vstoken and
+			                      * 100 vsbond */
 		) -> DispatchResult {
 			// Check origin
 			T::ControlOrigin::ensure_origin(origin)?;
@@ -222,12 +339,20 @@ pub mod pallet {
 
 			let price_percent: Percent =
 				Percent::from_rational(price, BalanceOf::<T>::unique_saturated_from(100u128));
-			let original_price: BalanceOf<T> = Self::get_exchange_price_discount()
-				.mul_floor(BalanceOf::<T>::unique_saturated_from(100u128));
 
-			ExchangePriceDiscount::<T>::mutate(|p| *p = price_percent);
+			let original_price = Pools::<T>::try_mutate(
+				pool_id,
+				|maybe_pool| -> Result<BalanceOf<T>, DispatchError> {
+					let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotExist)?;
+					let original_price = pool
+						.exchange_price_discount
+						.mul_floor(BalanceOf::<T>::unique_saturated_from(100u128));
+					pool.exchange_price_discount = price_percent;
+					Ok(original_price)
+				},
+			)?;
 
-			Self::deposit_event(Event::PriceEdited(original_price, price));
+			Self::deposit_event(Event::PriceEdited(pool_id, original_price, price));
 
 			Ok(())
 		}
@@ -236,16 +361,27 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::edit_release_per_day())]
 		pub fn edit_release_per_day(
 			origin: OriginFor<T>,
+			pool_id: RedeemPoolId,
 			amount_per_day: BalanceOf<T>,
 		) -> DispatchResult {
 			// Check origin
 			T::ControlOrigin::ensure_origin(origin)?;
 			ensure!(amount_per_day > Zero::zero(), Error::<T>::NotGreaterThanZero);
 
-			let originla_amount_per_day = Self::get_token_release_per_round();
-			TokenReleasePerDay::<T>::mutate(|amt| *amt = amount_per_day);
+			let originla_amount_per_day = Pools::<T>::try_mutate(
+				pool_id,
+				|maybe_pool| -> Result<BalanceOf<T>, DispatchError> {
+					let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotExist)?;
+					// the days passed are released at the former rate
+					Self::release(pool_id, pool);
+					let originla_amount_per_day = pool.token_release_per_day;
+					pool.token_release_per_day = amount_per_day;
+					Ok(originla_amount_per_day)
+				},
+			)?;
 
 			Self::deposit_event(Event::ReleasedPerDayEdited(
+				pool_id,
 				originla_amount_per_day,
 				amount_per_day,
 			));
@@ -257,6 +393,7 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::edit_release_start_and_end_block())]
 		pub fn edit_release_start_and_end_block(
 			origin: OriginFor<T>,
+			pool_id: RedeemPoolId,
 			start: BlockNumberFor<T>,
 			end: BlockNumberFor<T>,
 		) -> DispatchResult {
@@ -267,11 +404,95 @@ pub mod pallet {
 			ensure!(start > current_block_number, Error::<T>::InvalidReleaseInterval);
 			ensure!(end >= start, Error::<T>::InvalidReleaseInterval);
 
-			StartEndReleaseBlock::<T>::mutate(|interval| *interval = (start, end));
+			Pools::<T>::try_mutate(pool_id, |maybe_pool| -> DispatchResult {
+				let pool = maybe_pool.as_mut().ok_or(Error::<T>::PoolNotExist)?;
+				Self::release(pool_id, pool);
+				pool.start_end_release_block = (start, end);
+				pool.released_until = start;
+				Ok(())
+			})?;
 
-			Self::deposit_event(Event::BlockIntervalEdited(start, end));
+			Self::deposit_event(Event::BlockIntervalEdited(pool_id, start, end));
 
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the relay token of a pool and the vsToken/vsBond exchanged in.
+		pub fn pool_account(pool_id: RedeemPoolId) -> AccountIdOf<T> {
+			T::PalletId::get().into_sub_account(pool_id)
+		}
+
+		/// Release the days of the release window passed since the last release, as far as the
+		/// pool account holds the token for them.
+		pub(crate) fn release(pool_id: RedeemPoolId, pool: &mut PoolInfoOf<T>) {
+			let (start, end) = pool.start_end_release_block;
+			let now = frame_system::Pallet::<T>::block_number().min(end);
+			let released_until = pool.released_until.max(start);
+			let day = BlockNumberFor::<T>::from(BLOCKS_PER_DAY);
+
+			if now > released_until {
+				let days = (now - released_until) / day;
+				pool.released_until = released_until.saturating_add(days * day);
+				let days: u128 = days.unique_saturated_into();
+				pool.pending_release = pool.pending_release.saturating_add(
+					pool.token_release_per_day
+						.saturating_mul(BalanceOf::<T>::unique_saturated_from(days)),
+				);
+			}
+
+			let token = CurrencyId::Token(pool_id.0);
+			let unreleased = T::MultiCurrency::free_balance(token, &Self::pool_account(pool_id))
+				.saturating_sub(pool.pool_amount);
+			let released = pool.pending_release.min(unreleased);
+			pool.pool_amount = pool.pool_amount.saturating_add(released);
+			pool.pending_release = pool.pending_release.saturating_sub(released);
+		}
+
+		/// The amount of token which can be exchanged for in the pool now.
+		pub fn exchangeable_amount(pool_id: RedeemPoolId) -> BalanceOf<T> {
+			Self::pools(pool_id).map_or(Zero::zero(), |mut pool| {
+				Self::release(pool_id, &mut pool);
+				pool.pool_amount
+			})
+		}
+
+		/// The block of the next release in the pool and the amount released then, if any is left
+		/// in the release window.
+		pub fn next_release(pool_id: RedeemPoolId) -> Option<(BlockNumberFor<T>, BalanceOf<T>)> {
+			let mut pool = Self::pools(pool_id)?;
+			Self::release(pool_id, &mut pool);
+
+			let (start, end) = pool.start_end_release_block;
+			let next = pool.released_until.max(start).saturating_add(BLOCKS_PER_DAY.into());
+			if next > end {
+				return None;
+			}
+
+			Some((next, pool.token_release_per_day))
+		}
+
+		/// The amount of vstoken and vsbond each needed to exchange for `token_amount` in the pool.
+		pub fn amount_needed(
+			pool_id: RedeemPoolId,
+			token_amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotExist)?;
+			Self::amount_needed_of(&pool, token_amount)
+		}
+
+		fn amount_needed_of(
+			pool: &PoolInfoOf<T>,
+			token_amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			ensure!(pool.exchange_price_discount != Percent::zero(), Error::<T>::DenominatorZero);
+			Ok(pool.exchange_price_discount.saturating_reciprocal_mul(token_amount))
+		}
+
+		pub(crate) fn vs_assets(pool_id: RedeemPoolId) -> (CurrencyId, CurrencyId) {
+			let (token_symbol, index, first_slot, last_slot) = pool_id;
+			CurrencyId::vsAssets(token_symbol, index, first_slot, last_slot)
+		}
+	}
 }
@@ -36,6 +36,7 @@ use sp_runtime::{
 };
 
 use crate as bifrost_lightening_redeem;
+use crate::RedeemPoolId;
 
 pub type BlockNumber = u64;
 pub type Balance = u64;
@@ -48,6 +49,12 @@ pub const vsBond: CurrencyId = CurrencyId::VSBond(TokenSymbol::BNC, 2001, 13, 20
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId = AccountId32::new([3u8; 32]);
+pub const TREASURY: AccountId = AccountId32::new([9u8; 32]);
+pub const KSM_POOL: RedeemPoolId = (TokenSymbol::KSM, 2001, 13, 20);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const vsDOT: CurrencyId = CurrencyId::VSToken(TokenSymbol::DOT);
+pub const vsBondDOT: CurrencyId = CurrencyId::VSBond(TokenSymbol::DOT, 2004, 6, 13);
+pub const DOT_POOL: RedeemPoolId = (TokenSymbol::DOT, 2004, 6, 13);
 
 frame_support::construct_runtime!(
 	pub enum Runtime where
@@ -133,6 +140,7 @@ impl pallet_collective::Config<CouncilCollective> for Runtime {
 
 parameter_types! {
 	pub const LighteningRedeemPalletId: PalletId = PalletId(*b"lighten#");
+	pub const TreasuryAccount: AccountId = TREASURY;
 }
 
 impl bifrost_lightening_redeem::Config for Runtime {
@@ -141,6 +149,7 @@ impl bifrost_lightening_redeem::Config for Runtime {
 	type ControlOrigin =
 		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
 	type PalletId = LighteningRedeemPalletId;
+	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
 }
 
@@ -167,17 +176,21 @@ impl ExtBuilder {
 			(ALICE, vsKSM, 100),
 			(BOB, vsBond, 100),
 			(BOB, vsKSM, 100),
+			(BOB, vsBondDOT, 100),
+			(BOB, vsDOT, 100),
+			(TREASURY, KSM, 100),
+			(TREASURY, DOT, 100),
 		])
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn one_hundred_precision_for_each_currency_type_for_whitelist_account(self) -> Self {
 		let whitelist_caller: AccountId = whitelisted_caller();
-		let pool_account = LighteningRedeemPalletId::get().into_account();
 
 		self.balances(vec![
-			(whitelist_caller.clone(), KSM, 100_000_000_000_000),
-			(pool_account.clone(), KSM, 100_000_000_000_000),
+			(whitelist_caller.clone(), vsKSM, 100_000_000_000_000),
+			(whitelist_caller.clone(), vsBond, 100_000_000_000_000),
+			(TREASURY, KSM, 100_000_000_000_000),
 		])
 	}
 
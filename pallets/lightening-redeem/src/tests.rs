@@ -24,34 +24,102 @@ use sp_runtime::DispatchError::BadOrigin;
 use crate::{mock::*, *};
 
 fn initialize_pool() {
+	assert_ok!(LighteningRedeem::create_pool(
+		pallet_collective::RawOrigin::Members(2, 3).into(),
+		TokenSymbol::KSM,
+		2001,
+		13,
+		20
+	));
 	assert_ok!(LighteningRedeem::edit_release_start_and_end_block(
 		pallet_collective::RawOrigin::Members(2, 3).into(),
+		KSM_POOL,
 		10,
 		15000
 	));
 	assert_ok!(LighteningRedeem::edit_release_per_day(
 		pallet_collective::RawOrigin::Members(2, 3).into(),
+		KSM_POOL,
 		BalanceOf::<Runtime>::unique_saturated_from(50)
 	));
-	assert_ok!(LighteningRedeem::add_ksm_to_pool(Origin::signed(ALICE), 100));
+	assert_ok!(LighteningRedeem::add_token_to_pool(
+		pallet_collective::RawOrigin::Members(2, 3).into(),
+		KSM_POOL,
+		100
+	));
+}
+
+fn create_ksm_pool() {
+	assert_ok!(LighteningRedeem::create_pool(
+		pallet_collective::RawOrigin::Members(2, 3).into(),
+		TokenSymbol::KSM,
+		2001,
+		13,
+		20
+	));
+}
+
+#[test]
+fn create_pool_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		// Charlie doesn't have the permission to create a pool.
+		assert_noop!(
+			LighteningRedeem::create_pool(Origin::signed(CHARLIE), TokenSymbol::KSM, 2001, 13, 20),
+			BadOrigin
+		);
+
+		create_ksm_pool();
+		let pool = LighteningRedeem::pools(KSM_POOL).unwrap();
+		assert_eq!(pool.pool_amount, 0);
+		assert_eq!(pool.token_release_per_day, 30 * TRILLION as Balance);
+		assert_eq!(pool.exchange_price_discount, Percent::from_percent(90));
+
+		assert_noop!(
+			LighteningRedeem::create_pool(
+				pallet_collective::RawOrigin::Members(2, 3).into(),
+				TokenSymbol::KSM,
+				2001,
+				13,
+				20
+			),
+			Error::<Runtime>::PoolAlreadyExist
+		);
+	});
 }
 
 #[test]
 fn edit_release_start_and_end_block_should_work() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_noop!(
+			LighteningRedeem::edit_release_start_and_end_block(
+				pallet_collective::RawOrigin::Members(2, 3).into(),
+				KSM_POOL,
+				10,
+				15000
+			),
+			Error::<Runtime>::PoolNotExist
+		);
+		create_ksm_pool();
+
 		// Charlie doesn't have the permission to edit.
 		assert_noop!(
-			LighteningRedeem::edit_release_start_and_end_block(Origin::signed(CHARLIE), 10, 15000),
+			LighteningRedeem::edit_release_start_and_end_block(
+				Origin::signed(CHARLIE),
+				KSM_POOL,
+				10,
+				15000
+			),
 			BadOrigin
 		);
 
 		assert_ok!(LighteningRedeem::edit_release_start_and_end_block(
 			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
 			10,
 			15000
 		));
 
-		let (start, end) = LighteningRedeem::get_start_and_end_release_block();
+		let (start, end) = LighteningRedeem::pools(KSM_POOL).unwrap().start_end_release_block;
 		assert_eq!(start, 10);
 		assert_eq!(end, 15000);
 	});
@@ -60,19 +128,28 @@ fn edit_release_start_and_end_block_should_work() {
 #[test]
 fn edit_exchange_price_should_work() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
-		let original_price = LighteningRedeem::get_exchange_price_discount()
+		create_ksm_pool();
+		let original_price = LighteningRedeem::pools(KSM_POOL)
+			.unwrap()
+			.exchange_price_discount
 			.mul_floor(BalanceOf::<Runtime>::unique_saturated_from(100u128));
 		assert_eq!(original_price, 90);
 
 		// Charlie doesn't have the permission to edit.
-		assert_noop!(LighteningRedeem::edit_exchange_price(Origin::signed(CHARLIE), 80), BadOrigin);
+		assert_noop!(
+			LighteningRedeem::edit_exchange_price(Origin::signed(CHARLIE), KSM_POOL, 80),
+			BadOrigin
+		);
 
 		assert_ok!(LighteningRedeem::edit_exchange_price(
 			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
 			80
 		));
 
-		let current_price = LighteningRedeem::get_exchange_price_discount()
+		let current_price = LighteningRedeem::pools(KSM_POOL)
+			.unwrap()
+			.exchange_price_discount
 			.mul_floor(BalanceOf::<Runtime>::unique_saturated_from(100u128));
 		assert_eq!(current_price, 80);
 	});
@@ -81,7 +158,9 @@ fn edit_exchange_price_should_work() {
 #[test]
 fn edit_release_per_day_should_work() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
-		let originla_amount_per_day = LighteningRedeem::get_token_release_per_round();
+		create_ksm_pool();
+		let originla_amount_per_day =
+			LighteningRedeem::pools(KSM_POOL).unwrap().token_release_per_day;
 		assert_eq!(
 			originla_amount_per_day,
 			BalanceOf::<Runtime>::unique_saturated_from(30 * TRILLION)
@@ -91,6 +170,7 @@ fn edit_release_per_day_should_work() {
 		assert_noop!(
 			LighteningRedeem::edit_release_per_day(
 				Origin::signed(CHARLIE),
+				KSM_POOL,
 				BalanceOf::<Runtime>::unique_saturated_from(50 * TRILLION)
 			),
 			BadOrigin
@@ -98,10 +178,12 @@ fn edit_release_per_day_should_work() {
 
 		assert_ok!(LighteningRedeem::edit_release_per_day(
 			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
 			BalanceOf::<Runtime>::unique_saturated_from(50 * TRILLION)
 		));
 
-		let current_amount_per_day = LighteningRedeem::get_token_release_per_round();
+		let current_amount_per_day =
+			LighteningRedeem::pools(KSM_POOL).unwrap().token_release_per_day;
 		assert_eq!(
 			current_amount_per_day,
 			BalanceOf::<Runtime>::unique_saturated_from(50 * TRILLION)
@@ -110,25 +192,40 @@ fn edit_release_per_day_should_work() {
 }
 
 #[test]
-fn add_ksm_to_pool_should_work() {
+fn add_token_to_pool_should_work() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
-		// Charlie doesn't have KSM.
+		create_ksm_pool();
+
+		// Alice doesn't have the permission to fund a pool.
+		assert_noop!(
+			LighteningRedeem::add_token_to_pool(Origin::signed(ALICE), KSM_POOL, 80),
+			BadOrigin
+		);
+
+		// The treasury only has 100 KSM.
 		assert_noop!(
-			LighteningRedeem::add_ksm_to_pool(Origin::signed(CHARLIE), 80),
+			LighteningRedeem::add_token_to_pool(
+				pallet_collective::RawOrigin::Members(2, 3).into(),
+				KSM_POOL,
+				180
+			),
 			Error::<Runtime>::NotEnoughBalance
 		);
 
-		// Charlie succuessfully issue 800 unit of ZLK to Alice account
-		assert_ok!(LighteningRedeem::add_ksm_to_pool(Origin::signed(ALICE), 80));
-		assert_eq!(Tokens::free_balance(KSM, &ALICE), 20);
+		assert_ok!(LighteningRedeem::add_token_to_pool(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
+			80
+		));
+		assert_eq!(Tokens::free_balance(KSM, &TREASURY), 20);
 
-		let pool_account = <Runtime as crate::Config>::PalletId::get().into_account();
+		let pool_account = LighteningRedeem::pool_account(KSM_POOL);
 		assert_eq!(Tokens::free_balance(KSM, &pool_account), 80);
 	});
 }
 
 #[test]
-fn exchange_for_ksm_should_work() {
+fn exchange_for_token_should_work() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
 		run_to_block(2);
 		initialize_pool();
@@ -137,19 +234,19 @@ fn exchange_for_ksm_should_work() {
 		// In block 9, the pool still dosn't have any KSM which can be redeemed.
 		run_to_block(20);
 		assert_noop!(
-			LighteningRedeem::exchange_for_ksm(Origin::signed(CHARLIE), 90),
+			LighteningRedeem::exchange_for_token(Origin::signed(CHARLIE), KSM_POOL, 90, 100),
 			Error::<Runtime>::ExceedPoolAmount
 		);
 
 		run_to_block(7300);
-		assert_eq!(LighteningRedeem::get_pool_amount(), 50);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 50);
 		// Charlie doesn't have vsKSM and vsBond.
 		assert_noop!(
-			LighteningRedeem::exchange_for_ksm(Origin::signed(CHARLIE), 30),
+			LighteningRedeem::exchange_for_token(Origin::signed(CHARLIE), KSM_POOL, 30, 100),
 			Error::<Runtime>::NotEnoughBalance
 		);
 
-		let pool_account = <Runtime as crate::Config>::PalletId::get().into_account();
+		let pool_account = LighteningRedeem::pool_account(KSM_POOL);
 
 		// Before doing exchange
 		assert_eq!(Tokens::free_balance(vsKSM, &BOB), 100);
@@ -161,10 +258,10 @@ fn exchange_for_ksm_should_work() {
 		assert_eq!(Tokens::free_balance(vsBond, &pool_account), 0);
 
 		run_to_block(14900);
-		assert_eq!(LighteningRedeem::get_pool_amount(), 100);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 100);
 
 		// perform the exchange
-		assert_ok!(LighteningRedeem::exchange_for_ksm(Origin::signed(BOB), 90));
+		assert_ok!(LighteningRedeem::exchange_for_token(Origin::signed(BOB), KSM_POOL, 90, 100));
 		assert_eq!(Tokens::free_balance(vsKSM, &BOB), 0);
 		assert_eq!(Tokens::free_balance(vsBond, &BOB), 0);
 		assert_eq!(Tokens::free_balance(KSM, &BOB), 90);
@@ -173,6 +270,143 @@ fn exchange_for_ksm_should_work() {
 		assert_eq!(Tokens::free_balance(vsKSM, &pool_account), 100);
 		assert_eq!(Tokens::free_balance(vsBond, &pool_account), 100);
 
-		assert_eq!(LighteningRedeem::get_pool_amount(), 10);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 10);
+	});
+}
+
+#[test]
+fn pools_should_be_independent() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		run_to_block(2);
+		initialize_pool();
+		assert_ok!(LighteningRedeem::create_pool(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			TokenSymbol::DOT,
+			2004,
+			6,
+			13
+		));
+		assert_ok!(LighteningRedeem::edit_release_start_and_end_block(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			DOT_POOL,
+			10,
+			15000
+		));
+		assert_ok!(LighteningRedeem::edit_release_per_day(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			DOT_POOL,
+			40
+		));
+		assert_ok!(LighteningRedeem::edit_exchange_price(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			DOT_POOL,
+			50
+		));
+		assert_ok!(LighteningRedeem::add_token_to_pool(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			DOT_POOL,
+			100
+		));
+
+		run_to_block(7300);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 50);
+		assert_eq!(LighteningRedeem::exchangeable_amount(DOT_POOL), 40);
+
+		// 20 DOT at a 50% discount costs 40 vsDOT and 40 vsBond of the DOT crowdloan.
+		assert_ok!(LighteningRedeem::exchange_for_token(Origin::signed(BOB), DOT_POOL, 20, 40));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 20);
+		assert_eq!(Tokens::free_balance(vsDOT, &BOB), 60);
+		assert_eq!(Tokens::free_balance(vsBondDOT, &BOB), 60);
+		assert_eq!(Tokens::free_balance(vsKSM, &BOB), 100);
+		assert_eq!(Tokens::free_balance(vsBond, &BOB), 100);
+
+		assert_eq!(LighteningRedeem::exchangeable_amount(DOT_POOL), 20);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 50);
+		assert_eq!(Tokens::free_balance(KSM, &LighteningRedeem::pool_account(KSM_POOL)), 100);
+	});
+}
+
+#[test]
+fn release_should_follow_window() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		let day = BLOCKS_PER_DAY as BlockNumber;
+		run_to_block(2);
+		create_ksm_pool();
+		assert_ok!(LighteningRedeem::edit_release_start_and_end_block(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
+			10,
+			10 + 3 * day + 100
+		));
+		assert_ok!(LighteningRedeem::edit_release_per_day(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
+			30
+		));
+		assert_ok!(LighteningRedeem::add_token_to_pool(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
+			50
+		));
+
+		// Nothing is released until a day of the window has passed.
+		System::set_block_number(10 + day - 1);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 0);
+		assert_eq!(LighteningRedeem::next_release(KSM_POOL), Some((10 + day, 30)));
+
+		System::set_block_number(10 + day);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 30);
+		assert_eq!(LighteningRedeem::next_release(KSM_POOL), Some((10 + 2 * day, 30)));
+
+		// The pool only holds 50 KSM, the rest of the release waits for funds.
+		System::set_block_number(10 + 2 * day);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 50);
+		assert_ok!(LighteningRedeem::add_token_to_pool(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
+			50
+		));
+		assert_eq!(LighteningRedeem::pools(KSM_POOL).unwrap().pool_amount, 60);
+		assert_eq!(LighteningRedeem::pools(KSM_POOL).unwrap().pending_release, 0);
+
+		// The last day of the window is released and nothing after the end block.
+		System::set_block_number(10 + 3 * day);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 90);
+		assert_eq!(LighteningRedeem::next_release(KSM_POOL), None);
+
+		System::set_block_number(10 + 5 * day);
+		assert_eq!(LighteningRedeem::exchangeable_amount(KSM_POOL), 90);
+	});
+}
+
+#[test]
+fn exchange_should_respect_max_amount_needed() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		run_to_block(2);
+		initialize_pool();
+		System::set_block_number(10 + 2 * BLOCKS_PER_DAY as BlockNumber);
+
+		assert_eq!(LighteningRedeem::amount_needed(KSM_POOL, 90), Ok(100));
+		assert_eq!(
+			LighteningRedeem::amount_needed(DOT_POOL, 90),
+			Err(Error::<Runtime>::PoolNotExist.into())
+		);
+
+		// The price is edited before the exchange is executed.
+		assert_ok!(LighteningRedeem::edit_exchange_price(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			KSM_POOL,
+			75
+		));
+		assert_eq!(LighteningRedeem::amount_needed(KSM_POOL, 90), Ok(120));
+		assert_noop!(
+			LighteningRedeem::exchange_for_token(Origin::signed(BOB), KSM_POOL, 90, 100),
+			Error::<Runtime>::ExceedMaxAmountNeeded
+		);
+
+		assert_ok!(LighteningRedeem::exchange_for_token(Origin::signed(BOB), KSM_POOL, 75, 100));
+		assert_eq!(Tokens::free_balance(KSM, &BOB), 75);
+		assert_eq!(Tokens::free_balance(vsKSM, &BOB), 0);
+		assert_eq!(Tokens::free_balance(vsBond, &BOB), 0);
 	});
 }
@@ -27,22 +27,33 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for the pallet.
 pub trait WeightInfo {
-	fn add_ksm_to_pool() -> Weight;
-	fn exchange_for_ksm() -> Weight;
+	fn create_pool() -> Weight;
+	fn add_token_to_pool() -> Weight;
+	fn exchange_for_token() -> Weight;
 	fn edit_exchange_price() -> Weight;
 	fn edit_release_per_day() -> Weight;
 	fn edit_release_start_and_end_block() -> Weight;
-	fn on_initialize() -> Weight;
 }
 
-// For backwards compatibility and tests
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl WeightInfo for () {
-	fn add_ksm_to_pool() -> Weight {
-		(50_000_000 as Weight)
+	fn create_pool() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 
-	fn exchange_for_ksm() -> Weight {
-		(50_000_000 as Weight)
+	fn add_token_to_pool() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+
+	fn exchange_for_token() -> Weight {
+		(400_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
 	}
 
 	fn edit_exchange_price() -> Weight {
@@ -56,8 +67,4 @@ impl WeightInfo for () {
 	fn edit_release_start_and_end_block() -> Weight {
 		(50_000_000 as Weight)
 	}
-
-	fn on_initialize() -> Weight {
-		(50_000_000 as Weight)
-	}
 }
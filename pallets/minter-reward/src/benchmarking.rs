@@ -19,6 +19,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
 use frame_system::RawOrigin;
 use node_primitives::{CurrencyId, TokenSymbol};
 use sp_runtime::traits::UniqueSaturatedFrom;
@@ -43,6 +44,39 @@ benchmarks! {
 
 	}:{MinterReward::<T>::on_initialize(block_num);}
 
+	sample_prices {
+		let n in 0 .. 2;
+
+		for currency_id in [CurrencyId::VToken(TokenSymbol::KSM), CurrencyId::VToken(TokenSymbol::DOT)].iter().take(n as usize) {
+			MinterReward::<T>::track_price(*currency_id, T::BlockNumber::from(1u32));
+		}
+		let block_num = T::BlockNumber::from(2u32);
+	}:{
+		for currency_id in [CurrencyId::VToken(TokenSymbol::KSM), CurrencyId::VToken(TokenSymbol::DOT)].iter().take(n as usize) {
+			MinterReward::<T>::sample_price(*currency_id, block_num);
+		}
+	}
+
+	set_currency_weight {
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::set_currency_weight {
+			currency_id: CurrencyId::VToken(TokenSymbol::KSM),
+			weight: T::ShareWeight::from(BalanceOf::<T>::unique_saturated_from(2 as u128)),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_reward_per_block {
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::set_reward_per_block {
+			reward_per_block: BalanceOf::<T>::unique_saturated_from(100 as u128),
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	set_reward_currency {
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::set_reward_currency { currency_id: CurrencyId::Stable(TokenSymbol::KUSD) };
+	}: { call.dispatch_bypass_filter(origin)? }
+
 }
 
 impl_benchmark_test_suite!(
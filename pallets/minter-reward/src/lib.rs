@@ -20,30 +20,36 @@
 
 use core::{convert::TryFrom, marker::PhantomData};
 
+use codec::{Decode, Encode};
 use fixed::{types::extra::U0, FixedU128};
 #[cfg(feature = "std")]
 pub use frame_support::traits::GenesisBuild;
 use frame_support::{
 	pallet_prelude::{
-		Blake2_128Concat, DispatchResult, IsType, StorageDoubleMap, StorageMap, StorageValue,
-		ValueQuery, Weight,
+		Blake2_128Concat, DispatchResult, IsType, OptionQuery, StorageDoubleMap, StorageMap,
+		StorageValue, Twox64Concat, ValueQuery, Weight,
 	},
-	traits::{Get, Hooks},
+	sp_std::vec::Vec,
+	traits::{EnsureOrigin, Get, GetStorageVersion, Hooks, StorageVersion},
 	Parameter,
 };
 use frame_system::{
 	ensure_signed,
 	pallet_prelude::{BlockNumberFor, OriginFor},
 };
-use node_primitives::{CurrencyId, MinterRewardExt, TokenSymbol};
+use node_primitives::{CurrencyId, MinterRewardExt, TokenInfo};
 use orml_traits::{
 	currency::TransferAll, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
 	MultiReservableCurrency,
 };
 pub use pallet::*;
-use sp_runtime::traits::{
-	AtLeast32Bit, MaybeSerializeDeserialize, Member, SaturatedConversion, Saturating,
-	UniqueSaturatedFrom, Zero,
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{
+		AtLeast32Bit, CheckedDiv, MaybeSerializeDeserialize, Member, SaturatedConversion,
+		Saturating, UniqueSaturatedFrom, Zero,
+	},
+	FixedPointNumber, RuntimeDebug,
 };
 pub use weights::WeightInfo;
 use zenlink_protocol::{AssetId, ExportZenlink};
@@ -54,6 +60,59 @@ mod mock;
 mod tests;
 pub mod weights;
 
+/// The time weighted average price of a currency in the stable currency, accumulated from the
+/// Zenlink spot price sampled at the start of each block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct PriceAccumulator<BlockNumber> {
+	/// The spot price sampled last time, zero until the first sample.
+	pub price: Rate,
+	/// The block the spot price was sampled at last time.
+	pub updated_at: BlockNumber,
+	/// The sum of the spot prices, each weighted by the blocks it held for.
+	pub cumulative: Rate,
+	/// The cumulative price at the start of the current window.
+	pub window_cumulative: Rate,
+	/// The block the current window started at.
+	pub window_start: BlockNumber,
+	/// The average price over the last completed window, zero until a window is completed.
+	pub twap: Rate,
+}
+
+pub type Rate = sp_runtime::FixedU128;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub mod migration {
+	use super::*;
+
+	/// Tag the vtokens minted in the current round with the round index, so that their reward is
+	/// settled lazily. The rewards already issued stay in `UserReward`.
+	///
+	/// Start tracking the prices of the currencies sharing the reward.
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+			return 0;
+		}
+
+		let round = RoundIndex::<T>::get();
+		let mut reads_writes: Weight = 0;
+		Minter::<T>::translate::<BalanceOf<T>, _>(|_, _, minted| {
+			reads_writes = reads_writes.saturating_add(1);
+			Some((round, minted))
+		});
+
+		let now = frame_system::Pallet::<T>::block_number();
+		for currency_id in CurrencyWeights::<T>::iter_keys() {
+			reads_writes = reads_writes.saturating_add(1);
+			Pallet::<T>::track_price(currency_id, now);
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads_writes.saturating_add(1), reads_writes)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -94,6 +153,17 @@ pub mod pallet {
 		#[pallet::constant]
 		type StableCurrencyId: Get<CurrencyId>;
 
+		/// The blocks over which the price of the minted vtokens is averaged.
+		#[pallet::constant]
+		type TwapWindow: Get<BlockNumberFor<Self>>;
+
+		/// The native currency of the chain, which the reward is paid in by default.
+		#[pallet::constant]
+		type NativeCurrencyId: Get<CurrencyId>;
+
+		/// The origin which may change the reward parameters.
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Get price from swap module to compare maximum vtoken minted
 		type DexOperator: ExportZenlink<Self::AccountId>;
 
@@ -115,6 +185,17 @@ pub mod pallet {
 	#[pallet::getter(fn reward_per_block)]
 	pub(crate) type RewardPerBlock<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	#[pallet::type_value]
+	pub fn DefaultRewardCurrency<T: Config>() -> CurrencyIdOf<T> {
+		T::NativeCurrencyId::get()
+	}
+
+	/// The currency the reward is paid in.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_currency)]
+	pub(crate) type RewardCurrency<T: Config> =
+		StorageValue<_, CurrencyIdOf<T>, ValueQuery, DefaultRewardCurrency<T>>;
+
 	/// Ideally, BNC reward will be issued after each 50 blocks.
 	#[pallet::storage]
 	#[pallet::getter(fn current_round_start_at)]
@@ -131,7 +212,7 @@ pub mod pallet {
 	#[pallet::getter(fn total_vtoken_minted)]
 	pub type TotalVtokenMinted<T: Config> =
 		StorageMap<_, Blake2_128Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
-	/// Who mints vtoken
+	/// Who mints vtoken: (round index, amount minted in the round)
 	#[pallet::storage]
 	#[pallet::getter(fn minter)]
 	pub(crate) type Minter<T: Config> = StorageDoubleMap<
@@ -140,10 +221,32 @@ pub mod pallet {
 		T::AccountId,
 		Blake2_128Concat,
 		CurrencyIdOf<T>,
-		BalanceOf<T>,
+		(u32, BalanceOf<T>),
 		ValueQuery,
 	>;
 
+	/// The prices of the vtokens minted, in the stable currency.
+	#[pallet::storage]
+	#[pallet::getter(fn price_accumulator)]
+	pub(crate) type PriceAccumulators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CurrencyIdOf<T>,
+		PriceAccumulator<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The index of the current round.
+	#[pallet::storage]
+	#[pallet::getter(fn round_index)]
+	pub(crate) type RoundIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The reward for 1 unit of the currency minted in a settled round.
+	#[pallet::storage]
+	#[pallet::getter(fn round_reward_per_share)]
+	pub(crate) type RoundRewardPerShare<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, CurrencyIdOf<T>, Rate, ValueQuery>;
+
 	/// Record maximum vtoken value is minted and when minted
 	#[pallet::storage]
 	#[pallet::getter(fn maximum_vtoken_minted)]
@@ -166,9 +269,24 @@ pub mod pallet {
 	pub(crate) type CurrentCycle<T: Config> = StorageValue<_, u8, ValueQuery>;
 
 	#[pallet::event]
-	pub enum Event<T: Config> {}
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// [currency_id, weight]
+		CurrencyWeightSet(CurrencyIdOf<T>, T::ShareWeight),
+		/// [reward_per_block]
+		RewardPerBlockSet(BalanceOf<T>),
+		/// [currency_id]
+		RewardCurrencySet(CurrencyIdOf<T>),
+		/// A round starts with the first mint. [started_at]
+		RoundStarted(BlockNumberFor<T>),
+		/// The reward of a round is issued to the minters. [started_at, total_reward]
+		RoundSettled(BlockNumberFor<T>, BalanceOf<T>),
+		/// [claimer, currency_id, amount]
+		RewardClaimed(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::error]
@@ -196,6 +314,13 @@ pub mod pallet {
 				});
 			}
 
+			// Sample the prices before any swap in the block.
+			let currency_ids: Vec<CurrencyIdOf<T>> = PriceAccumulators::<T>::iter_keys().collect();
+			let sampled = currency_ids.len() as u32;
+			for currency_id in currency_ids {
+				Self::sample_price(currency_id, n);
+			}
+
 			let started_block_num = CurrentRoundStartAt::<T>::get();
 			let max_extended_period = T::MaximumExtendedPeriod::get();
 			let (last_max_minted_block, _current_max_minted, _last_currency_id) =
@@ -217,11 +342,15 @@ pub mod pallet {
 				// after issued reward, need to clean this round data
 				let _ = MaximumVtokenMinted::<T>::kill();
 				CurrentRoundStartAt::<T>::put(BlockNumberFor::<T>::from(0u32));
-				let _ = Minter::<T>::remove_all(None);
-				let _ = TotalVtokenMinted::<T>::remove_all(None);
+
+				Self::deposit_event(Event::RoundSettled(started_block_num, total_reward));
 			}
 
-			70_943_000 as Weight
+			(70_943_000 as Weight).saturating_add(T::WeightInfo::sample_prices(sampled))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
 		}
 	}
 
@@ -247,6 +376,7 @@ pub mod pallet {
 		fn build(&self) {
 			for (currency_id, weight) in self.currency_weights.iter() {
 				CurrencyWeights::<T>::insert(currency_id, weight);
+				Pallet::<T>::track_price(*currency_id, Zero::zero());
 			}
 
 			CurrentCycle::<T>::put(self.cycle_index);
@@ -260,15 +390,86 @@ pub mod pallet {
 		pub fn claim_reward(origin: OriginFor<T>) -> DispatchResult {
 			let claimer = ensure_signed(origin)?;
 
+			// Settle the reward of the vtokens minted in the settled rounds.
+			let currency_ids: Vec<CurrencyIdOf<T>> =
+				Minter::<T>::iter_key_prefix(&claimer).collect();
+			for currency_id in currency_ids {
+				Self::settle_minter_reward(&claimer, currency_id);
+			}
+
 			// get reward amount and deposit it to the claimer's account
 			let amount = Self::user_reward(&claimer);
 			if amount > Zero::zero() {
-				T::MultiCurrency::deposit(CurrencyId::Native(TokenSymbol::ASG), &claimer, amount)?;
+				let currency_id = Self::reward_currency();
+				T::MultiCurrency::deposit(currency_id, &claimer, amount)?;
 
 				// delete the record in the storage
 				crate::UserReward::<T>::remove(&claimer);
+
+				Self::deposit_event(Event::RewardClaimed(claimer, currency_id, amount));
+			}
+
+			Ok(())
+		}
+
+		/// Set the weight of the currency in sharing the reward. A zero weight removes the
+		/// currency from the reward.
+		///
+		/// The price of the currency is tracked as long as it shares the reward.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(T::WeightInfo::set_currency_weight())]
+		pub fn set_currency_weight(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			weight: T::ShareWeight,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			if weight.is_zero() {
+				CurrencyWeights::<T>::remove(currency_id);
+				PriceAccumulators::<T>::remove(currency_id);
+			} else {
+				CurrencyWeights::<T>::insert(currency_id, weight);
+				Self::track_price(currency_id, frame_system::Pallet::<T>::block_number());
 			}
 
+			Self::deposit_event(Event::CurrencyWeightSet(currency_id, weight));
+
+			Ok(())
+		}
+
+		/// Set the reward issued to the minters per block.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(T::WeightInfo::set_reward_per_block())]
+		pub fn set_reward_per_block(
+			origin: OriginFor<T>,
+			#[pallet::compact] reward_per_block: BalanceOf<T>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			RewardPerBlock::<T>::put(reward_per_block);
+
+			Self::deposit_event(Event::RewardPerBlockSet(reward_per_block));
+
+			Ok(())
+		}
+
+		/// Set the currency the reward is paid in.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(T::WeightInfo::set_reward_currency())]
+		pub fn set_reward_currency(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			RewardCurrency::<T>::put(currency_id);
+
+			Self::deposit_event(Event::RewardCurrencySet(currency_id));
+
 			Ok(())
 		}
 	}
@@ -294,6 +495,8 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Share the reward of the round among the currencies minted, by the weights of the
+		/// currencies. The reward of each minter is settled lazily.
 		pub fn issue_bnc_reward(bnc_reward: BalanceOf<T>) {
 			let total_weight: BalanceOf<T> = {
 				let mut total: T::ShareWeight = Zero::zero();
@@ -302,42 +505,130 @@ pub mod pallet {
 				}
 				total.into()
 			};
-			for (minter, currency_id, vtoken_amount) in Minter::<T>::iter() {
+
+			let round = RoundIndex::<T>::get();
+			for (currency_id, total_vtoken_mint) in TotalVtokenMinted::<T>::drain() {
 				let weight = CurrencyWeights::<T>::get(&currency_id);
-				let total_vtoken_mint = TotalVtokenMinted::<T>::get(currency_id); // KUSD
-				let reward = bnc_reward.saturating_mul(weight.into().saturating_mul(vtoken_amount)) /
-					(total_weight.saturating_mul(total_vtoken_mint));
-
-				// Record all BNC rewards the user receives.
-				if UserReward::<T>::contains_key(&minter) {
-					UserReward::<T>::mutate(&minter, |balance| {
-						*balance = balance.saturating_add(reward);
-					})
-				} else {
-					UserReward::<T>::insert(&minter, reward);
+				let currency_reward: u128 =
+					bnc_reward.saturating_mul(weight.into()).saturated_into::<u128>();
+				let total_shares: u128 =
+					total_weight.saturating_mul(total_vtoken_mint).saturated_into::<u128>();
+
+				if let Some(reward_per_share) =
+					Rate::checked_from_rational(currency_reward, total_shares)
+				{
+					RoundRewardPerShare::<T>::insert(round, currency_id, reward_per_share);
 				}
 			}
+
+			RoundIndex::<T>::put(round.saturating_add(1));
+		}
+
+		/// Add the reward of the vtokens minted in a settled round to `UserReward`.
+		pub(crate) fn settle_minter_reward(minter: &T::AccountId, currency_id: CurrencyIdOf<T>) {
+			let (round, minted) = Minter::<T>::get(minter, currency_id);
+			if round >= RoundIndex::<T>::get() {
+				return;
+			}
+
+			let reward_per_share = RoundRewardPerShare::<T>::get(round, currency_id);
+			let reward = BalanceOf::<T>::unique_saturated_from(
+				reward_per_share.saturating_mul_int(minted.saturated_into::<u128>()),
+			);
+			if !reward.is_zero() {
+				UserReward::<T>::mutate(minter, |balance| {
+					*balance = balance.saturating_add(reward);
+				});
+			}
+
+			Minter::<T>::remove(minter, currency_id);
 		}
 
+		/// Value the vtokens in the stable currency by the time weighted average price of the
+		/// prices sampled from Zenlink. Until the first window of the price is completed, or if the
+		/// price isn't tracked, they're valued at zero.
 		pub fn get_ausd_amount_by_zenlink(
 			vtoken_amount: BalanceOf<T>,
 			currency_id: CurrencyId,
-		) -> Result<BalanceOf<T>, Error<T>> {
-			let currency_asset_id =
-				AssetId::try_from(currency_id).map_err(|_| Error::<T>::ConversionError)?;
-			let stable_asset_id = AssetId::try_from(T::StableCurrencyId::get())
-				.map_err(|_| Error::<T>::ConversionError)?;
-
-			let ausd_amount = T::DexOperator::get_amount_out_by_path(
-				vtoken_amount.saturated_into(),
-				&[currency_asset_id, stable_asset_id],
-			)
-			.map_err(|_| Error::<T>::FailToGetSwapPrice)?
-			.last()
-			.copied()
-			.ok_or(Error::<T>::FailToGetSwapPrice)?;
-
-			Ok(BalanceOf::<T>::unique_saturated_from(ausd_amount))
+		) -> BalanceOf<T> {
+			Self::price_accumulator(currency_id).map_or(Zero::zero(), |accumulator| {
+				BalanceOf::<T>::unique_saturated_from(
+					accumulator.twap.saturating_mul_int(vtoken_amount.saturated_into::<u128>()),
+				)
+			})
+		}
+
+		/// The price of 1 unit of the currency in the stable currency, by swapping on Zenlink.
+		pub(crate) fn spot_price(currency_id: CurrencyId) -> Option<Rate> {
+			let path = [
+				AssetId::try_from(currency_id).ok()?,
+				AssetId::try_from(T::StableCurrencyId::get()).ok()?,
+			];
+
+			let unit = 10u128.checked_pow(currency_id.decimals() as u32)?;
+			let amount_out =
+				T::DexOperator::get_amount_out_by_path(unit, &path).ok()?.last().copied()?;
+
+			Rate::checked_from_rational(amount_out, unit)
+		}
+
+		/// Start tracking the price of the currency, from the next block, unless it's tracked.
+		pub(crate) fn track_price(currency_id: CurrencyId, block_num: BlockNumberFor<T>) {
+			if !PriceAccumulators::<T>::contains_key(currency_id) {
+				PriceAccumulators::<T>::insert(
+					currency_id,
+					PriceAccumulator {
+						updated_at: block_num,
+						window_start: block_num,
+						..Default::default()
+					},
+				);
+			}
+		}
+
+		/// Accumulate the price held since the last sample, and sample the spot price.
+		pub(crate) fn sample_price(currency_id: CurrencyId, n: BlockNumberFor<T>) {
+			let spot_price = Self::spot_price(currency_id);
+
+			PriceAccumulators::<T>::mutate_exists(currency_id, |maybe_accumulator| {
+				let accumulator = match maybe_accumulator {
+					Some(accumulator) => accumulator,
+					None => return,
+				};
+
+				if accumulator.price.is_zero() {
+					// The window starts with the first sample.
+					if let Some(spot_price) = spot_price {
+						accumulator.price = spot_price;
+						accumulator.updated_at = n;
+						accumulator.window_start = n;
+					}
+					return;
+				}
+
+				let elapsed: u128 = n.saturating_sub(accumulator.updated_at).saturated_into();
+				accumulator.cumulative = accumulator.cumulative.saturating_add(
+					accumulator.price.saturating_mul(Rate::saturating_from_integer(elapsed)),
+				);
+				accumulator.updated_at = n;
+				if let Some(spot_price) = spot_price {
+					accumulator.price = spot_price;
+				}
+
+				let window = n.saturating_sub(accumulator.window_start);
+				if !window.is_zero() && window >= T::TwapWindow::get() {
+					let window: u128 = window.saturated_into();
+					if let Some(twap) = accumulator
+						.cumulative
+						.saturating_sub(accumulator.window_cumulative)
+						.checked_div(&Rate::saturating_from_integer(window))
+					{
+						accumulator.twap = twap;
+					}
+					accumulator.window_cumulative = accumulator.cumulative;
+					accumulator.window_start = n;
+				}
+			});
 		}
 	}
 }
@@ -353,31 +644,28 @@ impl<T: Config> MinterRewardExt<T::AccountId, BalanceOf<T>, CurrencyIdOf<T>, Blo
 		minted_vtoken: BalanceOf<T>,
 		block_num: BlockNumberFor<T>,
 	) -> Result<(), Self::Error> {
-		let ausd_amount = Self::get_ausd_amount_by_zenlink(minted_vtoken, vtoken_id)?;
+		let ausd_amount = Self::get_ausd_amount_by_zenlink(minted_vtoken, vtoken_id);
 
 		// Update minter mint how much vtoken
-		if TotalVtokenMinted::<T>::contains_key(vtoken_id) {
-			TotalVtokenMinted::<T>::mutate(vtoken_id, |total| {
-				total.saturating_add(ausd_amount.saturated_into());
-			});
-		} else {
-			TotalVtokenMinted::<T>::insert(vtoken_id, ausd_amount);
-		}
+		TotalVtokenMinted::<T>::mutate(vtoken_id, |total| {
+			*total = total.saturating_add(ausd_amount);
+		});
 
 		// check it is a new round
 		if CurrentRoundStartAt::<T>::get() == Zero::zero() {
 			CurrentRoundStartAt::<T>::put(block_num);
-		}
 
-		// Update minter mint how much vtoken
-		if Minter::<T>::contains_key(minter, &vtoken_id) {
-			Minter::<T>::mutate(minter, &vtoken_id, |minted| {
-				minted.saturating_add(ausd_amount);
-			});
-		} else {
-			Minter::<T>::insert(minter, &vtoken_id, ausd_amount);
+			Self::deposit_event(Event::RoundStarted(block_num));
 		}
 
+		// Update minter mint how much vtoken, after settling the previous rounds.
+		Self::settle_minter_reward(minter, vtoken_id);
+		let round = RoundIndex::<T>::get();
+		Minter::<T>::mutate(minter, &vtoken_id, |(minted_round, minted)| {
+			*minted_round = round;
+			*minted = minted.saturating_add(ausd_amount);
+		});
+
 		Self::compare_max_vtoken_minted(vtoken_id, ausd_amount, block_num)
 	}
 }
@@ -31,14 +31,17 @@ use frame_support::{
 	traits::{GenesisBuild, Nothing},
 	PalletId,
 };
-use node_primitives::{CurrencyId, TokenSymbol};
-use orml_traits::MultiCurrency;
+use frame_system::EnsureRoot;
+use node_primitives::{CurrencyId, MessageId, TokenSymbol};
+use orml_traits::{MultiCurrency, XcmTransfer};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup, UniqueSaturatedInto},
-	AccountId32, DispatchError, DispatchResult, SaturatedConversion,
+	AccountId32, DispatchError, DispatchResult, Permill, SaturatedConversion,
 };
+use xcm::{latest::prelude::*, DoubleEncoded};
+use xcm_support::{BifrostXcmExecutor, Weight};
 use zenlink_protocol::{AssetBalance, AssetId, LocalAssetHandler, ZenlinkMultiAssets};
 
 use crate::{self as bifrost_minter_reward};
@@ -70,6 +73,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Storage, Event<T>},
 		Assets: orml_tokens::{Pallet, Call, Storage, Event<T>, Config<T>},
+		OrmlNFT: orml_nft::{Pallet, Storage},
 		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		MinterReward: bifrost_minter_reward::{Pallet, Call, Storage, Event<T>, Config<T>},
 		ZenlinkProtocol: zenlink_protocol::{Pallet, Call, Storage, Event<T>},
@@ -155,10 +159,112 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl orml_nft::Config for Runtime {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ();
+	type TokenData = ();
+	type MaxClassMetadata = ();
+	type MaxTokenMetadata = ();
+}
+
+parameter_types! {
+	pub const StakingPalletId: PalletId = PalletId(*b"staking ");
+	pub const EraLength: BlockNumber = 10;
+	pub const MaxRateHistory: u32 = 3;
+	pub const BlocksPerYear: BlockNumber = 100;
+	pub const CancelRedeemFee: Permill = Permill::from_percent(10);
+	pub const InstantRedeemFee: Permill = Permill::from_percent(10);
+	pub const MaxRedeemUnlocksPerBlock: u32 = 50;
+	pub const RelayCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
+	pub const RelayNetwork: NetworkId = NetworkId::Kusama;
+	pub const StakingDerivativeIndex: u16 = 1;
+	pub StakingSubAccountLocation: MultiLocation = MultiLocation::new(
+		1,
+		X1(Junction::AccountId32 { network: NetworkId::Any, id: [9u8; 32] }),
+	);
+	pub const BaseXcmWeight: u64 = 1_000_000_000;
+	pub const StakingCallWeight: u64 = 1_000_000_000;
+	pub const MaxStakingRewardsPerEra: Permill = Permill::from_percent(10);
+}
+
+pub struct MockXTokens;
+
+impl XcmTransfer<AccountId, Balance, CurrencyId> for MockXTokens {
+	fn transfer(
+		who: AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		_dest: MultiLocation,
+		_dest_weight: Weight,
+	) -> DispatchResult {
+		Currencies::withdraw(currency_id, &who, amount)
+	}
+
+	fn transfer_multi_asset(
+		_who: AccountId,
+		_asset: MultiAsset,
+		_dest: MultiLocation,
+		_dest_weight: Weight,
+	) -> DispatchResult {
+		Ok(())
+	}
+}
+
+pub struct MockXcmExecutor;
+
+impl BifrostXcmExecutor for MockXcmExecutor {
+	fn transact_weight(_: u64, _: u32) -> u64 {
+		0
+	}
+
+	fn transact_id(_data: &[u8]) -> MessageId {
+		[0; 32]
+	}
+
+	fn ump_transact(
+		_origin: MultiLocation,
+		_call: DoubleEncoded<()>,
+		_weight: u64,
+		_relay: bool,
+		_nonce: u32,
+	) -> Result<MessageId, XcmError> {
+		Ok([0; 32])
+	}
+
+	fn ump_transfer_asset(
+		_origin: MultiLocation,
+		_dest: MultiLocation,
+		_amount: u128,
+		_relay: bool,
+		_nonce: u32,
+	) -> Result<MessageId, XcmError> {
+		Ok([0; 32])
+	}
+}
+
 impl bifrost_vtoken_mint::Config for Runtime {
 	type Event = Event;
 	type MinterReward = MinterReward;
 	type MultiCurrency = Currencies;
+	type PalletId = StakingPalletId;
+	type EraLength = EraLength;
+	type ControlOrigin = EnsureRoot<AccountId>;
+	type RelayChainToken = RelayCurrencyId;
+	type RelayNetwork = RelayNetwork;
+	type BifrostXcmExecutor = MockXcmExecutor;
+	type XcmTransfer = MockXTokens;
+	type StakingDerivativeIndex = StakingDerivativeIndex;
+	type StakingSubAccountLocation = StakingSubAccountLocation;
+	type BaseXcmWeight = BaseXcmWeight;
+	type StakingCallWeight = StakingCallWeight;
+	type SelfParaId = SelfParaId;
+	type MaxStakingRewardsPerEra = MaxStakingRewardsPerEra;
+	type MaxRateHistory = MaxRateHistory;
+	type BlocksPerYear = BlocksPerYear;
+	type CancelRedeemFee = CancelRedeemFee;
+	type InstantRedeemFee = InstantRedeemFee;
+	type MaxRedeemUnlocksPerBlock = MaxRedeemUnlocksPerBlock;
 	type WeightInfo = ();
 }
 
@@ -249,6 +355,7 @@ parameter_types! {
 	pub const HalvingCycle: u32 = 60;
 	pub const RewardWindow: u32 = 10;
 	pub const MaximumExtendedPeriod: u32 = 20;
+	pub const TwapWindow: u32 = 20;
 }
 
 impl crate::Config for Runtime {
@@ -260,6 +367,9 @@ impl crate::Config for Runtime {
 	type RewardWindow = RewardWindow;
 	type ShareWeight = Balance;
 	type StableCurrencyId = StableCurrencyId;
+	type TwapWindow = TwapWindow;
+	type NativeCurrencyId = NativeCurrencyId;
+	type ControlOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
 }
 
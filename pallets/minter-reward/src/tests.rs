@@ -23,7 +23,8 @@
 
 use std::convert::TryFrom;
 
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
 
 use crate::{mock::*, *};
 
@@ -40,20 +41,157 @@ pub(crate) fn run_to_block(n: u64) {
 #[test]
 fn claim_reward_should_work() {
 	ExtBuilder::default().ten_thousand_for_alice_n_bob().build().execute_with(|| {
+		System::set_block_number(1);
+
 		crate::UserReward::<Runtime>::insert(&ALICE, 1000);
 		assert_eq!(MinterReward::user_reward(&ALICE), 1000);
+		// The reward is paid in the native token by default.
+		assert_eq!(MinterReward::reward_currency(), NativeCurrencyId::get());
 		// Alice original has 100000 native token.
-		assert_eq!(Currencies::free_balance(CurrencyId::Native(TokenSymbol::ASG), &ALICE), 100000);
+		assert_eq!(Currencies::free_balance(NativeCurrencyId::get(), &ALICE), 100000);
 
 		assert_ok!(MinterReward::claim_reward(Origin::signed(ALICE)));
+		System::assert_last_event(mock::Event::MinterReward(crate::Event::RewardClaimed(
+			ALICE,
+			NativeCurrencyId::get(),
+			1000,
+		)));
 		assert_eq!(MinterReward::user_reward(&ALICE), 0);
-		assert_eq!(
-			Currencies::free_balance(CurrencyId::Native(TokenSymbol::ASG), &ALICE),
-			100000 + 1000
-		)
+		assert_eq!(Currencies::free_balance(NativeCurrencyId::get(), &ALICE), 100000 + 1000)
+	});
+}
+
+#[test]
+fn governance_should_set_reward_parameters() {
+	ExtBuilder::default().ten_thousand_for_alice_n_bob().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			MinterReward::set_reward_per_block(Origin::signed(ALICE), 100),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(MinterReward::set_reward_per_block(Origin::root(), 100));
+		System::assert_last_event(mock::Event::MinterReward(crate::Event::RewardPerBlockSet(100)));
+		assert_eq!(MinterReward::reward_per_block(), 100);
+
+		assert_ok!(MinterReward::set_currency_weight(Origin::root(), vKSM, 2));
+		System::assert_last_event(mock::Event::MinterReward(crate::Event::CurrencyWeightSet(
+			vKSM, 2,
+		)));
+		assert_eq!(MinterReward::currency_weight(vKSM), 2);
+		assert_ok!(MinterReward::set_currency_weight(Origin::root(), vKSM, 0));
+		assert!(!CurrencyWeights::<Runtime>::contains_key(vKSM));
+
+		// The reward is paid in the new currency.
+		assert_ok!(MinterReward::set_reward_currency(Origin::root(), KUSD));
+		System::assert_last_event(mock::Event::MinterReward(crate::Event::RewardCurrencySet(KUSD)));
+		crate::UserReward::<Runtime>::insert(&BOB, 1000);
+		assert_ok!(MinterReward::claim_reward(Origin::signed(BOB)));
+		assert_eq!(Currencies::free_balance(KUSD, &BOB), 10000 + 1000);
+	});
+}
+
+#[test]
+fn round_reward_should_be_settled_lazily() {
+	ExtBuilder::default().ten_thousand_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(MinterReward::set_currency_weight(Origin::root(), vDOT, 1));
+		TotalVtokenMinted::<Runtime>::insert(vDOT, 100);
+		Minter::<Runtime>::insert(ALICE, vDOT, (0, 60));
+		Minter::<Runtime>::insert(BOB, vDOT, (0, 40));
+
+		// vDOT takes 1/6 of the weights.
+		MinterReward::issue_bnc_reward(600);
+		assert_eq!(MinterReward::round_index(), 1);
+		assert_eq!(MinterReward::round_reward_per_share(0, vDOT), Rate::one());
+		assert!(!TotalVtokenMinted::<Runtime>::contains_key(vDOT));
+		assert_eq!(MinterReward::user_reward(&ALICE), 0);
+
+		let alice_native = Currencies::free_balance(NativeCurrencyId::get(), &ALICE);
+		assert_ok!(MinterReward::claim_reward(Origin::signed(ALICE)));
+		assert_eq!(Currencies::free_balance(NativeCurrencyId::get(), &ALICE), alice_native + 60);
+		assert!(!Minter::<Runtime>::contains_key(ALICE, vDOT));
+
+		// Bob's reward is kept until he claims.
+		assert_eq!(MinterReward::minter(BOB, vDOT), (0, 40));
+		MinterReward::settle_minter_reward(&BOB, vDOT);
+		assert_eq!(MinterReward::user_reward(&BOB), 40);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_tag_minted_with_round() {
+	ExtBuilder::default().ten_thousand_for_alice_n_bob().build().execute_with(|| {
+		StorageVersion::new(0).put::<MinterReward>();
+		RoundIndex::<Runtime>::put(2);
+		frame_support::storage::unhashed::put(
+			&Minter::<Runtime>::hashed_key_for(ALICE, vDOT),
+			&50u64,
+		);
+		PriceAccumulators::<Runtime>::remove(DOT);
+
+		migration::migrate_to_v1::<Runtime>();
+		assert_eq!(MinterReward::minter(ALICE, vDOT), (2, 50));
+		assert!(MinterReward::price_accumulator(DOT).is_some());
+		assert_eq!(StorageVersion::get::<MinterReward>(), STORAGE_VERSION);
 	});
 }
 
+#[test]
+fn mints_should_be_valued_by_twap() {
+	ExtBuilder::default()
+		.balances(vec![
+			(ALICE, BNC, 100000),
+			(ALICE, KUSD, 10u64.pow(13)),
+			(ALICE, vDOT, 10u64.pow(13)),
+		])
+		.build()
+		.execute_with(|| {
+			let ausd_asset_id: AssetId = AssetId::try_from(KUSD).unwrap();
+			let vdot_asset_id: AssetId = AssetId::try_from(vDOT).unwrap();
+			assert_ok!(ZenlinkProtocol::create_pair(Origin::root(), ausd_asset_id, vdot_asset_id));
+			assert_ok!(ZenlinkProtocol::add_liquidity(
+				Origin::signed(ALICE),
+				ausd_asset_id,
+				vdot_asset_id,
+				1_000_000_000_000,
+				1_000_000_000_000,
+				1,
+				1,
+				100
+			));
+
+			// The price isn't tracked until vDOT shares the reward.
+			run_to_block(1);
+			assert_eq!(MinterReward::price_accumulator(vDOT), None);
+			assert_ok!(MinterReward::set_currency_weight(Origin::root(), vDOT, 1));
+
+			// The mints are valued at zero until the first window is completed.
+			let minted = 10u64.pow(11);
+			assert_ok!(MinterReward::reward_minted_vtoken(&ALICE, vDOT, minted, 1));
+			assert_eq!(MinterReward::total_vtoken_minted(vDOT), 0);
+			run_to_block(21);
+			assert_eq!(MinterReward::price_accumulator(vDOT).unwrap().twap, Rate::zero());
+
+			// The price is averaged over the window of 20 blocks, and the round is settled.
+			run_to_block(22);
+			assert!(!TotalVtokenMinted::<Runtime>::contains_key(vDOT));
+			let spot_price = MinterReward::spot_price(vDOT).unwrap();
+			let accumulator = MinterReward::price_accumulator(vDOT).unwrap();
+			assert_eq!(accumulator.window_start, 22);
+			assert_eq!(accumulator.twap, spot_price);
+
+			// The mint is valued by the average price, not moved by the slippage of the swap.
+			assert_ok!(MinterReward::reward_minted_vtoken(&ALICE, vDOT, minted, 22));
+			let valued = spot_price.saturating_mul_int(minted as u128) as u64;
+			assert_eq!(MinterReward::total_vtoken_minted(vDOT), valued);
+
+			// The price is no longer tracked once vDOT doesn't share the reward.
+			assert_ok!(MinterReward::set_currency_weight(Origin::root(), vDOT, 0));
+			assert_eq!(MinterReward::price_accumulator(vDOT), None);
+		});
+}
+
 #[test]
 fn minter_reward_should_work() {
 	ExtBuilder::default().ten_thousand_for_alice_n_bob().build().execute_with(|| {
@@ -129,6 +267,12 @@ fn minter_reward_should_work() {
 			28
 		));
 
+		// vDOT is valued at 1 KUSD, as if a window of the price is completed.
+		PriceAccumulators::<Runtime>::insert(
+			vDOT,
+			PriceAccumulator { twap: Rate::one(), ..Default::default() },
+		);
+
 		// add some data to the vtoken mint pools
 		assert_ok!(VtokenMint::set_vtoken_pool(Origin::root(), DOT, 10000, 10000));
 		assert_ok!(VtokenMint::mint(Origin::signed(ALICE), vDOT, to_sell_dot));
@@ -140,22 +284,22 @@ fn minter_reward_should_work() {
 		assert_eq!(MinterReward::reward_per_block(), 300);
 		assert_eq!(
 			MinterReward::maximum_vtoken_minted(),
-			(2, 19, CurrencyId::VToken(TokenSymbol::DOT))
+			(2, 20, CurrencyId::VToken(TokenSymbol::DOT))
 		);
 
 		run_to_block(12);
 		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vDOT, to_sell_vdot + 40));
 		assert_eq!(
 			MinterReward::maximum_vtoken_minted(),
-			(12, 56, CurrencyId::VToken(TokenSymbol::DOT))
+			(12, 60, CurrencyId::VToken(TokenSymbol::DOT))
 		);
 
 		// start block is 2, max extended block is 20, block 21 should still be the last block of
-		// maximum_vtoken(12, 56, CurrencyId::VToken(TokenSymbol::DOT))
+		// maximum_vtoken(12, 60, CurrencyId::VToken(TokenSymbol::DOT))
 		run_to_block(21);
 		assert_eq!(
 			MinterReward::maximum_vtoken_minted(),
-			(12, 56, CurrencyId::VToken(TokenSymbol::DOT))
+			(12, 60, CurrencyId::VToken(TokenSymbol::DOT))
 		);
 
 		// 23-2=21 >20, so the previous round will be ended and new round will be started.
@@ -28,9 +28,14 @@ use frame_support::{
 pub trait WeightInfo {
 	fn claim_reward() -> Weight;
 	fn on_initialize() -> Weight;
+	fn set_currency_weight() -> Weight;
+	fn set_reward_per_block() -> Weight;
+	fn set_reward_currency() -> Weight;
+	fn sample_prices(n: u32) -> Weight;
 }
 
-// For backwards compatibility and tests
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl WeightInfo for () {
 	fn claim_reward() -> Weight {
 		(50_000_000 as Weight)
@@ -39,4 +44,27 @@ impl WeightInfo for () {
 	fn on_initialize() -> Weight {
 		(50_000_000 as Weight)
 	}
+
+	fn set_currency_weight() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_reward_per_block() -> Weight {
+		(50_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_reward_currency() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn sample_prices(n: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((50_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
 }
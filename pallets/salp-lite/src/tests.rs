@@ -787,10 +787,18 @@ fn release_from_redeem_to_bancor_should_work() {
 		assert_ok!(Salp::fund_success(Some(ALICE).into(), 3_000));
 		assert_ok!(Salp::fund_retire(Some(ALICE).into(), 3_000));
 		assert_ok!(Salp::withdraw(Some(ALICE).into(), 3_000));
+		assert_eq!(Salp::redeem_pool(), 100);
+		assert_eq!(Bancor::get_bancor_reserve(RelayCurrencyId::get()), Some(0));
 
 		run_to_block(ReleaseCycle::get());
 
-		// TODO: Check the balance of bancor(Waiting Bancor to Support..)
+		assert_eq!(Salp::redeem_pool(), 50);
+		assert_eq!(Bancor::get_bancor_reserve(RelayCurrencyId::get()), Some(50));
+
+		run_to_block(2 * ReleaseCycle::get());
+
+		assert_eq!(Salp::redeem_pool(), 25);
+		assert_eq!(Bancor::get_bancor_reserve(RelayCurrencyId::get()), Some(75));
 	});
 }
 
@@ -58,6 +58,7 @@ construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
 		Tokens: orml_tokens::{Pallet, Call, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Bancor: bifrost_bancor::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
 		Salp: salp::{Pallet, Call, Storage, Event<T>},
@@ -172,6 +173,15 @@ impl orml_currencies::Config for Test {
 parameter_types! {
 	pub const InterventionPercentage: Percent = Percent::from_percent(75);
 	pub const DailyReleasePercentage: Percent = Percent::from_percent(5);
+	pub const ReleaseEpoch: u64 = 60 * 60;
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+	type MinimumPeriod = MinimumPeriod;
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type WeightInfo = ();
 }
 
 impl bifrost_bancor::Config for Test {
@@ -179,6 +189,10 @@ impl bifrost_bancor::Config for Test {
 	type InterventionPercentage = InterventionPercentage;
 	type DailyReleasePercentage = DailyReleasePercentage;
 	type MultiCurrency = Tokens;
+	type ControlOrigin = frame_system::EnsureRoot<AccountId>;
+	type ReleaseEpoch = ReleaseEpoch;
+	type UnixTime = Timestamp;
+	type MarketPrice = ();
 	type WeightInfo = ();
 }
 
@@ -385,6 +399,10 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 	.assimilate_storage(&mut t)
 	.unwrap();
 
+	bifrost_bancor::GenesisConfig::<Test> { bancor_pools: vec![(RelayCurrencyId::get(), 10_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 	t.into()
 }
 
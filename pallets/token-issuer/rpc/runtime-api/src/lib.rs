@@ -0,0 +1,36 @@
+// This file is part of Bifrost.
+
+// Copyright (C) 2019-2021 Liebi Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_api::decl_runtime_apis;
+use sp_std::vec::Vec;
+
+decl_runtime_apis! {
+	pub trait TokenIssuerRuntimeApi<CurrencyId, AccountId> where
+		CurrencyId: Codec,
+		AccountId: Codec
+	{
+		/// get the accounts which can issue the currency
+		fn get_issue_whitelist(currency_id: CurrencyId) -> Vec<AccountId>;
+
+		/// get the accounts which can transfer the currency
+		fn get_transfer_whitelist(currency_id: CurrencyId) -> Vec<AccountId>;
+	}
+}
@@ -95,6 +95,126 @@ benchmarks! {
 		assert_eq!(T::MultiCurrency::free_balance(currency_id.clone(), &caller), caller_original_balance - transfer_token_amount);
 		assert_eq!(T::MultiCurrency::free_balance(currency_id.clone(), &receiver), transfer_token_amount+ receiver_original_balance);
 	}
+
+	propose_issue {
+		let origin = T::ControlOrigin::successful_origin();
+		let caller: T::AccountId = whitelisted_caller();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let add_call = Call::<T>::add_to_issue_whitelist { currency_id, account: caller.clone() };
+		add_call.dispatch_bypass_filter(origin)?;
+
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u32 as u128);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), currency_id, token_amount)
+
+	approve_issue {
+		let origin = T::ControlOrigin::successful_origin();
+		let caller: T::AccountId = whitelisted_caller();
+		let proposer: T::AccountId = account("bechmarking_account_1", 0, 0);
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		for issuer in [caller.clone(), proposer.clone()] {
+			let add_call = Call::<T>::add_to_issue_whitelist { currency_id, account: issuer };
+			add_call.dispatch_bypass_filter(origin.clone())?;
+		}
+
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u32 as u128);
+		TokenIssuer::<T>::propose_issue(RawOrigin::Signed(proposer.clone()).into(), proposer.clone(), currency_id, token_amount)?;
+		let original_balance = T::MultiCurrency::free_balance(currency_id, &proposer);
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(currency_id, &proposer), token_amount + original_balance);
+	}
+
+	cancel_issue_proposal {
+		let origin = T::ControlOrigin::successful_origin();
+		let caller: T::AccountId = whitelisted_caller();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let add_call = Call::<T>::add_to_issue_whitelist { currency_id, account: caller.clone() };
+		add_call.dispatch_bypass_filter(origin)?;
+
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u32 as u128);
+		TokenIssuer::<T>::propose_issue(RawOrigin::Signed(caller.clone()).into(), caller.clone(), currency_id, token_amount)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(TokenIssuer::<T>::issue_proposal(0).is_none());
+	}
+
+	burn {
+		let origin = T::ControlOrigin::successful_origin();
+		let caller: T::AccountId = whitelisted_caller();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let add_call = Call::<T>::add_to_issue_whitelist { currency_id, account: caller.clone() };
+		add_call.dispatch_bypass_filter(origin)?;
+
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u32 as u128);
+		TokenIssuer::<T>::issue(RawOrigin::Signed(caller.clone()).into(), caller.clone(), currency_id, token_amount)?;
+		let original_balance = T::MultiCurrency::free_balance(currency_id, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), currency_id, token_amount)
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(currency_id, &caller), original_balance - token_amount);
+	}
+
+	set_issuer_cap {
+		let origin = T::ControlOrigin::successful_origin();
+		let account: T::AccountId = whitelisted_caller();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let cap = Some(BalanceOf::<T>::unique_saturated_from(1000u32 as u128));
+		let call = Call::<T>::set_issuer_cap { account, currency_id, cap };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	set_supply_cap {
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let cap = Some(BalanceOf::<T>::unique_saturated_from(1000u32 as u128));
+		let call = Call::<T>::set_supply_cap { currency_id, cap };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	set_two_step_issue {
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let call = Call::<T>::set_two_step_issue { currency_id, required: true };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	batch_add_to_issue_whitelist {
+		let n in 1 .. 100;
+
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let accounts: Vec<T::AccountId> = (0 .. n).map(|i| account("whitelist_account", i, 0)).collect();
+		let call = Call::<T>::batch_add_to_issue_whitelist { currency_id, accounts };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	batch_remove_from_issue_whitelist {
+		let n in 1 .. 100;
+
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let accounts: Vec<T::AccountId> = (0 .. n).map(|i| account("whitelist_account", i, 0)).collect();
+		let add_call = Call::<T>::batch_add_to_issue_whitelist { currency_id, accounts: accounts.clone() };
+		add_call.dispatch_bypass_filter(origin.clone())?;
+
+		let remove_call = Call::<T>::batch_remove_from_issue_whitelist { currency_id, accounts };
+	}: {remove_call.dispatch_bypass_filter(origin)?}
+
+	batch_add_to_transfer_whitelist {
+		let n in 1 .. 100;
+
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let accounts: Vec<T::AccountId> = (0 .. n).map(|i| account("whitelist_account", i, 0)).collect();
+		let call = Call::<T>::batch_add_to_transfer_whitelist { currency_id, accounts };
+	}: {call.dispatch_bypass_filter(origin)?}
+
+	batch_remove_from_transfer_whitelist {
+		let n in 1 .. 100;
+
+		let origin = T::ControlOrigin::successful_origin();
+		let currency_id = CurrencyId::Token(TokenSymbol::KSM);
+		let accounts: Vec<T::AccountId> = (0 .. n).map(|i| account("whitelist_account", i, 0)).collect();
+		let add_call = Call::<T>::batch_add_to_transfer_whitelist { currency_id, accounts: accounts.clone() };
+		add_call.dispatch_bypass_filter(origin.clone())?;
+
+		let remove_call = Call::<T>::batch_remove_from_transfer_whitelist { currency_id, accounts };
+	}: {remove_call.dispatch_bypass_filter(origin)?}
 }
 
 impl_benchmark_test_suite!(
@@ -25,10 +25,16 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
-use frame_support::{ensure, pallet_prelude::*, transactional};
+use codec::{Decode, Encode};
+use frame_support::{ensure, pallet_prelude::*, traits::StorageVersion, transactional};
 use frame_system::pallet_prelude::*;
 use node_primitives::CurrencyId;
 use orml_traits::MultiCurrency;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedDiv, Saturating, Zero},
+	RuntimeDebug,
+};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -39,11 +45,69 @@ mod weights;
 
 pub use pallet::*;
 
+pub mod migration {
+	use frame_support::{
+		storage::migration::{remove_storage_prefix, storage_key_iter},
+		traits::PalletInfoAccess,
+	};
+
+	use super::*;
+
+	/// Move the whitelists from a vector per currency to an entry per account.
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+			return 0;
+		}
+
+		let prefix = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+		let mut reads_writes: Weight = 0;
+
+		let issue_whitelists: Vec<(CurrencyId, Vec<AccountIdOf<T>>)> =
+			storage_key_iter::<_, _, Blake2_128Concat>(prefix, b"IssueWhiteList").collect();
+		remove_storage_prefix(prefix, b"IssueWhiteList", &[]);
+		for (currency_id, accounts) in issue_whitelists {
+			reads_writes = reads_writes.saturating_add(1);
+			for account in accounts {
+				reads_writes = reads_writes.saturating_add(1);
+				IssueWhiteList::<T>::insert(currency_id, account, ());
+			}
+		}
+
+		let transfer_whitelists: Vec<(CurrencyId, Vec<AccountIdOf<T>>)> =
+			storage_key_iter::<_, _, Blake2_128Concat>(prefix, b"TransferWhiteList").collect();
+		remove_storage_prefix(prefix, b"TransferWhiteList", &[]);
+		for (currency_id, accounts) in transfer_whitelists {
+			reads_writes = reads_writes.saturating_add(1);
+			for account in accounts {
+				reads_writes = reads_writes.saturating_add(1);
+				TransferWhiteList::<T>::insert(currency_id, account, ());
+			}
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads_writes.saturating_add(1), reads_writes)
+	}
+}
+
 type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<
 	<T as frame_system::Config>::AccountId,
 >>::Balance;
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 
+pub type IssueProposalId = u32;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// An issue waiting for the approval of a second account in the issue whitelist.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct IssueProposal<AccountId, Balance> {
+	pub proposer: AccountId,
+	pub dest: AccountId,
+	pub currency_id: CurrencyId,
+	pub amount: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -57,6 +121,14 @@ pub mod pallet {
 		/// The only origin that can edit token issuer list
 		type ControlOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The number of blocks the issue caps of issuers apply to
+		#[pallet::constant]
+		type IssuePeriod: Get<Self::BlockNumber>;
+
+		/// The most issue proposals an issuer can have waiting for approval
+		#[pallet::constant]
+		type MaxIssueProposalsPerIssuer: Get<u32>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -69,6 +141,18 @@ pub mod pallet {
 		NotExist,
 		/// The origin is not allowed to perform the operation.
 		NotAllowed,
+		/// The issue exceeds what the issuer can issue in the period.
+		ExceedIssuerCap,
+		/// The issue exceeds the total supply allowed for the currency.
+		ExceedSupplyCap,
+		/// The currency has to be issued through a proposal approved by another issuer.
+		ApprovalRequired,
+		/// The issue proposal doesn't exist.
+		ProposalNotExist,
+		/// The proposer can't approve its own proposal.
+		SelfApproval,
+		/// The proposer has too many issue proposals waiting for approval.
+		TooManyIssueProposals,
 	}
 
 	#[pallet::event]
@@ -82,28 +166,109 @@ pub mod pallet {
 		AddedToTransferList(T::AccountId, CurrencyId),
 		/// Successful remove an account from the transfer whitelist. \[account, currency_id]\
 		RemovedFromTransferList(T::AccountId, CurrencyId),
-		/// Token issue success, \[currency_id, dest, amount\]
-		Issued(T::AccountId, CurrencyId, BalanceOf<T>),
+		/// Token issue success, \[issuer, dest, currency_id, amount, total_issued_by_issuer\]
+		Issued(T::AccountId, T::AccountId, CurrencyId, BalanceOf<T>, BalanceOf<T>),
+		/// Token burned success, \[account, currency_id, amount\]
+		Burned(T::AccountId, CurrencyId, BalanceOf<T>),
+		/// An issue is proposed, \[proposal_id, proposer, dest, currency_id, amount\]
+		IssueProposed(IssueProposalId, T::AccountId, T::AccountId, CurrencyId, BalanceOf<T>),
+		/// An issue proposal is approved, \[proposal_id, approver\]
+		IssueApproved(IssueProposalId, T::AccountId),
+		/// An issue proposal is cancelled, \[proposal_id\]
+		IssueProposalCancelled(IssueProposalId),
+		/// The cap of an issuer per period is set, \[account, currency_id, cap\]
+		IssuerCapSet(T::AccountId, CurrencyId, Option<BalanceOf<T>>),
+		/// The cap of the total supply is set, \[currency_id, cap\]
+		SupplyCapSet(CurrencyId, Option<BalanceOf<T>>),
+		/// Whether the currency needs two-step issue is set, \[currency_id, required\]
+		TwoStepIssueSet(CurrencyId, bool),
 		/// Token transferred success, \[origin, dest, currency_id, amount\]
 		Transferred(T::AccountId, T::AccountId, CurrencyId, BalanceOf<T>),
 	}
 
 	/// Accounts in the whitelist can issue the corresponding Currency.
 	#[pallet::storage]
-	#[pallet::getter(fn get_issue_whitelist)]
-	pub type IssueWhiteList<T> = StorageMap<_, Blake2_128Concat, CurrencyId, Vec<AccountIdOf<T>>>;
+	pub type IssueWhiteList<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CurrencyId, Blake2_128Concat, AccountIdOf<T>, ()>;
 
 	/// Accounts in the whitelist can transfer the corresponding Currency.
 	#[pallet::storage]
-	#[pallet::getter(fn get_transfer_whitelist)]
-	pub type TransferWhiteList<T> =
-		StorageMap<_, Blake2_128Concat, CurrencyId, Vec<AccountIdOf<T>>>;
+	pub type TransferWhiteList<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CurrencyId, Blake2_128Concat, AccountIdOf<T>, ()>;
+
+	/// The most an issuer can issue of a currency per `IssuePeriod`. No cap if absent.
+	#[pallet::storage]
+	#[pallet::getter(fn issuer_cap)]
+	pub type IssuerCaps<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		CurrencyId,
+		BalanceOf<T>,
+	>;
+
+	/// The amount an issuer has issued of a currency in the period, as `(period, amount)`.
+	#[pallet::storage]
+	#[pallet::getter(fn issued_in_period)]
+	pub type IssuedInPeriod<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		CurrencyId,
+		(BlockNumberFor<T>, BalanceOf<T>),
+		ValueQuery,
+	>;
+
+	/// The amount an issuer has issued of a currency since ever.
+	#[pallet::storage]
+	#[pallet::getter(fn total_issued)]
+	pub type TotalIssued<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Blake2_128Concat,
+		CurrencyId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The most total issuance of a currency that can be reached by issuing. No cap if absent.
+	#[pallet::storage]
+	#[pallet::getter(fn supply_cap)]
+	pub type SupplyCaps<T: Config> = StorageMap<_, Blake2_128Concat, CurrencyId, BalanceOf<T>>;
+
+	/// Currencies which are issued through a proposal approved by a second issuer.
+	#[pallet::storage]
+	#[pallet::getter(fn two_step_issue)]
+	pub type TwoStepIssue<T> = StorageMap<_, Blake2_128Concat, CurrencyId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn issue_proposal)]
+	pub type IssueProposals<T: Config> =
+		StorageMap<_, Twox64Concat, IssueProposalId, IssueProposal<AccountIdOf<T>, BalanceOf<T>>>;
+
+	/// The number of issue proposals of an issuer waiting for approval.
+	#[pallet::storage]
+	#[pallet::getter(fn open_issue_proposals)]
+	pub type OpenIssueProposals<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_issue_proposal_id)]
+	pub type NextIssueProposalId<T> = StorageValue<_, IssueProposalId, ValueQuery>;
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -116,22 +281,13 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ControlOrigin::ensure_origin(origin)?;
 
-			let empty_vec: Vec<AccountIdOf<T>> = Vec::new();
-			if Self::get_issue_whitelist(currency_id) == None {
-				IssueWhiteList::<T>::insert(currency_id, empty_vec);
-			}
-
-			IssueWhiteList::<T>::mutate(currency_id, |issue_whitelist| -> Result<(), Error<T>> {
-				match issue_whitelist {
-					Some(issue_list) if !issue_list.contains(&account) => {
-						issue_list.push(account.clone());
-						Self::deposit_event(Event::AddedToIssueList(account, currency_id));
-						Ok(())
-					},
-					_ => Err(Error::<T>::NotAllowed),
-				}
-			})?;
+			ensure!(
+				!IssueWhiteList::<T>::contains_key(currency_id, &account),
+				Error::<T>::NotAllowed
+			);
+			IssueWhiteList::<T>::insert(currency_id, &account, ());
 
+			Self::deposit_event(Event::AddedToIssueList(account, currency_id));
 			Ok(())
 		}
 
@@ -144,17 +300,10 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ControlOrigin::ensure_origin(origin)?;
 
-			IssueWhiteList::<T>::mutate(currency_id, |issue_whitelist| -> Result<(), Error<T>> {
-				match issue_whitelist {
-					Some(issue_list) if issue_list.contains(&account) => {
-						issue_list.retain(|x| x.clone() != account);
-						Self::deposit_event(Event::RemovedFromIssueList(account, currency_id));
-						Ok(())
-					},
-					_ => Err(Error::<T>::NotExist),
-				}
-			})?;
+			ensure!(IssueWhiteList::<T>::contains_key(currency_id, &account), Error::<T>::NotExist);
+			IssueWhiteList::<T>::remove(currency_id, &account);
 
+			Self::deposit_event(Event::RemovedFromIssueList(account, currency_id));
 			Ok(())
 		}
 
@@ -167,25 +316,13 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ControlOrigin::ensure_origin(origin)?;
 
-			let empty_vec: Vec<AccountIdOf<T>> = Vec::new();
-			if Self::get_transfer_whitelist(currency_id) == None {
-				TransferWhiteList::<T>::insert(currency_id, empty_vec);
-			}
-
-			TransferWhiteList::<T>::mutate(
-				currency_id,
-				|transfer_whitelist| -> Result<(), Error<T>> {
-					match transfer_whitelist {
-						Some(transfer_list) if !transfer_list.contains(&account) => {
-							transfer_list.push(account.clone());
-							Self::deposit_event(Event::AddedToTransferList(account, currency_id));
-							Ok(())
-						},
-						_ => Err(Error::<T>::NotAllowed),
-					}
-				},
-			)?;
+			ensure!(
+				!TransferWhiteList::<T>::contains_key(currency_id, &account),
+				Error::<T>::NotAllowed
+			);
+			TransferWhiteList::<T>::insert(currency_id, &account, ());
 
+			Self::deposit_event(Event::AddedToTransferList(account, currency_id));
 			Ok(())
 		}
 
@@ -198,22 +335,90 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::ControlOrigin::ensure_origin(origin)?;
 
-			TransferWhiteList::<T>::mutate(
-				currency_id,
-				|transfer_whitelist| -> Result<(), Error<T>> {
-					match transfer_whitelist {
-						Some(transfer_list) if transfer_list.contains(&account) => {
-							transfer_list.retain(|x| x.clone() != account);
-							Self::deposit_event(Event::RemovedFromTransferList(
-								account,
-								currency_id,
-							));
-							Ok(())
-						},
-						_ => Err(Error::<T>::NotExist),
-					}
-				},
-			)?;
+			ensure!(
+				TransferWhiteList::<T>::contains_key(currency_id, &account),
+				Error::<T>::NotExist
+			);
+			TransferWhiteList::<T>::remove(currency_id, &account);
+
+			Self::deposit_event(Event::RemovedFromTransferList(account, currency_id));
+			Ok(())
+		}
+
+		/// Add accounts to the issue whitelist, the ones already in it are skipped.
+		#[pallet::weight(T::WeightInfo::batch_add_to_issue_whitelist(accounts.len() as u32))]
+		#[transactional]
+		pub fn batch_add_to_issue_whitelist(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			accounts: Vec<AccountIdOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			for account in accounts {
+				if !IssueWhiteList::<T>::contains_key(currency_id, &account) {
+					IssueWhiteList::<T>::insert(currency_id, &account, ());
+					Self::deposit_event(Event::AddedToIssueList(account, currency_id));
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Remove accounts from the issue whitelist, the ones not in it are skipped.
+		#[pallet::weight(T::WeightInfo::batch_remove_from_issue_whitelist(accounts.len() as u32))]
+		#[transactional]
+		pub fn batch_remove_from_issue_whitelist(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			accounts: Vec<AccountIdOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			for account in accounts {
+				if IssueWhiteList::<T>::take(currency_id, &account).is_some() {
+					Self::deposit_event(Event::RemovedFromIssueList(account, currency_id));
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Add accounts to the transfer whitelist, the ones already in it are skipped.
+		#[pallet::weight(T::WeightInfo::batch_add_to_transfer_whitelist(accounts.len() as u32))]
+		#[transactional]
+		pub fn batch_add_to_transfer_whitelist(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			accounts: Vec<AccountIdOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			for account in accounts {
+				if !TransferWhiteList::<T>::contains_key(currency_id, &account) {
+					TransferWhiteList::<T>::insert(currency_id, &account, ());
+					Self::deposit_event(Event::AddedToTransferList(account, currency_id));
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Remove accounts from the transfer whitelist, the ones not in it are skipped.
+		#[pallet::weight(T::WeightInfo::batch_remove_from_transfer_whitelist(accounts.len() as u32))]
+		#[transactional]
+		pub fn batch_remove_from_transfer_whitelist(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			accounts: Vec<AccountIdOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			for account in accounts {
+				if TransferWhiteList::<T>::take(currency_id, &account).is_some() {
+					Self::deposit_event(Event::RemovedFromTransferList(account, currency_id));
+				}
+			}
 
 			Ok(())
 		}
@@ -228,13 +433,168 @@ pub mod pallet {
 		) -> DispatchResult {
 			let issuer = ensure_signed(origin)?;
 
-			let issue_whitelist =
-				Self::get_issue_whitelist(currency_id).ok_or(Error::<T>::NotAllowed)?;
-			ensure!(issue_whitelist.contains(&issuer), Error::<T>::NotAllowed);
+			Self::ensure_issuer(&issuer, currency_id)?;
+			ensure!(!Self::two_step_issue(currency_id), Error::<T>::ApprovalRequired);
 
-			T::MultiCurrency::deposit(currency_id, &dest, amount)?;
+			Self::do_issue(issuer, dest, currency_id, amount)
+		}
+
+		/// Propose an issue of a currency which needs two-step issue. It is executed once another
+		/// account in the issue whitelist approves it.
+		#[pallet::weight(T::WeightInfo::propose_issue())]
+		#[transactional]
+		pub fn propose_issue(
+			origin: OriginFor<T>,
+			dest: AccountIdOf<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+
+			Self::ensure_issuer(&proposer, currency_id)?;
+			OpenIssueProposals::<T>::try_mutate(&proposer, |open| -> DispatchResult {
+				ensure!(
+					*open < T::MaxIssueProposalsPerIssuer::get(),
+					Error::<T>::TooManyIssueProposals
+				);
+				*open = open.saturating_add(1);
+				Ok(())
+			})?;
+
+			let proposal_id = Self::next_issue_proposal_id();
+			NextIssueProposalId::<T>::put(proposal_id.saturating_add(1));
+			IssueProposals::<T>::insert(
+				proposal_id,
+				IssueProposal {
+					proposer: proposer.clone(),
+					dest: dest.clone(),
+					currency_id,
+					amount,
+				},
+			);
+
+			Self::deposit_event(Event::IssueProposed(
+				proposal_id,
+				proposer,
+				dest,
+				currency_id,
+				amount,
+			));
+			Ok(())
+		}
+
+		/// Approve an issue proposal, the issue counts towards the caps of the proposer.
+		#[pallet::weight(T::WeightInfo::approve_issue())]
+		#[transactional]
+		pub fn approve_issue(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: IssueProposalId,
+		) -> DispatchResult {
+			let approver = ensure_signed(origin)?;
+
+			let proposal = Self::take_issue_proposal(proposal_id)?;
+			ensure!(proposal.proposer != approver, Error::<T>::SelfApproval);
+			Self::ensure_issuer(&approver, proposal.currency_id)?;
+			Self::ensure_issuer(&proposal.proposer, proposal.currency_id)?;
+
+			Self::deposit_event(Event::IssueApproved(proposal_id, approver));
+
+			Self::do_issue(proposal.proposer, proposal.dest, proposal.currency_id, proposal.amount)
+		}
+
+		/// Cancel an issue proposal waiting for approval. Only the proposer or `ControlOrigin` can
+		/// cancel it.
+		#[pallet::weight(T::WeightInfo::cancel_issue_proposal())]
+		#[transactional]
+		pub fn cancel_issue_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: IssueProposalId,
+		) -> DispatchResult {
+			let canceller = match T::ControlOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let proposal = Self::take_issue_proposal(proposal_id)?;
+			if let Some(canceller) = canceller {
+				ensure!(proposal.proposer == canceller, Error::<T>::NotAllowed);
+			}
+
+			Self::deposit_event(Event::IssueProposalCancelled(proposal_id));
+			Ok(())
+		}
+
+		/// Destroy some balance of the issuer.
+		#[pallet::weight(T::WeightInfo::burn())]
+		#[transactional]
+		pub fn burn(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let burner = ensure_signed(origin)?;
+
+			Self::ensure_issuer(&burner, currency_id)?;
+
+			let balance = T::MultiCurrency::free_balance(currency_id, &burner);
+			ensure!(balance >= amount, Error::<T>::NotEnoughBalance);
+
+			T::MultiCurrency::withdraw(currency_id, &burner, amount)?;
+
+			Self::deposit_event(Event::Burned(burner, currency_id, amount));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_issuer_cap())]
+		pub fn set_issuer_cap(
+			origin: OriginFor<T>,
+			account: AccountIdOf<T>,
+			currency_id: CurrencyId,
+			cap: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			match cap {
+				Some(cap) => IssuerCaps::<T>::insert(&account, currency_id, cap),
+				None => IssuerCaps::<T>::remove(&account, currency_id),
+			}
+
+			Self::deposit_event(Event::IssuerCapSet(account, currency_id, cap));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_supply_cap())]
+		pub fn set_supply_cap(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			cap: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			match cap {
+				Some(cap) => SupplyCaps::<T>::insert(currency_id, cap),
+				None => SupplyCaps::<T>::remove(currency_id),
+			}
+
+			Self::deposit_event(Event::SupplyCapSet(currency_id, cap));
+			Ok(())
+		}
+
+		#[pallet::weight(T::WeightInfo::set_two_step_issue())]
+		pub fn set_two_step_issue(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			required: bool,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			if required {
+				TwoStepIssue::<T>::insert(currency_id, true);
+			} else {
+				TwoStepIssue::<T>::remove(currency_id);
+			}
 
-			Self::deposit_event(Event::Issued(dest, currency_id, amount));
+			Self::deposit_event(Event::TwoStepIssueSet(currency_id, required));
 			Ok(())
 		}
 
@@ -252,9 +612,10 @@ pub mod pallet {
 		) -> DispatchResult {
 			let transferrer = ensure_signed(origin)?;
 
-			let transfer_whitelist =
-				Self::get_transfer_whitelist(currency_id).ok_or(Error::<T>::NotAllowed)?;
-			ensure!(transfer_whitelist.contains(&transferrer), Error::<T>::NotAllowed);
+			ensure!(
+				TransferWhiteList::<T>::contains_key(currency_id, &transferrer),
+				Error::<T>::NotAllowed
+			);
 
 			let balance = T::MultiCurrency::free_balance(currency_id, &transferrer);
 			ensure!(balance >= amount, Error::<T>::NotEnoughBalance);
@@ -265,4 +626,68 @@ pub mod pallet {
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// The accounts which can issue the currency.
+		pub fn issue_whitelist(currency_id: CurrencyId) -> Vec<AccountIdOf<T>> {
+			IssueWhiteList::<T>::iter_key_prefix(currency_id).collect()
+		}
+
+		/// The accounts which can transfer the currency.
+		pub fn transfer_whitelist(currency_id: CurrencyId) -> Vec<AccountIdOf<T>> {
+			TransferWhiteList::<T>::iter_key_prefix(currency_id).collect()
+		}
+
+		fn ensure_issuer(account: &AccountIdOf<T>, currency_id: CurrencyId) -> DispatchResult {
+			ensure!(
+				IssueWhiteList::<T>::contains_key(currency_id, account),
+				Error::<T>::NotAllowed
+			);
+			Ok(())
+		}
+
+		fn take_issue_proposal(
+			proposal_id: IssueProposalId,
+		) -> Result<IssueProposal<AccountIdOf<T>, BalanceOf<T>>, DispatchError> {
+			let proposal =
+				IssueProposals::<T>::take(proposal_id).ok_or(Error::<T>::ProposalNotExist)?;
+			OpenIssueProposals::<T>::mutate_exists(&proposal.proposer, |open| {
+				*open = open.and_then(|open| open.checked_sub(1)).filter(|open| *open > 0);
+			});
+			Ok(proposal)
+		}
+
+		fn do_issue(
+			issuer: AccountIdOf<T>,
+			dest: AccountIdOf<T>,
+			currency_id: CurrencyId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			if let Some(cap) = Self::supply_cap(currency_id) {
+				let total_issuance = T::MultiCurrency::total_issuance(currency_id);
+				ensure!(total_issuance.saturating_add(amount) <= cap, Error::<T>::ExceedSupplyCap);
+			}
+
+			let period = frame_system::Pallet::<T>::block_number()
+				.checked_div(&T::IssuePeriod::get())
+				.unwrap_or_else(Zero::zero);
+			let (last_period, issued) = Self::issued_in_period(&issuer, currency_id);
+			let issued = if last_period == period { issued } else { Zero::zero() };
+			let issued = issued.saturating_add(amount);
+			if let Some(cap) = Self::issuer_cap(&issuer, currency_id) {
+				ensure!(issued <= cap, Error::<T>::ExceedIssuerCap);
+			}
+			IssuedInPeriod::<T>::insert(&issuer, currency_id, (period, issued));
+
+			T::MultiCurrency::deposit(currency_id, &dest, amount)?;
+
+			let total_issued = TotalIssued::<T>::mutate(&issuer, currency_id, |total| {
+				*total = total.saturating_add(amount);
+				*total
+			});
+
+			Self::deposit_event(Event::Issued(issuer, dest, currency_id, amount, total_issued));
+			Ok(())
+		}
+	}
 }
@@ -165,11 +165,18 @@ impl pallet_collective::Config<CouncilCollective> for Runtime {
 	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const IssuePeriod: BlockNumber = 10;
+	pub const MaxIssueProposalsPerIssuer: u32 = 2;
+}
+
 impl bifrost_token_issuer::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Currencies;
 	type ControlOrigin =
 		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
+	type IssuePeriod = IssuePeriod;
+	type MaxIssueProposalsPerIssuer = MaxIssueProposalsPerIssuer;
 	type WeightInfo = ();
 }
 
@@ -18,7 +18,8 @@
 
 #![cfg(test)]
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, StorageHasher};
+use sp_runtime::DispatchError::BadOrigin;
 
 use crate::{mock::*, *};
 
@@ -47,7 +48,7 @@ fn add_to_issue_whitelist_should_work() {
 			ZLK,
 			CHARLIE
 		));
-		assert_eq!(TokenIssuer::get_issue_whitelist(ZLK), Some(vec![CHARLIE]));
+		assert_eq!(TokenIssuer::issue_whitelist(ZLK), vec![CHARLIE]);
 		// Charlie succuessfully issue 800 unit of ZLK to Alice account
 		assert_ok!(TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 800));
 		assert_eq!(Tokens::free_balance(ZLK, &ALICE), 800);
@@ -107,7 +108,7 @@ fn add_to_transfer_whitelist_should_work() {
 			ZLK,
 			CHARLIE
 		));
-		assert_eq!(TokenIssuer::get_transfer_whitelist(ZLK), Some(vec![CHARLIE]));
+		assert_eq!(TokenIssuer::transfer_whitelist(ZLK), vec![CHARLIE]);
 		// Charlie succuessfully transfer 800 unit of ZLK to Alice account
 		assert_ok!(TokenIssuer::transfer(Origin::signed(CHARLIE), ALICE, ZLK, 800));
 		assert_eq!(Tokens::free_balance(ZLK, &ALICE), 800);
@@ -157,3 +158,245 @@ fn remove_from_transfer_whitelist_should_work() {
 		);
 	});
 }
+
+#[test]
+fn issue_should_respect_caps() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokenIssuer::add_to_issue_whitelist(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			ZLK,
+			CHARLIE
+		));
+		assert_noop!(
+			TokenIssuer::set_issuer_cap(Origin::signed(CHARLIE), CHARLIE, ZLK, Some(1000)),
+			BadOrigin
+		);
+		assert_ok!(TokenIssuer::set_issuer_cap(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			CHARLIE,
+			ZLK,
+			Some(1000)
+		));
+
+		// Charlie can issue 1000 ZLK per period.
+		assert_ok!(TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 800));
+		assert_noop!(
+			TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 300),
+			Error::<Runtime>::ExceedIssuerCap
+		);
+
+		System::set_block_number(10);
+		assert_ok!(TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 300));
+
+		// The total supply of ZLK can't exceed 1500.
+		assert_ok!(TokenIssuer::set_supply_cap(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			ZLK,
+			Some(1500)
+		));
+		assert_noop!(
+			TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 500),
+			Error::<Runtime>::ExceedSupplyCap
+		);
+		assert_ok!(TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 400));
+		System::assert_last_event(mock::Event::TokenIssuer(crate::Event::Issued(
+			CHARLIE, ALICE, ZLK, 400, 1500,
+		)));
+
+		assert_eq!(Tokens::free_balance(ZLK, &ALICE), 1500);
+		assert_eq!(TokenIssuer::total_issued(CHARLIE, ZLK), 1500);
+		assert_eq!(TokenIssuer::issued_in_period(CHARLIE, ZLK), (1, 700));
+	});
+}
+
+#[test]
+fn two_step_issue_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		System::set_block_number(1);
+		for account in [BOB, CHARLIE] {
+			assert_ok!(TokenIssuer::add_to_issue_whitelist(
+				pallet_collective::RawOrigin::Members(2, 3).into(),
+				ZLK,
+				account
+			));
+		}
+		assert_ok!(TokenIssuer::set_two_step_issue(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			ZLK,
+			true
+		));
+
+		// ZLK can't be issued directly anymore.
+		assert_noop!(
+			TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 800),
+			Error::<Runtime>::ApprovalRequired
+		);
+
+		assert_ok!(TokenIssuer::propose_issue(Origin::signed(CHARLIE), ALICE, ZLK, 800));
+		assert_eq!(Tokens::free_balance(ZLK, &ALICE), 0);
+
+		// Charlie can't approve their own proposal, and Alice isn't an issuer.
+		assert_noop!(
+			TokenIssuer::approve_issue(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::SelfApproval
+		);
+		assert_noop!(
+			TokenIssuer::approve_issue(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NotAllowed
+		);
+
+		assert_ok!(TokenIssuer::approve_issue(Origin::signed(BOB), 0));
+		assert_eq!(Tokens::free_balance(ZLK, &ALICE), 800);
+		assert_eq!(TokenIssuer::total_issued(CHARLIE, ZLK), 800);
+		assert_eq!(TokenIssuer::issue_proposal(0), None);
+		assert_eq!(TokenIssuer::open_issue_proposals(CHARLIE), 0);
+		assert_noop!(
+			TokenIssuer::approve_issue(Origin::signed(BOB), 0),
+			Error::<Runtime>::ProposalNotExist
+		);
+	});
+}
+
+#[test]
+fn cancel_issue_proposal_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		for account in [BOB, CHARLIE] {
+			assert_ok!(TokenIssuer::add_to_issue_whitelist(
+				pallet_collective::RawOrigin::Members(2, 3).into(),
+				ZLK,
+				account
+			));
+		}
+
+		// Charlie can't have more than two proposals waiting for approval.
+		assert_ok!(TokenIssuer::propose_issue(Origin::signed(CHARLIE), ALICE, ZLK, 800));
+		assert_ok!(TokenIssuer::propose_issue(Origin::signed(CHARLIE), ALICE, ZLK, 800));
+		assert_eq!(TokenIssuer::open_issue_proposals(CHARLIE), 2);
+		assert_noop!(
+			TokenIssuer::propose_issue(Origin::signed(CHARLIE), ALICE, ZLK, 800),
+			Error::<Runtime>::TooManyIssueProposals
+		);
+
+		// Only the proposer or the control origin can cancel a proposal.
+		assert_noop!(
+			TokenIssuer::cancel_issue_proposal(Origin::signed(BOB), 0),
+			Error::<Runtime>::NotAllowed
+		);
+		assert_ok!(TokenIssuer::cancel_issue_proposal(Origin::signed(CHARLIE), 0));
+		assert_ok!(TokenIssuer::cancel_issue_proposal(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			1
+		));
+		assert_eq!(TokenIssuer::issue_proposal(0), None);
+		assert_eq!(TokenIssuer::issue_proposal(1), None);
+		assert_eq!(TokenIssuer::open_issue_proposals(CHARLIE), 0);
+		assert_noop!(
+			TokenIssuer::approve_issue(Origin::signed(BOB), 0),
+			Error::<Runtime>::ProposalNotExist
+		);
+		assert_noop!(
+			TokenIssuer::cancel_issue_proposal(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::ProposalNotExist
+		);
+
+		// The cancelled proposals make room for new ones.
+		assert_ok!(TokenIssuer::propose_issue(Origin::signed(CHARLIE), ALICE, ZLK, 800));
+		assert_eq!(Tokens::free_balance(ZLK, &ALICE), 0);
+	});
+}
+
+#[test]
+fn burn_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		initialize_charlie_as_issue_whitelist_member();
+
+		// Alice isn't in the issue whitelist of DOT.
+		assert_noop!(
+			TokenIssuer::burn(Origin::signed(ALICE), DOT, 50),
+			Error::<Runtime>::NotAllowed
+		);
+		assert_noop!(
+			TokenIssuer::burn(Origin::signed(CHARLIE), ZLK, 1200),
+			Error::<Runtime>::NotEnoughBalance
+		);
+
+		assert_ok!(TokenIssuer::burn(Origin::signed(CHARLIE), ZLK, 400));
+		assert_eq!(Tokens::free_balance(ZLK, &CHARLIE), 600);
+		assert_eq!(Tokens::total_issuance(ZLK), 600);
+	});
+}
+
+#[test]
+fn batch_whitelist_calls_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_noop!(
+			TokenIssuer::batch_add_to_issue_whitelist(Origin::signed(CHARLIE), ZLK, vec![CHARLIE]),
+			BadOrigin
+		);
+
+		// Accounts already in the whitelist are skipped.
+		assert_ok!(TokenIssuer::add_to_issue_whitelist(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			ZLK,
+			ALICE
+		));
+		assert_ok!(TokenIssuer::batch_add_to_issue_whitelist(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			ZLK,
+			vec![ALICE, BOB, CHARLIE]
+		));
+		let mut issue_whitelist = TokenIssuer::issue_whitelist(ZLK);
+		issue_whitelist.sort();
+		assert_eq!(issue_whitelist, vec![ALICE, BOB, CHARLIE]);
+
+		assert_ok!(TokenIssuer::batch_remove_from_issue_whitelist(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			ZLK,
+			vec![ALICE, CHARLIE, CHARLIE]
+		));
+		assert_eq!(TokenIssuer::issue_whitelist(ZLK), vec![BOB]);
+		assert_noop!(
+			TokenIssuer::issue(Origin::signed(CHARLIE), ALICE, ZLK, 800),
+			Error::<Runtime>::NotAllowed
+		);
+
+		assert_ok!(TokenIssuer::batch_add_to_transfer_whitelist(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			DOT,
+			vec![ALICE, BOB]
+		));
+		assert_ok!(TokenIssuer::transfer(Origin::signed(BOB), CHARLIE, DOT, 50));
+		assert_ok!(TokenIssuer::batch_remove_from_transfer_whitelist(
+			pallet_collective::RawOrigin::Members(2, 3).into(),
+			DOT,
+			vec![BOB]
+		));
+		assert_eq!(TokenIssuer::transfer_whitelist(DOT), vec![ALICE]);
+		assert_eq!(TokenIssuer::issue_whitelist(DOT), vec![]);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_split_whitelists() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		StorageVersion::new(0).put::<TokenIssuer>();
+		for (item, accounts) in
+			[(&b"IssueWhiteList"[..], vec![ALICE, BOB]), (&b"TransferWhiteList"[..], vec![CHARLIE])]
+		{
+			frame_support::storage::migration::put_storage_value(
+				b"TokenIssuer",
+				item,
+				&Blake2_128Concat::hash(&ZLK.encode()),
+				accounts,
+			);
+		}
+
+		migration::migrate_to_v1::<Runtime>();
+		let mut issue_whitelist = TokenIssuer::issue_whitelist(ZLK);
+		issue_whitelist.sort();
+		assert_eq!(issue_whitelist, vec![ALICE, BOB]);
+		assert_eq!(TokenIssuer::transfer_whitelist(ZLK), vec![CHARLIE]);
+		assert_eq!(StorageVersion::get::<TokenIssuer>(), STORAGE_VERSION);
+	});
+}
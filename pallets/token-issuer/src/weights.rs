@@ -31,8 +31,21 @@ pub trait WeightInfo {
 	fn remove_from_transfer_whitelist() -> Weight;
 	fn issue() -> Weight;
 	fn transfer() -> Weight;
+	fn propose_issue() -> Weight;
+	fn approve_issue() -> Weight;
+	fn cancel_issue_proposal() -> Weight;
+	fn burn() -> Weight;
+	fn set_issuer_cap() -> Weight;
+	fn set_supply_cap() -> Weight;
+	fn set_two_step_issue() -> Weight;
+	fn batch_add_to_issue_whitelist(n: u32) -> Weight;
+	fn batch_remove_from_issue_whitelist(n: u32) -> Weight;
+	fn batch_add_to_transfer_whitelist(n: u32) -> Weight;
+	fn batch_remove_from_transfer_whitelist(n: u32) -> Weight;
 }
 
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl WeightInfo for () {
 	fn add_to_issue_whitelist() -> Weight {
 		(50_000_000 as Weight)
@@ -57,4 +70,70 @@ impl WeightInfo for () {
 	fn transfer() -> Weight {
 		(50_000_000 as Weight)
 	}
+
+	fn propose_issue() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn approve_issue() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+
+	fn cancel_issue_proposal() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+
+	fn burn() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn set_issuer_cap() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_supply_cap() -> Weight {
+		(50_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_two_step_issue() -> Weight {
+		(50_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn batch_add_to_issue_whitelist(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn batch_remove_from_issue_whitelist(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn batch_add_to_transfer_whitelist(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn batch_remove_from_transfer_whitelist(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((20_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
 }
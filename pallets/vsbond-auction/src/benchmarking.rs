@@ -19,7 +19,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::sp_runtime::traits::UniqueSaturatedFrom;
+use frame_support::{sp_runtime::traits::UniqueSaturatedFrom, traits::Hooks};
 use frame_system::RawOrigin;
 
 use super::*;
@@ -75,6 +75,72 @@ benchmarks! {
 		VSBondAuction::<T>::create_order(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(order_owner)), index, first_slot, last_slot, supply, total_price, order_type)?;
 	}: _(RawOrigin::Signed(caller),0u64, BalanceOf::<T>::unique_saturated_from(5u128))
 
+	create_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		let index: ParaId = 3000;
+		let first_slot = 13u32;
+		let last_slot = 20u32;
+		let supply = BalanceOf::<T>::unique_saturated_from(10u128);
+		let kind = AuctionKind::English { reserve_price: BalanceOf::<T>::unique_saturated_from(30u128) };
+	}: _(RawOrigin::Signed(caller), index, first_slot, last_slot, supply, kind, 10u32)
+
+	revoke_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		let index: ParaId = 3000;
+		let first_slot = 13u32;
+		let last_slot = 20u32;
+		let supply = BalanceOf::<T>::unique_saturated_from(10u128);
+		let kind = AuctionKind::English { reserve_price: BalanceOf::<T>::unique_saturated_from(30u128) };
+
+		VSBondAuction::<T>::create_auction(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller.clone())), index, first_slot, last_slot, supply, kind, 10u32)?;
+	}: _(RawOrigin::Signed(caller),0u64)
+
+	clinch_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		let index: ParaId = 3000;
+		let first_slot = 13u32;
+		let last_slot = 20u32;
+		let supply = BalanceOf::<T>::unique_saturated_from(10u128);
+		let auction_owner = account("bechmarking_account_1", 0, 0);
+		let kind = AuctionKind::Dutch {
+			start_price: BalanceOf::<T>::unique_saturated_from(30u128),
+			end_price: BalanceOf::<T>::unique_saturated_from(10u128),
+		};
+
+		VSBondAuction::<T>::create_auction(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(auction_owner)), index, first_slot, last_slot, supply, kind, 10u32)?;
+	}: _(RawOrigin::Signed(caller),0u64)
+
+	bid_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		let index: ParaId = 3000;
+		let first_slot = 13u32;
+		let last_slot = 20u32;
+		let supply = BalanceOf::<T>::unique_saturated_from(10u128);
+		let auction_owner = account("bechmarking_account_1", 0, 0);
+		let kind = AuctionKind::English { reserve_price: BalanceOf::<T>::unique_saturated_from(30u128) };
+
+		VSBondAuction::<T>::create_auction(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(auction_owner)), index, first_slot, last_slot, supply, kind, 10u32)?;
+	}: _(RawOrigin::Signed(caller),0u64, BalanceOf::<T>::unique_saturated_from(40u128))
+
+	on_initialize {
+		let n in 0 .. T::MaximumOrderInTrade::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let bidder: T::AccountId = account("bechmarking_account_1", 0, 0);
+		let index: ParaId = 3000;
+		let first_slot = 13u32;
+		let last_slot = 20u32;
+		let supply = BalanceOf::<T>::unique_saturated_from(10u128);
+		let kind = AuctionKind::English { reserve_price: BalanceOf::<T>::unique_saturated_from(30u128) };
+
+		for auction_id in 0 .. n {
+			VSBondAuction::<T>::create_auction(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller.clone())), index, first_slot, last_slot, supply, kind, 10u32)?;
+			VSBondAuction::<T>::bid_auction(<T as frame_system::Config>::Origin::from(RawOrigin::Signed(bidder.clone())), auction_id as u64, BalanceOf::<T>::unique_saturated_from(40u128))?;
+		}
+
+		let end_block = frame_system::Pallet::<T>::block_number().saturating_add(10u32);
+	}: { VSBondAuction::<T>::on_initialize(end_block); }
+
 }
 
 impl_benchmark_test_suite!(VSBondAuction, crate::mock::new_test_ext(), crate::mock::Test);
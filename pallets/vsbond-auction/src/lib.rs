@@ -24,14 +24,24 @@
 //! Users can create sell orders by `create_order`;
 //! Or buy the sell orders by `clinch_order`, `partial_clinch_order`.
 //!
-//! NOTE: Pallet does not support users creating buy orders by now.
+//! Users can also sell a lot of `vsbond` by a time-bound auction through `create_auction`:
+//! - `Dutch`: the price descends linearly from `start_price` to `end_price`, the first user who
+//!   calls `clinch_auction` takes the whole lot at the current price;
+//! - `English`: users raise the price by `bid_auction`, the bids are reserved and the highest one
+//!   clinches the lot when the auction is settled in `on_initialize` at the end block. A bid placed
+//!   within `AntiSnipingPeriod` blocks before the end extends the auction.
+//!
+//! At most `MaxAuctionsEndPerBlock` auctions end in a block, the end of the others is put off to
+//! the first following block with room.
 
 use frame_support::{
 	pallet_prelude::*,
 	sp_runtime::{
 		traits::{SaturatedConversion, Saturating, Zero},
-		FixedPointNumber, FixedU128,
+		FixedPointNumber, FixedU128, Perbill,
 	},
+	traits::BalanceStatus,
+	transactional,
 };
 use frame_system::pallet_prelude::*;
 use node_primitives::{CurrencyId, LeasePeriod, TokenInfo, TokenSymbol};
@@ -99,7 +109,75 @@ pub enum OrderType {
 	Buy,
 }
 
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug, TypeInfo)]
+pub enum AuctionKind<Balance> {
+	/// The total price descends linearly from `start_price` to `end_price` during the auction
+	Dutch { start_price: Balance, end_price: Balance },
+	/// The bids need to be greater than or equal to `reserve_price`
+	English { reserve_price: Balance },
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub struct AuctionInfo<T: Config> {
+	/// The owner of the auction
+	owner: AccountIdOf<T>,
+	/// The vsbond type of the auction to sell
+	vsbond: CurrencyId,
+	/// The quantity of vsbond to sell, the lot can only be clinched as a whole
+	amount: BalanceOf<T>,
+	/// The kind of the auction with its prices
+	kind: AuctionKind<BalanceOf<T>>,
+	/// The block at which the auction started
+	start_block: BlockNumberFor<T>,
+	/// The block at which the auction will be settled
+	end_block: BlockNumberFor<T>,
+	/// The highest bidder and its bid(total price) of an english auction
+	best_bid: Option<(AccountIdOf<T>, BalanceOf<T>)>,
+	/// The unique id of the auction
+	auction_id: AuctionId,
+}
+
+impl<T: Config> AuctionInfo<T> {
+	/// Get the total price of a dutch auction at `now`, or the price to beat of an english
+	/// auction.
+	pub fn current_price(&self, now: BlockNumberFor<T>) -> BalanceOf<T> {
+		match self.kind {
+			AuctionKind::Dutch { start_price, end_price } => {
+				let duration = self.end_block.saturating_sub(self.start_block);
+				let elapsed = min(now.saturating_sub(self.start_block), duration);
+
+				if duration.is_zero() {
+					return end_price;
+				}
+
+				let descended = Perbill::from_rational(elapsed, duration)
+					.mul_floor(start_price.saturating_sub(end_price));
+
+				start_price.saturating_sub(descended)
+			},
+			AuctionKind::English { reserve_price } => match self.best_bid {
+				Some((_, bid)) => bid,
+				None => reserve_price,
+			},
+		}
+	}
+}
+
+impl<T: Config> core::fmt::Debug for AuctionInfo<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_tuple("")
+			.field(&self.owner)
+			.field(&self.vsbond)
+			.field(&self.amount)
+			.field(&self.kind)
+			.field(&self.end_block)
+			.field(&self.auction_id)
+			.finish()
+	}
+}
+
 type OrderId = u64;
+type AuctionId = u64;
 type ParaId = u32;
 
 #[allow(type_alias_bounds)]
@@ -135,6 +213,20 @@ pub mod pallet {
 		type MultiCurrency: MultiCurrency<AccountIdOf<Self>, CurrencyId = CurrencyId>
 			+ MultiReservableCurrency<AccountIdOf<Self>, CurrencyId = CurrencyId>;
 
+		/// The maximum number of blocks that an auction can last
+		#[pallet::constant]
+		type MaximumAuctionDuration: Get<BlockNumberFor<Self>>;
+
+		/// A bid placed within `AntiSnipingPeriod` blocks before the end of an english auction
+		/// extends the auction to `AntiSnipingPeriod` blocks after the bid
+		#[pallet::constant]
+		type AntiSnipingPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of auctions settled in a block. The auctions ending beyond it are
+		/// settled in the following blocks.
+		#[pallet::constant]
+		type MaxAuctionsEndPerBlock: Get<u32>;
+
 		/// Set default weight.
 		type WeightInfo: WeightInfo;
 	}
@@ -151,6 +243,17 @@ pub mod pallet {
 		ForbidClinchOrderNotInTrade,
 		ForbidClinchOrderWithinOwnership,
 		ExceedMaximumOrderInTrade,
+		NotFindAuctionInfo,
+		InvalidAuctionDuration,
+		InvalidAuctionPrice,
+		AuctionKindNotMatch,
+		AuctionAlreadyEnded,
+		ForbidRevokeAuctionWithoutOwnership,
+		ForbidRevokeAuctionWithBid,
+		ForbidClinchAuctionWithinOwnership,
+		ForbidBidAuctionWithinOwnership,
+		BidNotHighEnough,
+		ExceedMaximumAuctionInTrade,
 		Unexpected,
 	}
 
@@ -189,6 +292,40 @@ pub mod pallet {
 			BalanceOf<T>,
 			BalanceOf<T>,
 		),
+		/// The auction has been created.
+		///
+		/// [auction_id, auction_creator, vsbond_type, vsbond_amount, auction_kind, end_block]
+		AuctionCreated(
+			AuctionId,
+			AccountIdOf<T>,
+			CurrencyId,
+			BalanceOf<T>,
+			AuctionKind<BalanceOf<T>>,
+			BlockNumberFor<T>,
+		),
+		/// The auction has been revoked.
+		///
+		/// [auction_id, auction_creator, vsbond_type, vsbond_amount]
+		AuctionRevoked(AuctionId, AccountIdOf<T>, CurrencyId, BalanceOf<T>),
+		/// A bid has been placed on the english auction.
+		///
+		/// [auction_id, bidder, total_price, end_block]
+		AuctionBidPlaced(AuctionId, AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>),
+		/// The auction has been clinched.
+		///
+		/// [auction_id, auction_creator, auction_winner, vsbond_type, vsbond_amount, total_price]
+		AuctionClinched(
+			AuctionId,
+			AccountIdOf<T>,
+			AccountIdOf<T>,
+			CurrencyId,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		/// The auction has ended without any taker or bid.
+		///
+		/// [auction_id, auction_creator, vsbond_type, vsbond_amount]
+		AuctionUnsold(AuctionId, AccountIdOf<T>, CurrencyId, BalanceOf<T>),
 	}
 
 	#[pallet::storage]
@@ -213,9 +350,56 @@ pub mod pallet {
 	pub(crate) type TotalOrderInfos<T: Config> =
 		StorageMap<_, Blake2_128Concat, OrderId, OrderInfo<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn auction_id)]
+	pub(crate) type NextAuctionId<T: Config> = StorageValue<_, AuctionId, ValueQuery>;
+
+	// Just store auction ids that be in-trade.
+	#[pallet::storage]
+	#[pallet::getter(fn user_auction_ids)]
+	pub(crate) type UserAuctionIds<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<AuctionId, T::MaximumOrderInTrade>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auction_info)]
+	pub(crate) type TotalAuctionInfos<T: Config> =
+		StorageMap<_, Blake2_128Concat, AuctionId, AuctionInfo<T>>;
+
+	// The auctions to settle at the block.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions_end_at)]
+	pub(crate) type AuctionsEndAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<AuctionId, T::MaxAuctionsEndPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let auction_ids = AuctionsEndAt::<T>::take(n);
+			let settled = auction_ids.len() as u32;
+
+			for auction_id in auction_ids {
+				if let Some(auction_info) = Self::auction_info(auction_id) {
+					Self::settle_auction(auction_info);
+				}
+			}
+
+			T::WeightInfo::on_initialize(settled)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a sell order or buy order to sell `vsbond`.
@@ -235,14 +419,8 @@ pub mod pallet {
 			// Check amount
 			ensure!(amount > T::MinimumAmount::get(), Error::<T>::NotEnoughAmount);
 
-			let currency_id_u64: u64 = T::InvoicingCurrency::get().currency_id();
-			let token_symbol_bit = (currency_id_u64 & 0x0000_0000_0000_00ff) as u8;
-			let currency_token_symbol =
-				TokenSymbol::try_from(token_symbol_bit).map_err(|_| Error::<T>::Unexpected)?;
-
 			// Construct vsbond
-			let (_, vsbond) =
-				CurrencyId::vsAssets(currency_token_symbol, index, first_slot, last_slot);
+			let vsbond = Self::vsbond_of(index, first_slot, last_slot)?;
 
 			// Check the balance
 			let (token_reserved, amount_reserved) = match order_type {
@@ -460,9 +638,307 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Create a dutch or english auction to sell `vsbond` as a whole lot.
+		///
+		/// The auction starts at the current block and will be settled after `duration` blocks.
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ParaId,
+			#[pallet::compact] first_slot: LeasePeriodOf<T>,
+			#[pallet::compact] last_slot: LeasePeriodOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			kind: AuctionKind<BalanceOf<T>>,
+			#[pallet::compact] duration: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			// Check origin
+			let owner = ensure_signed(origin)?;
+
+			// Check amount
+			ensure!(amount > T::MinimumAmount::get(), Error::<T>::NotEnoughAmount);
+
+			// Check duration
+			ensure!(
+				!duration.is_zero() && duration <= T::MaximumAuctionDuration::get(),
+				Error::<T>::InvalidAuctionDuration
+			);
+
+			// Check price
+			match kind {
+				AuctionKind::Dutch { start_price, end_price } => ensure!(
+					start_price >= end_price && !end_price.is_zero(),
+					Error::<T>::InvalidAuctionPrice
+				),
+				AuctionKind::English { reserve_price } =>
+					ensure!(!reserve_price.is_zero(), Error::<T>::InvalidAuctionPrice),
+			}
+
+			// Construct vsbond
+			let vsbond = Self::vsbond_of(index, first_slot, last_slot)?;
+
+			// Check the balance
+			ensure!(
+				T::MultiCurrency::can_reserve(vsbond, &owner, amount),
+				Error::<T>::NotEnoughBalanceToReserve
+			);
+
+			let auction_ids_len = Self::user_auction_ids(&owner).len();
+			ensure!(
+				auction_ids_len < T::MaximumOrderInTrade::get() as usize,
+				Error::<T>::ExceedMaximumAuctionInTrade,
+			);
+
+			// Create AuctionInfo
+			let start_block = frame_system::Pallet::<T>::block_number();
+			let auction_id = Self::next_auction_id();
+			let end_block =
+				Self::schedule_auction_end(auction_id, start_block.saturating_add(duration));
+			let auction_info = AuctionInfo::<T> {
+				owner: owner.clone(),
+				vsbond,
+				amount,
+				kind,
+				start_block,
+				end_block,
+				best_bid: None,
+				auction_id,
+			};
+
+			// Reserve the balance.
+			T::MultiCurrency::reserve(vsbond, &owner, amount)?;
+
+			// Insert AuctionInfo to Storage
+			TotalAuctionInfos::<T>::insert(auction_id, auction_info);
+			UserAuctionIds::<T>::try_append(owner.clone(), auction_id)
+				.map_err(|_| Error::<T>::Unexpected)?;
+
+			Self::deposit_event(Event::AuctionCreated(
+				auction_id, owner, vsbond, amount, kind, end_block,
+			));
+
+			Ok(().into())
+		}
+
+		/// Revoke an auction by the auction creator.
+		///
+		/// NOTE: An english auction which has received bids cannot be revoked.
+		#[pallet::weight(T::WeightInfo::revoke_auction())]
+		pub fn revoke_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: AuctionId,
+		) -> DispatchResultWithPostInfo {
+			// Check origin
+			let from = ensure_signed(origin)?;
+
+			// Check AuctionInfo
+			let auction_info =
+				Self::auction_info(auction_id).ok_or(Error::<T>::NotFindAuctionInfo)?;
+
+			// Check AuctionOwner
+			ensure!(auction_info.owner == from, Error::<T>::ForbidRevokeAuctionWithoutOwnership);
+			ensure!(auction_info.best_bid.is_none(), Error::<T>::ForbidRevokeAuctionWithBid);
+
+			// To unreserve
+			let reserved_balance =
+				T::MultiCurrency::reserved_balance(auction_info.vsbond, &auction_info.owner);
+			ensure!(
+				reserved_balance >= auction_info.amount,
+				Error::<T>::NotEnoughBalanceToUnreserve
+			);
+			T::MultiCurrency::unreserve(
+				auction_info.vsbond,
+				&auction_info.owner,
+				auction_info.amount,
+			);
+
+			// Revoke auction
+			Self::remove_auction(&auction_info);
+			Self::unschedule_auction_end(auction_id, auction_info.end_block);
+
+			Self::deposit_event(Event::AuctionRevoked(
+				auction_id,
+				auction_info.owner,
+				auction_info.vsbond,
+				auction_info.amount,
+			));
+
+			Ok(().into())
+		}
+
+		/// Users(non-auction-creator) take the whole lot of a dutch auction at the current price.
+		#[pallet::weight(T::WeightInfo::clinch_auction())]
+		#[transactional]
+		pub fn clinch_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: AuctionId,
+		) -> DispatchResultWithPostInfo {
+			// Check origin
+			let opponent = ensure_signed(origin)?;
+
+			// Check AuctionInfo
+			let auction_info =
+				Self::auction_info(auction_id).ok_or(Error::<T>::NotFindAuctionInfo)?;
+
+			ensure!(
+				matches!(auction_info.kind, AuctionKind::Dutch { .. }),
+				Error::<T>::AuctionKindNotMatch
+			);
+			ensure!(auction_info.owner != opponent, Error::<T>::ForbidClinchAuctionWithinOwnership);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now < auction_info.end_block, Error::<T>::AuctionAlreadyEnded);
+
+			// Calculate the total price that buyer need to pay
+			let price_to_pay = auction_info.current_price(now);
+
+			// Check the balance of opponent
+			T::MultiCurrency::ensure_can_withdraw(
+				T::InvoicingCurrency::get(),
+				&opponent,
+				price_to_pay,
+			)
+			.map_err(|_| Error::<T>::DontHaveEnoughToPay)?;
+
+			let reserved_balance =
+				T::MultiCurrency::reserved_balance(auction_info.vsbond, &auction_info.owner);
+			ensure!(
+				reserved_balance >= auction_info.amount,
+				Error::<T>::NotEnoughBalanceToUnreserve
+			);
+
+			// Exchange: Transfer assets to owner
+			T::MultiCurrency::transfer(
+				T::InvoicingCurrency::get(),
+				&opponent,
+				&auction_info.owner,
+				price_to_pay,
+			)?;
+			// Exchange: Transfer assets to opponent
+			let not_repatriated = T::MultiCurrency::repatriate_reserved(
+				auction_info.vsbond,
+				&auction_info.owner,
+				&opponent,
+				auction_info.amount,
+				BalanceStatus::Free,
+			)?;
+			ensure!(not_repatriated.is_zero(), Error::<T>::NotEnoughBalanceToUnreserve);
+
+			Self::remove_auction(&auction_info);
+			Self::unschedule_auction_end(auction_id, auction_info.end_block);
+
+			Self::deposit_event(Event::AuctionClinched(
+				auction_id,
+				auction_info.owner,
+				opponent,
+				auction_info.vsbond,
+				auction_info.amount,
+				price_to_pay,
+			));
+
+			Ok(().into())
+		}
+
+		/// Users(non-auction-creator) bid on an english auction with the total price of the lot.
+		///
+		/// The bid is reserved until it is outbid or the auction is settled.
+		#[pallet::weight(T::WeightInfo::bid_auction())]
+		#[transactional]
+		pub fn bid_auction(
+			origin: OriginFor<T>,
+			#[pallet::compact] auction_id: AuctionId,
+			#[pallet::compact] total_price: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			// Check origin
+			let bidder = ensure_signed(origin)?;
+
+			// Check AuctionInfo
+			let auction_info =
+				Self::auction_info(auction_id).ok_or(Error::<T>::NotFindAuctionInfo)?;
+
+			let reserve_price = match auction_info.kind {
+				AuctionKind::English { reserve_price } => reserve_price,
+				AuctionKind::Dutch { .. } => return Err(Error::<T>::AuctionKindNotMatch.into()),
+			};
+			ensure!(auction_info.owner != bidder, Error::<T>::ForbidBidAuctionWithinOwnership);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now < auction_info.end_block, Error::<T>::AuctionAlreadyEnded);
+
+			// Check the bid
+			ensure!(total_price >= reserve_price, Error::<T>::BidNotHighEnough);
+			if let Some((_, best_price)) = auction_info.best_bid {
+				ensure!(total_price > best_price, Error::<T>::BidNotHighEnough);
+			}
+
+			// Check the balance of bidder, who only needs the increase when raising their own bid
+			let own_previous = match auction_info.best_bid {
+				Some((ref best_bidder, best_price)) if *best_bidder == bidder => best_price,
+				_ => Zero::zero(),
+			};
+			ensure!(
+				T::MultiCurrency::can_reserve(
+					T::InvoicingCurrency::get(),
+					&bidder,
+					total_price.saturating_sub(own_previous)
+				),
+				Error::<T>::DontHaveEnoughToPay
+			);
+
+			// Release the previous bid
+			if let Some((ref best_bidder, best_price)) = auction_info.best_bid {
+				T::MultiCurrency::unreserve(T::InvoicingCurrency::get(), best_bidder, best_price);
+			}
+
+			// Reserve the new bid
+			T::MultiCurrency::reserve(T::InvoicingCurrency::get(), &bidder, total_price)?;
+
+			// Extend the auction when the bid is close to the end
+			let sniping_end = now.saturating_add(T::AntiSnipingPeriod::get());
+			let end_block = if sniping_end > auction_info.end_block {
+				Self::unschedule_auction_end(auction_id, auction_info.end_block);
+				Self::schedule_auction_end(auction_id, sniping_end)
+			} else {
+				auction_info.end_block
+			};
+
+			TotalAuctionInfos::<T>::insert(
+				auction_id,
+				AuctionInfo {
+					end_block,
+					best_bid: Some((bidder.clone(), total_price)),
+					..auction_info
+				},
+			);
+
+			Self::deposit_event(Event::AuctionBidPlaced(
+				auction_id,
+				bidder,
+				total_price,
+				end_block,
+			));
+
+			Ok(().into())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		pub(crate) fn vsbond_of(
+			index: ParaId,
+			first_slot: LeasePeriodOf<T>,
+			last_slot: LeasePeriodOf<T>,
+		) -> Result<CurrencyId, Error<T>> {
+			let currency_id_u64: u64 = T::InvoicingCurrency::get().currency_id();
+			let token_symbol_bit = (currency_id_u64 & 0x0000_0000_0000_00ff) as u8;
+			let currency_token_symbol =
+				TokenSymbol::try_from(token_symbol_bit).map_err(|_| Error::<T>::Unexpected)?;
+
+			let (_, vsbond) =
+				CurrencyId::vsAssets(currency_token_symbol, index, first_slot, last_slot);
+
+			Ok(vsbond)
+		}
+
 		pub(crate) fn next_order_id() -> OrderId {
 			let next_order_id = Self::order_id();
 			NextOrderId::<T>::mutate(|current| *current += 1);
@@ -481,6 +957,113 @@ pub mod pallet {
 			});
 		}
 
+		pub(crate) fn next_auction_id() -> AuctionId {
+			let next_auction_id = Self::auction_id();
+			NextAuctionId::<T>::mutate(|current| *current += 1);
+			next_auction_id
+		}
+
+		pub(crate) fn remove_auction(auction_info: &AuctionInfo<T>) {
+			TotalAuctionInfos::<T>::remove(auction_info.auction_id);
+			UserAuctionIds::<T>::mutate(&auction_info.owner, |auction_ids| {
+				if let Some(position) =
+					auction_ids.iter().position(|&r| r == auction_info.auction_id)
+				{
+					auction_ids.remove(position);
+				}
+			});
+		}
+
+		/// Settle the auction at the first block with room from `end_block` on, return the block.
+		pub(crate) fn schedule_auction_end(
+			auction_id: AuctionId,
+			mut end_block: BlockNumberFor<T>,
+		) -> BlockNumberFor<T> {
+			while AuctionsEndAt::<T>::try_mutate(end_block, |auction_ids| {
+				auction_ids.try_push(auction_id)
+			})
+			.is_err()
+			{
+				end_block = end_block.saturating_add(1);
+			}
+
+			end_block
+		}
+
+		pub(crate) fn unschedule_auction_end(auction_id: AuctionId, end_block: BlockNumberFor<T>) {
+			AuctionsEndAt::<T>::mutate(end_block, |auction_ids| {
+				if let Some(position) = auction_ids.iter().position(|&r| r == auction_id) {
+					auction_ids.remove(position);
+				}
+			});
+		}
+
+		/// Settle the auction at its end block: deliver the lot to the highest bidder of an
+		/// english auction, or return it to the owner if nobody clinched it.
+		///
+		/// NOTE: If the exchange with the highest bidder fails, the bid is released and the lot is
+		/// returned to the owner as unsold.
+		pub(crate) fn settle_auction(auction_info: AuctionInfo<T>) {
+			Self::remove_auction(&auction_info);
+
+			if let Some((winner, total_price)) = auction_info.best_bid.clone() {
+				if Self::exchange_auction_lot(&auction_info, &winner, total_price).is_ok() {
+					Self::deposit_event(Event::AuctionClinched(
+						auction_info.auction_id,
+						auction_info.owner,
+						winner,
+						auction_info.vsbond,
+						auction_info.amount,
+						total_price,
+					));
+					return;
+				}
+
+				T::MultiCurrency::unreserve(T::InvoicingCurrency::get(), &winner, total_price);
+			}
+
+			T::MultiCurrency::unreserve(
+				auction_info.vsbond,
+				&auction_info.owner,
+				auction_info.amount,
+			);
+
+			Self::deposit_event(Event::AuctionUnsold(
+				auction_info.auction_id,
+				auction_info.owner,
+				auction_info.vsbond,
+				auction_info.amount,
+			));
+		}
+
+		/// Exchange the reserved bid of the winner with the reserved lot of the owner, as a whole.
+		#[transactional]
+		fn exchange_auction_lot(
+			auction_info: &AuctionInfo<T>,
+			winner: &AccountIdOf<T>,
+			total_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let not_repatriated = T::MultiCurrency::repatriate_reserved(
+				T::InvoicingCurrency::get(),
+				winner,
+				&auction_info.owner,
+				total_price,
+				BalanceStatus::Free,
+			)?;
+			ensure!(not_repatriated.is_zero(), Error::<T>::NotEnoughBalanceToUnreserve);
+
+			let not_repatriated = T::MultiCurrency::repatriate_reserved(
+				auction_info.vsbond,
+				&auction_info.owner,
+				winner,
+				auction_info.amount,
+				BalanceStatus::Free,
+			)?;
+			ensure!(not_repatriated.is_zero(), Error::<T>::NotEnoughBalanceToUnreserve);
+
+			Ok(())
+		}
+
 		/// Get the price(round up) needed to pay.
 		pub(crate) fn price_to_pay(quantity: BalanceOf<T>, unit_price: FixedU128) -> BalanceOf<T> {
 			let quantity: u128 = quantity.saturated_into();
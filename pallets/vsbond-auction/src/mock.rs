@@ -106,6 +106,9 @@ parameter_types! {
 	pub const InvoicingCurrency: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
 	pub const MaximumOrderInTrade: u32 = 5;
 	pub const MinimumSupply: Balance = 0;
+	pub const MaximumAuctionDuration: BlockNumber = 100;
+	pub const AntiSnipingPeriod: BlockNumber = 5;
+	pub const MaxAuctionsEndPerBlock: u32 = 2;
 }
 
 impl vsbond_auction::Config for Test {
@@ -114,6 +117,9 @@ impl vsbond_auction::Config for Test {
 	type MaximumOrderInTrade = MaximumOrderInTrade;
 	type MinimumAmount = MinimumSupply;
 	type MultiCurrency = orml_tokens::Pallet<Self>;
+	type MaximumAuctionDuration = MaximumAuctionDuration;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MaxAuctionsEndPerBlock = MaxAuctionsEndPerBlock;
 	type WeightInfo = ();
 }
 
@@ -133,6 +139,7 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 			(BRUCE, VSBOND, 100),
 			(ALICE, SPECIAL_VSBOND, 100),
 			(BRUCE, SPECIAL_VSBOND, 100),
+			(CATHI, TOKEN, 100),
 			#[cfg(feature = "runtime-benchmarks")]
 			(whitelist_caller.clone(), TOKEN, 100_000_000_000_000),
 			#[cfg(feature = "runtime-benchmarks")]
@@ -151,6 +158,7 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 
 pub(crate) const ALICE: AccountId = 1;
 pub(crate) const BRUCE: AccountId = 2;
+pub(crate) const CATHI: AccountId = 3;
 pub(crate) const TOKEN: CurrencyId = InvoicingCurrency::get();
 pub(crate) const VSBOND: CurrencyId = CurrencyId::VSBond(TokenSymbol::KSM, 3000, 13, 20);
 pub(crate) const SPECIAL_VSBOND: CurrencyId = CurrencyId::VSBond(TokenSymbol::BNC, 2001, 13, 20);
@@ -16,7 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, traits::BalanceStatus};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchError,
+	traits::{BalanceStatus, Hooks},
+};
 use orml_traits::{LockIdentifier, MultiLockableCurrency};
 
 use crate::{mock::*, *};
@@ -712,6 +716,397 @@ fn handle_special_vsbond_buy_order_should_work() {
 	});
 }
 
+#[test]
+fn create_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::English { reserve_price: 50 },
+			10
+		));
+
+		assert_eq!(Auction::auction_id(), 1);
+		assert!(Auction::user_auction_ids(ALICE).contains(&0));
+		assert!(Auction::auctions_end_at(11).contains(&0));
+
+		let auction_info = Auction::auction_info(0).unwrap();
+		assert_eq!(auction_info.start_block, 1);
+		assert_eq!(auction_info.end_block, 11);
+		assert_eq!(auction_info.current_price(1), 50);
+
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 0);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 100);
+	});
+}
+
+#[test]
+fn create_auction_with_illegal_params_should_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Auction::create_auction(
+				Some(ALICE).into(),
+				3000,
+				13,
+				20,
+				100,
+				AuctionKind::English { reserve_price: 50 },
+				0
+			),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			Auction::create_auction(
+				Some(ALICE).into(),
+				3000,
+				13,
+				20,
+				100,
+				AuctionKind::English { reserve_price: 50 },
+				101
+			),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		assert_noop!(
+			Auction::create_auction(
+				Some(ALICE).into(),
+				3000,
+				13,
+				20,
+				100,
+				AuctionKind::Dutch { start_price: 50, end_price: 60 },
+				10
+			),
+			Error::<Test>::InvalidAuctionPrice
+		);
+		assert_noop!(
+			Auction::create_auction(
+				Some(ALICE).into(),
+				3000,
+				13,
+				20,
+				100,
+				AuctionKind::English { reserve_price: 0 },
+				10
+			),
+			Error::<Test>::InvalidAuctionPrice
+		);
+		assert_noop!(
+			Auction::create_auction(
+				Some(ALICE).into(),
+				3000,
+				13,
+				20,
+				101,
+				AuctionKind::English { reserve_price: 50 },
+				10
+			),
+			Error::<Test>::NotEnoughBalanceToReserve
+		);
+	});
+}
+
+#[test]
+fn dutch_auction_price_should_descend() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::Dutch { start_price: 100, end_price: 20 },
+			10
+		));
+
+		let auction_info = Auction::auction_info(0).unwrap();
+		assert_eq!(auction_info.current_price(1), 100);
+		assert_eq!(auction_info.current_price(2), 92);
+		assert_eq!(auction_info.current_price(6), 60);
+		assert_eq!(auction_info.current_price(11), 20);
+		assert_eq!(auction_info.current_price(99), 20);
+	});
+}
+
+#[test]
+fn clinch_dutch_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::Dutch { start_price: 100, end_price: 20 },
+			10
+		));
+
+		System::set_block_number(6);
+
+		assert_noop!(
+			Auction::clinch_auction(Some(ALICE).into(), 0),
+			Error::<Test>::ForbidClinchAuctionWithinOwnership
+		);
+		assert_noop!(
+			Auction::bid_auction(Some(BRUCE).into(), 0, 100),
+			Error::<Test>::AuctionKindNotMatch
+		);
+
+		assert_ok!(Auction::clinch_auction(Some(BRUCE).into(), 0));
+
+		assert!(Auction::auction_info(0).is_none());
+		assert!(!Auction::auctions_end_at(11).contains(&0));
+		assert_eq!(Auction::user_auction_ids(ALICE).len(), 0);
+
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 0);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 0);
+		assert_eq!(Tokens::accounts(ALICE, TOKEN).free, 160);
+
+		assert_eq!(Tokens::accounts(BRUCE, VSBOND).free, 200);
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).free, 40);
+
+		assert_noop!(
+			Auction::clinch_auction(Some(BRUCE).into(), 0),
+			Error::<Test>::NotFindAuctionInfo
+		);
+	});
+}
+
+#[test]
+fn clinch_dutch_auction_after_end_should_fail() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::Dutch { start_price: 100, end_price: 20 },
+			10
+		));
+
+		System::set_block_number(11);
+		assert_noop!(
+			Auction::clinch_auction(Some(BRUCE).into(), 0),
+			Error::<Test>::AuctionAlreadyEnded
+		);
+
+		Auction::on_initialize(11);
+
+		assert!(Auction::auction_info(0).is_none());
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 100);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 0);
+		assert_eq!(Tokens::accounts(ALICE, TOKEN).free, 100);
+	});
+}
+
+#[test]
+fn english_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::English { reserve_price: 50 },
+			10
+		));
+
+		assert_noop!(
+			Auction::bid_auction(Some(ALICE).into(), 0, 60),
+			Error::<Test>::ForbidBidAuctionWithinOwnership
+		);
+		assert_noop!(
+			Auction::bid_auction(Some(BRUCE).into(), 0, 49),
+			Error::<Test>::BidNotHighEnough
+		);
+		assert_noop!(
+			Auction::clinch_auction(Some(BRUCE).into(), 0),
+			Error::<Test>::AuctionKindNotMatch
+		);
+
+		assert_ok!(Auction::bid_auction(Some(BRUCE).into(), 0, 60));
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).free, 40);
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).reserved, 60);
+
+		assert_noop!(
+			Auction::bid_auction(Some(CATHI).into(), 0, 60),
+			Error::<Test>::BidNotHighEnough
+		);
+		assert_noop!(
+			Auction::revoke_auction(Some(ALICE).into(), 0),
+			Error::<Test>::ForbidRevokeAuctionWithBid
+		);
+
+		assert_ok!(Auction::bid_auction(Some(CATHI).into(), 0, 70));
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).free, 100);
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).reserved, 0);
+		assert_eq!(Tokens::accounts(CATHI, TOKEN).free, 30);
+		assert_eq!(Tokens::accounts(CATHI, TOKEN).reserved, 70);
+
+		let auction_info = Auction::auction_info(0).unwrap();
+		assert_eq!(auction_info.best_bid, Some((CATHI, 70)));
+		assert_eq!(auction_info.current_price(2), 70);
+		assert_eq!(auction_info.end_block, 11);
+
+		System::set_block_number(11);
+		Auction::on_initialize(11);
+
+		assert!(Auction::auction_info(0).is_none());
+		assert!(!Auction::auctions_end_at(11).contains(&0));
+		assert_eq!(Auction::user_auction_ids(ALICE).len(), 0);
+
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 0);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 0);
+		assert_eq!(Tokens::accounts(ALICE, TOKEN).free, 170);
+
+		assert_eq!(Tokens::accounts(CATHI, VSBOND).free, 100);
+		assert_eq!(Tokens::accounts(CATHI, TOKEN).free, 30);
+		assert_eq!(Tokens::accounts(CATHI, TOKEN).reserved, 0);
+	});
+}
+
+#[test]
+fn bid_english_auction_near_the_end_should_extend_it() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::English { reserve_price: 50 },
+			10
+		));
+
+		System::set_block_number(9);
+		assert_ok!(Auction::bid_auction(Some(BRUCE).into(), 0, 60));
+
+		let auction_info = Auction::auction_info(0).unwrap();
+		assert_eq!(auction_info.end_block, 14);
+		assert!(!Auction::auctions_end_at(11).contains(&0));
+		assert!(Auction::auctions_end_at(14).contains(&0));
+
+		System::set_block_number(11);
+		Auction::on_initialize(11);
+		assert!(Auction::auction_info(0).is_some());
+
+		System::set_block_number(14);
+		assert_noop!(
+			Auction::bid_auction(Some(CATHI).into(), 0, 70),
+			Error::<Test>::AuctionAlreadyEnded
+		);
+		Auction::on_initialize(14);
+
+		assert!(Auction::auction_info(0).is_none());
+		assert_eq!(Tokens::accounts(ALICE, TOKEN).free, 160);
+		assert_eq!(Tokens::accounts(BRUCE, VSBOND).free, 200);
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).free, 40);
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).reserved, 0);
+	});
+}
+
+#[test]
+fn best_bidder_should_raise_own_bid_with_the_increase() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::English { reserve_price: 50 },
+			10
+		));
+
+		assert_ok!(Auction::bid_auction(Some(BRUCE).into(), 0, 60));
+		assert_noop!(
+			Auction::bid_auction(Some(BRUCE).into(), 0, 101),
+			Error::<Test>::DontHaveEnoughToPay
+		);
+
+		// Only 30 more are needed, though 90 are more than the free balance.
+		assert_ok!(Auction::bid_auction(Some(BRUCE).into(), 0, 90));
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).free, 10);
+		assert_eq!(Tokens::accounts(BRUCE, TOKEN).reserved, 90);
+		assert_eq!(Auction::auction_info(0).unwrap().best_bid, Some((BRUCE, 90)));
+	});
+}
+
+#[test]
+fn english_auction_without_bid_should_be_unsold() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::English { reserve_price: 50 },
+			10
+		));
+
+		System::set_block_number(11);
+		Auction::on_initialize(11);
+
+		assert!(Auction::auction_info(0).is_none());
+		assert_eq!(Auction::user_auction_ids(ALICE).len(), 0);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 100);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 0);
+	});
+}
+
+#[test]
+fn revoke_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Auction::create_auction(
+			Some(ALICE).into(),
+			3000,
+			13,
+			20,
+			100,
+			AuctionKind::Dutch { start_price: 100, end_price: 20 },
+			10
+		));
+
+		assert_noop!(
+			Auction::revoke_auction(Some(BRUCE).into(), 0),
+			Error::<Test>::ForbidRevokeAuctionWithoutOwnership
+		);
+
+		assert_ok!(Auction::revoke_auction(Some(ALICE).into(), 0));
+
+		assert!(Auction::auction_info(0).is_none());
+		assert_eq!(Auction::user_auction_ids(ALICE).len(), 0);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 100);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 0);
+
+		assert!(!Auction::auctions_end_at(11).contains(&0));
+	});
+}
+
 // Test Utilities
 #[test]
 fn check_price_to_pay() {
@@ -723,3 +1118,45 @@ fn check_price_to_pay() {
 		assert_eq!(Auction::price_to_pay(*quantity, unit_price), *price_to_pay);
 	}
 }
+
+#[test]
+fn auctions_ending_beyond_the_block_limit_should_be_settled_later() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for _ in 0..3 {
+			assert_ok!(Auction::create_auction(
+				Some(ALICE).into(),
+				3000,
+				13,
+				20,
+				30,
+				AuctionKind::English { reserve_price: 50 },
+				10
+			));
+		}
+
+		assert_eq!(Auction::auctions_end_at(11).into_inner(), vec![0, 1]);
+		assert_eq!(Auction::auctions_end_at(12).into_inner(), vec![2]);
+		assert_eq!(Auction::auction_info(2).unwrap().end_block, 12);
+
+		System::set_block_number(11);
+		Auction::on_initialize(11);
+
+		assert!(Auction::auction_info(0).is_none());
+		assert!(Auction::auction_info(1).is_none());
+		assert!(Auction::auction_info(2).is_some());
+		assert_ok!(Auction::bid_auction(Some(BRUCE).into(), 2, 60));
+
+		System::set_block_number(16);
+		Auction::on_initialize(12);
+		assert!(Auction::auction_info(2).is_some());
+		Auction::on_initialize(16);
+
+		assert!(Auction::auction_info(2).is_none());
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).free, 70);
+		assert_eq!(Tokens::accounts(ALICE, VSBOND).reserved, 0);
+		assert_eq!(Tokens::accounts(ALICE, TOKEN).free, 160);
+		assert_eq!(Tokens::accounts(BRUCE, VSBOND).free, 130);
+	});
+}
@@ -31,9 +31,15 @@ pub trait WeightInfo {
 	fn revoke_order() -> Weight;
 	fn clinch_order() -> Weight;
 	fn partial_clinch_order() -> Weight;
+	fn create_auction() -> Weight;
+	fn revoke_auction() -> Weight;
+	fn clinch_auction() -> Weight;
+	fn bid_auction() -> Weight;
+	fn on_initialize(n: u32) -> Weight;
 }
 
-// For backwards compatibility and tests
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl WeightInfo for () {
 	fn create_order() -> Weight {
 		(50_000_000 as Weight)
@@ -50,4 +56,37 @@ impl WeightInfo for () {
 	fn partial_clinch_order() -> Weight {
 		(50_000_000 as Weight)
 	}
+
+	fn create_auction() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+
+	fn revoke_auction() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+
+	fn clinch_auction() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+
+	fn bid_auction() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+
+	fn on_initialize(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((200_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
 }
@@ -20,13 +20,22 @@
 
 use codec::Codec;
 use sp_api::decl_runtime_apis;
+use sp_runtime::Permill;
+use sp_std::vec::Vec;
 
 decl_runtime_apis! {
-	pub trait VtokenMintPriceApi<AssetId, VtokenMintPrice> where
-		AssetId: Codec,
-		VtokenMintPrice: Codec
+	pub trait VtokenMintPriceApi<CurrencyId, BlockNumber, Balance> where
+		CurrencyId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec
 	{
-		/// get current vtoken mint rate
-		fn get_vtoken_mint_rate(asset_id: AssetId) -> VtokenMintPrice;
+		/// get current vtoken mint rate, as `(token pool, vtoken pool)`
+		fn get_vtoken_mint_rate(asset_id: CurrencyId) -> (Balance, Balance);
+
+		/// get the rates snapshotted at the end of the eras, as `(block, token pool, vtoken pool)`
+		fn get_vtoken_mint_rate_history(asset_id: CurrencyId) -> Vec<(BlockNumber, Balance, Balance)>;
+
+		/// get the yield of the vtoken over the rate history, annualized
+		fn get_annualized_yield(asset_id: CurrencyId) -> Permill;
 	}
 }
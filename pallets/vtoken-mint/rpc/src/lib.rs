@@ -21,9 +21,11 @@ use std::{marker::PhantomData, sync::Arc};
 use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result as JsonRpcResult};
 use jsonrpc_derive::rpc;
+use node_primitives::Balance;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, sp_std::convert::TryInto, traits::Block as BlockT, Permill};
 pub use vtoken_mint_rpc_runtime_api::{
 	self as runtime_api, VtokenMintPriceApi as VtokenMintRateRuntimeApi,
 };
@@ -43,37 +45,108 @@ impl<C, Block> VtokenMint<C, Block> {
 }
 
 #[rpc]
-pub trait VtokenMintPriceApi<BlockHash, AssetId, VtokenMintPrice> {
-	/// rpc method for getting current vtoken mint rate
+pub trait VtokenMintPriceApi<BlockHash, CurrencyId, BlockNumber> {
+	/// rpc method for getting current vtoken mint rate, as `(token pool, vtoken pool)`
 	#[rpc(name = "vtokenmint_getVtokenMintRate")]
 	fn get_vtoken_mint_rate(
 		&self,
-		asset_id: AssetId,
+		asset_id: CurrencyId,
 		at: Option<BlockHash>,
-	) -> JsonRpcResult<VtokenMintPrice>;
+	) -> JsonRpcResult<(NumberOrHex, NumberOrHex)>;
+
+	/// rpc method for getting the rates snapshotted at the end of the eras, as
+	/// `(block, token pool, vtoken pool)`
+	#[rpc(name = "vtokenmint_getVtokenMintRateHistory")]
+	fn get_vtoken_mint_rate_history(
+		&self,
+		asset_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<Vec<(BlockNumber, NumberOrHex, NumberOrHex)>>;
+
+	/// rpc method for getting the annualized yield of the vtoken over the rate history
+	#[rpc(name = "vtokenmint_getAnnualizedYield")]
+	fn get_annualized_yield(
+		&self,
+		asset_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> JsonRpcResult<Permill>;
+}
+
+impl<C, Block> VtokenMint<C, Block> {
+	fn convert_rpc_params(value: Balance) -> Result<NumberOrHex, RpcError> {
+		value.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", value),
+			data: None,
+		})
+	}
+
+	fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+		RpcError {
+			code: ErrorCode::InternalError,
+			message: message.to_owned(),
+			data: Some(format!("{:?}", e).into()),
+		}
+	}
 }
 
-impl<C, Block, AssetId, VtokenMintPrice>
-	VtokenMintPriceApi<<Block as BlockT>::Hash, AssetId, VtokenMintPrice> for VtokenMint<C, Block>
+impl<C, Block, CurrencyId, BlockNumber>
+	VtokenMintPriceApi<<Block as BlockT>::Hash, CurrencyId, BlockNumber> for VtokenMint<C, Block>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: VtokenMintRateRuntimeApi<Block, AssetId, VtokenMintPrice>,
-	AssetId: Codec,
-	VtokenMintPrice: Codec,
+	C::Api: VtokenMintRateRuntimeApi<Block, CurrencyId, BlockNumber, Balance>,
+	CurrencyId: Codec,
+	BlockNumber: Codec,
 {
 	fn get_vtoken_mint_rate(
 		&self,
-		asset_id: AssetId,
+		asset_id: CurrencyId,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> JsonRpcResult<VtokenMintPrice> {
+	) -> JsonRpcResult<(NumberOrHex, NumberOrHex)> {
 		let vtoken_mint_rpc_api = self.client.runtime_api();
 		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
-		vtoken_mint_rpc_api.get_vtoken_mint_rate(&at, asset_id).map_err(|e| RpcError {
-			code: ErrorCode::InternalError,
-			message: "Failed to get current vtoken mint rate.".to_owned(),
-			data: Some(format!("{:?}", e).into()),
-		})
+		match vtoken_mint_rpc_api.get_vtoken_mint_rate(&at, asset_id) {
+			Ok((token_pool, vtoken_pool)) =>
+				Ok((Self::convert_rpc_params(token_pool)?, Self::convert_rpc_params(vtoken_pool)?)),
+			Err(e) => Err(Self::runtime_error("Failed to get current vtoken mint rate.", e)),
+		}
+	}
+
+	fn get_vtoken_mint_rate_history(
+		&self,
+		asset_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<Vec<(BlockNumber, NumberOrHex, NumberOrHex)>> {
+		let vtoken_mint_rpc_api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		match vtoken_mint_rpc_api.get_vtoken_mint_rate_history(&at, asset_id) {
+			Ok(history) => history
+				.into_iter()
+				.map(|(block_number, token_pool, vtoken_pool)| {
+					Ok((
+						block_number,
+						Self::convert_rpc_params(token_pool)?,
+						Self::convert_rpc_params(vtoken_pool)?,
+					))
+				})
+				.collect(),
+			Err(e) => Err(Self::runtime_error("Failed to get vtoken mint rate history.", e)),
+		}
+	}
+
+	fn get_annualized_yield(
+		&self,
+		asset_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> JsonRpcResult<Permill> {
+		let vtoken_mint_rpc_api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		vtoken_mint_rpc_api
+			.get_annualized_yield(&at, asset_id)
+			.map_err(|e| Self::runtime_error("Failed to get annualized yield of vtoken.", e))
 	}
 }
@@ -18,7 +18,8 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
 use frame_system::RawOrigin;
 use node_primitives::{CurrencyId, TokenSymbol};
 use sp_runtime::traits::UniqueSaturatedFrom;
@@ -57,9 +58,111 @@ benchmarks! {
 		let vtoken_amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
 	}: _(RawOrigin::Signed(caller), token_id, vtoken_amount)
 
+	cancel_redeem {
+		VtokenMint::<T>::expand_mint_pool(CurrencyId::Token(TokenSymbol::KSM), BalanceOf::<T>::unique_saturated_from(100u32 as u128))?;
+		VtokenMint::<T>::expand_mint_pool(CurrencyId::VToken(TokenSymbol::KSM), BalanceOf::<T>::unique_saturated_from(200u32 as u128))?;
+		StakingLedgers::<T>::insert(
+			T::StakingDerivativeIndex::get(),
+			StakingLedger { active: BalanceOf::<T>::unique_saturated_from(100u32 as u128), unlocking: vec![] },
+		);
+
+		let caller: T::AccountId = whitelisted_caller();
+		let token_id = CurrencyId::Token(TokenSymbol::KSM);
+		let vtoken_amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
+		VtokenMint::<T>::set_token_staking_lock_period(RawOrigin::Root.into(), token_id, T::BlockNumber::from(100u32))?;
+		VtokenMint::<T>::redeem(RawOrigin::Signed(caller.clone()).into(), token_id, vtoken_amount)?;
+	}: _(RawOrigin::Signed(caller), Zero::zero())
+
+	claim_redeem {
+		VtokenMint::<T>::expand_mint_pool(CurrencyId::Token(TokenSymbol::KSM), BalanceOf::<T>::unique_saturated_from(100u32 as u128))?;
+		VtokenMint::<T>::expand_mint_pool(CurrencyId::VToken(TokenSymbol::KSM), BalanceOf::<T>::unique_saturated_from(200u32 as u128))?;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let token_id = CurrencyId::Token(TokenSymbol::KSM);
+		let vtoken_amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
+		VtokenMint::<T>::redeem(RawOrigin::Signed(caller.clone()).into(), token_id, vtoken_amount)?;
+		T::MultiCurrency::deposit(token_id, &VtokenMint::<T>::redeem_account(), vtoken_amount)?;
+		frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(100u32));
+	}: _(RawOrigin::Signed(caller), Zero::zero())
+
+	transfer_redeem_receipt {
+		VtokenMint::<T>::expand_mint_pool(CurrencyId::Token(TokenSymbol::KSM), BalanceOf::<T>::unique_saturated_from(100u32 as u128))?;
+		VtokenMint::<T>::expand_mint_pool(CurrencyId::VToken(TokenSymbol::KSM), BalanceOf::<T>::unique_saturated_from(200u32 as u128))?;
+
+		let caller: T::AccountId = whitelisted_caller();
+		let dest: T::AccountId = account("dest", 0, 0);
+		let token_id = CurrencyId::Token(TokenSymbol::KSM);
+		let vtoken_amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
+		VtokenMint::<T>::redeem(RawOrigin::Signed(caller.clone()).into(), token_id, vtoken_amount)?;
+	}: _(RawOrigin::Signed(caller), dest, Zero::zero())
+
+	withdraw_unbonded {
+		StakingLedgers::<T>::insert(
+			T::StakingDerivativeIndex::get(),
+			StakingLedger {
+				active: BalanceOf::<T>::unique_saturated_from(100u32 as u128),
+				unlocking: vec![(T::BlockNumber::from(0u32), BalanceOf::<T>::unique_saturated_from(10u32 as u128))],
+			},
+		);
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::withdraw_unbonded { num_slashing_spans: 0 };
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	nominate {
+		let n in 1 .. 16;
+
+		StakingLedgers::<T>::insert(T::StakingDerivativeIndex::get(), StakingLedgerOf::<T>::default());
+		let targets: Vec<T::AccountId> = (0 .. n).map(|i| account("validator", i, 0)).collect();
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::nominate { targets };
+	}: { call.dispatch_bypass_filter(origin)? }
+
+	report_staking_rewards {
+		let active = BalanceOf::<T>::unique_saturated_from(1_000_000u32 as u128);
+		StakingLedgers::<T>::insert(
+			T::StakingDerivativeIndex::get(),
+			StakingLedger { active, unlocking: vec![] },
+		);
+		let origin = T::ControlOrigin::successful_origin();
+		let call = Call::<T>::report_staking_rewards {
+			rewards: T::MaxStakingRewardsPerEra::get() * active,
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+
 	on_initialize {
+		let n in 0 .. T::MaxRedeemUnlocksPerBlock::get();
+
+		let token_id = CurrencyId::Token(TokenSymbol::KSM);
 		let block_num = T::BlockNumber::from(100u32);
+		let amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
+		for i in 0 .. n {
+			let redeemer: T::AccountId = account("redeemer", i, 0);
+			VtokenMint::<T>::issue_redeem_receipt(&redeemer, token_id, T::BlockNumber::from(1u32), amount, amount, block_num)?;
+			T::MultiCurrency::deposit(token_id, &VtokenMint::<T>::redeem_account(), amount)?;
+		}
 	}:{VtokenMint::<T>::on_initialize(block_num);}
+
+	settle_pending_mints {
+		let n in 0 .. 2;
+
+		let amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
+		for token_id in [CurrencyId::Token(TokenSymbol::KSM), CurrencyId::Token(TokenSymbol::DOT)].iter().take(n as usize) {
+			T::MultiCurrency::deposit(*token_id, &VtokenMint::<T>::account_id(), amount)?;
+			PendingMints::<T>::insert(token_id, amount);
+		}
+		let era_end = T::EraLength::get();
+	}:{VtokenMint::<T>::on_initialize(era_end);}
+
+	snapshot_rates {
+		let n in 0 .. 2;
+
+		let amount = BalanceOf::<T>::unique_saturated_from(10u32 as u128);
+		for symbol in [TokenSymbol::KSM, TokenSymbol::DOT].iter().take(n as usize) {
+			VtokenMint::<T>::expand_mint_pool(CurrencyId::Token(*symbol), amount)?;
+			VtokenMint::<T>::expand_mint_pool(CurrencyId::VToken(*symbol), amount)?;
+		}
+		let era_end = T::EraLength::get();
+	}:{VtokenMint::<T>::snapshot_rates(era_end);}
 }
 
 impl_benchmark_test_suite!(
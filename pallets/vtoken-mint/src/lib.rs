@@ -19,13 +19,13 @@
 
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec, vec::Vec};
 use core::marker::PhantomData;
 
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Hooks, IsType},
-	transactional,
+	traits::{Hooks, IsType, StorageVersion},
+	transactional, PalletId,
 };
 use frame_system::{
 	ensure_root, ensure_signed,
@@ -34,14 +34,16 @@ use frame_system::{
 use node_primitives::{CurrencyId, CurrencyIdExt, MinterRewardExt, VtokenMintExt};
 use orml_traits::{
 	currency::TransferAll, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-	MultiReservableCurrency,
+	MultiReservableCurrency, XcmTransfer,
 };
 pub use pallet::*;
 use sp_runtime::{
-	traits::{CheckedSub, Saturating, Zero},
-	DispatchResult,
+	traits::{AccountIdConversion, CheckedDiv, Saturating, UniqueSaturatedInto, Zero},
+	DispatchResult, FixedPointNumber, FixedU128, MultiAddress, Permill,
 };
 pub use weights::WeightInfo;
+use xcm::latest::prelude::*;
+use xcm_support::*;
 
 mod mock;
 mod tests;
@@ -57,12 +59,138 @@ type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<
 	<T as frame_system::Config>::AccountId,
 >>::CurrencyId;
 
+macro_rules! use_relay {
+    ({ $( $code:tt )* }) => {
+        if T::RelayNetwork::get() == NetworkId::Polkadot {
+            use polkadot::RelaychainCall;
+
+			$( $code )*
+        } else if T::RelayNetwork::get() == NetworkId::Kusama {
+            use kusama::RelaychainCall;
+
+			$( $code )*
+        } else {
+            unreachable!()
+        }
+    }
+}
+
+/// The ledger of the derivative account staking on the relay chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct StakingLedger<BlockNumber, Balance> {
+	/// The amount bonded and not being unbonded.
+	pub active: Balance,
+	/// The amounts being unbonded, with the blocks from which they can be withdrawn.
+	pub unlocking: Vec<(BlockNumber, Balance)>,
+}
+
+pub type StakingLedgerOf<T> = StakingLedger<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+/// The pending redeem of a receipt, which is an NFT transferable along with the right to claim
+/// the redeem at unlock.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RedeemReceipt<CurrencyId, BlockNumber, Balance> {
+	/// The token to claim.
+	pub token_id: CurrencyId,
+	/// The block at which the redeem started.
+	pub redeemed_at: BlockNumber,
+	/// The block at which the redeem unlocks, and the relay chain tokens are unbonded.
+	pub unlock_at: BlockNumber,
+	/// The amount of the token to claim.
+	pub amount: Balance,
+	/// The part of the amount unbonded on the relay chain, which is paid from the redeem account
+	/// once sent back. The rest was never staked, and is minted at unlock.
+	pub unbonded: Balance,
+}
+
+pub type RedeemReceiptOf<T> =
+	RedeemReceipt<CurrencyIdOf<T>, <T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+pub type RedeemReceiptIdOf<T> = <T as orml_nft::Config>::TokenId;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub mod migration {
+	use super::*;
+
+	/// The maximum number of the pending redeems migrated in a block.
+	pub const MAX_REDEEMS_MIGRATED_PER_BLOCK: u32 = 64;
+
+	/// Issue the receipts of the pending redeems, and queue them for unlocking, so that they are
+	/// no longer found by scanning the whole `RedeemRecord` in every block.
+	///
+	/// At most `MAX_REDEEMS_MIGRATED_PER_BLOCK` redeems are migrated in a block, and the rest are
+	/// left to the following blocks. The redeems failing to be migrated are kept and retried.
+	/// The storage version is bumped once `RedeemRecord` is drained.
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut budget = MAX_REDEEMS_MIGRATED_PER_BLOCK as usize;
+		let batch: Vec<_> = RedeemRecord::<T>::iter().take(budget).collect();
+		let mut reads: Weight = 2;
+		let mut writes: Weight = 0;
+		for (who, token_id, records) in batch {
+			let redeem_period = StakingLockPeriod::<T>::get(token_id);
+			let mut remaining = Vec::new();
+			for (redeemed_at, amount) in records {
+				if budget == 0 {
+					remaining.push((redeemed_at, amount));
+					continue;
+				}
+				budget -= 1;
+
+				// The overdue redeems are unlocked in the next block. None of them was unbonded
+				// on the relay chain, so they are all minted at unlock as before.
+				let unlock_at = redeemed_at.saturating_add(redeem_period).max(now + 1u32.into());
+				if let Err(e) = Pallet::<T>::issue_redeem_receipt(
+					&who,
+					token_id,
+					redeemed_at,
+					amount,
+					Zero::zero(),
+					unlock_at,
+				) {
+					log::error!(
+						"vtoken-mint migration: failed to issue the redeem receipt of {:?}: {:?}",
+						who,
+						e
+					);
+					remaining.push((redeemed_at, amount));
+				}
+				reads = reads.saturating_add(4);
+				writes = writes.saturating_add(6);
+			}
+
+			if remaining.is_empty() {
+				RedeemRecord::<T>::remove(&who, token_id);
+			} else {
+				RedeemRecord::<T>::insert(&who, token_id, remaining);
+			}
+			reads = reads.saturating_add(2);
+			writes = writes.saturating_add(1);
+		}
+
+		if RedeemRecord::<T>::iter_keys().next().is_none() {
+			STORAGE_VERSION.put::<Pallet<T>>();
+			log::info!("vtoken-mint migration: all the pending redeems are migrated to v1");
+			writes = writes.saturating_add(1);
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config:
+		frame_system::Config + orml_nft::Config<ClassData = (), TokenData = ()>
+	{
 		/// A handler to manipulate assets module.
 		type MultiCurrency: TransferAll<Self::AccountId>
 			+ MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId>
@@ -80,6 +208,78 @@ pub mod pallet {
 			Self::BlockNumber,
 		>;
 
+		/// The vtoken-mint's pallet id, whose account keeps the pending mints.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The number of blocks in an era, at the end of which the pending mints are staked.
+		#[pallet::constant]
+		type EraLength: Get<Self::BlockNumber>;
+
+		/// The origin which manages the staking on the relay chain.
+		type ControlOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The relay chain token, which is staked on the relay chain when minted.
+		#[pallet::constant]
+		type RelayChainToken: Get<CurrencyIdOf<Self>>;
+
+		#[pallet::constant]
+		type RelayNetwork: Get<NetworkId>;
+
+		type BifrostXcmExecutor: BifrostXcmExecutor;
+
+		/// The interface to Cross-chain transfer.
+		type XcmTransfer: XcmTransfer<Self::AccountId, BalanceOf<Self>, CurrencyIdOf<Self>>;
+
+		/// The index of the derivative account of the sovereign account, which stakes on the
+		/// relay chain.
+		#[pallet::constant]
+		type StakingDerivativeIndex: Get<u16>;
+
+		/// The location of the derivative account, which the minted tokens are sent to.
+		#[pallet::constant]
+		type StakingSubAccountLocation: Get<MultiLocation>;
+
+		/// Xcm weight
+		#[pallet::constant]
+		type BaseXcmWeight: Get<u64>;
+
+		/// The weight of a staking call on the relay chain.
+		#[pallet::constant]
+		type StakingCallWeight: Get<u64>;
+
+		/// The id of this parachain, which the unbonded tokens are sent back to.
+		#[pallet::constant]
+		type SelfParaId: Get<u32>;
+
+		/// The maximum staking rewards reported in an era, as a fraction of the active amount.
+		#[pallet::constant]
+		type MaxStakingRewardsPerEra: Get<Permill>;
+
+		/// The maximum number of the rates snapshotted for a token. The oldest snapshot is dropped
+		/// when it's exceeded.
+		#[pallet::constant]
+		type MaxRateHistory: Get<u32>;
+
+		/// The number of blocks in a year, to annualize the yield.
+		#[pallet::constant]
+		type BlocksPerYear: Get<Self::BlockNumber>;
+
+		/// The fee charged from the tokens of a cancelled redeem, which is left in the mint pool.
+		#[pallet::constant]
+		type CancelRedeemFee: Get<Permill>;
+
+		/// The fee charged from the tokens of a redeem paid out by the pending mints, which is
+		/// left in the mint pool. It keeps minting and redeeming in the same era from farming the
+		/// minter rewards.
+		#[pallet::constant]
+		type InstantRedeemFee: Get<Permill>;
+
+		/// The maximum number of redeems unlocked in a block. The redeems beyond it are queued in
+		/// the following blocks.
+		#[pallet::constant]
+		type MaxRedeemUnlocksPerBlock: Get<u32>;
+
 		/// Set default weight.
 		type WeightInfo: WeightInfo;
 	}
@@ -90,9 +290,17 @@ pub mod pallet {
 	pub(crate) type MintPool<T: Config> =
 		StorageMap<_, Blake2_128Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
 
-	/// Record when and how much balance user want to redeem.
+	/// The tokens minted in the current era. They pay out the redeems instantly, until they are
+	/// staked at the end of the era.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_mint)]
+	pub(crate) type PendingMints<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The pending redeems before the receipts were introduced: (redeemed at, amount)
+	///
+	/// It's drained by the migration to v1.
 	#[pallet::storage]
-	#[pallet::getter(fn redeem_record)]
 	pub(crate) type RedeemRecord<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
@@ -103,6 +311,52 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The receipts of the redeems to unlock at a block.
+	#[pallet::storage]
+	#[pallet::getter(fn redeem_unlocks)]
+	pub(crate) type RedeemUnlocks<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<RedeemReceiptIdOf<T>, T::MaxRedeemUnlocksPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The pending redeems, by the id of the receipt. The owner of the receipt is kept by the
+	/// NFT of the id.
+	#[pallet::storage]
+	#[pallet::getter(fn redeem_receipt)]
+	pub(crate) type RedeemReceipts<T: Config> =
+		StorageMap<_, Twox64Concat, RedeemReceiptIdOf<T>, RedeemReceiptOf<T>, OptionQuery>;
+
+	/// The NFT class of the redeem receipts, created by the first redeem.
+	#[pallet::storage]
+	#[pallet::getter(fn redeem_receipt_class)]
+	pub(crate) type RedeemReceiptClass<T: Config> = StorageValue<_, T::ClassId, OptionQuery>;
+
+	/// The ledgers of the derivative accounts staking on the relay chain.
+	#[pallet::storage]
+	#[pallet::getter(fn staking_ledger)]
+	pub(crate) type StakingLedgers<T: Config> =
+		StorageMap<_, Twox64Concat, u16, StakingLedgerOf<T>, OptionQuery>;
+
+	/// The last eras in which the staking rewards of the derivative accounts were reported.
+	#[pallet::storage]
+	#[pallet::getter(fn staking_rewards_reported_era)]
+	pub(crate) type StakingRewardsReportedEra<T: Config> =
+		StorageMap<_, Twox64Concat, u16, T::BlockNumber, OptionQuery>;
+
+	/// The rates snapshotted at the end of the eras: (block, token pool, vtoken pool)
+	#[pallet::storage]
+	#[pallet::getter(fn rate_history)]
+	pub(crate) type RateHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		BoundedVec<(T::BlockNumber, BalanceOf<T>, BalanceOf<T>), T::MaxRateHistory>,
+		ValueQuery,
+	>;
+
 	/// List lock period while staking.
 	#[pallet::storage]
 	#[pallet::getter(fn staking_lock_period)]
@@ -114,6 +368,32 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		Minted(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
 		RedeemStarted(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, T::BlockNumber),
+		/// [redeemer, token_id, amount]
+		RedeemUnlocked(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// The redeem is paid out by the pending mints. [redeemer, token_id, amount, fee]
+		RedeemedInstantly(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// The redeem waits for the staking lock period. [redeemer, token_id, amount]
+		RedeemQueued(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+		/// [owner, receipt_id, token_id, amount]
+		RedeemReceiptIssued(T::AccountId, RedeemReceiptIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>),
+		/// [from, to, receipt_id]
+		RedeemReceiptTransferred(T::AccountId, T::AccountId, RedeemReceiptIdOf<T>),
+		/// [redeemer, token_id, token_amount, vtoken_amount, fee]
+		RedeemCancelled(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+		/// The pending mints are staked. [token_id, amount]
+		PendingMintSettled(CurrencyIdOf<T>, BalanceOf<T>),
+		/// [derivative_index, amount]
+		Bonded(u16, BalanceOf<T>),
+		/// [derivative_index, amount]
+		Unbonded(u16, BalanceOf<T>),
+		/// [derivative_index, amount]
+		Rebonded(u16, BalanceOf<T>),
+		/// [derivative_index, amount]
+		Withdrawn(u16, BalanceOf<T>),
+		/// [derivative_index, targets]
+		Nominated(u16, Vec<T::AccountId>),
+		/// [derivative_index, rewards]
+		StakingRewardsReported(u16, BalanceOf<T>),
 		UpdateVtokenPoolSuccess,
 	}
 
@@ -131,9 +411,28 @@ pub mod pallet {
 		NotEnoughVtokenPool,
 		/// Calculation Overflow
 		CalculationOverflow,
+		/// The redeem has not been unlocked.
+		RedeemNotUnlocked,
+		/// Crosschain xcm failed
+		XcmFailed,
+		/// The location of the staking account is not an account on the relay chain.
+		InvalidStakingAccount,
+		/// The derivative account has not bonded on the relay chain.
+		NotBonded,
+		/// The redeem has been unlocked, and can't be cancelled.
+		RedeemAlreadyUnlocked,
+		/// The redeem receipt doesn't exist, or isn't owned by the sender.
+		RedeemReceiptNotExist,
+		/// The account receiving the unbonded tokens is not a 32 bytes account.
+		InvalidRedeemAccount,
+		/// The staking rewards exceed the maximum of an era.
+		ExceedMaxStakingRewards,
+		/// The staking rewards of the era have been reported.
+		StakingRewardsAlreadyReported,
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::call]
@@ -216,7 +515,11 @@ pub mod pallet {
 
 			let vtokens_buy = token_amount.saturating_mul(vtoken_pool) / token_pool;
 
-			T::MultiCurrency::withdraw(token_id, &minter, token_amount)?;
+			// Keep the tokens until the end of the era, to pay out the redeems.
+			T::MultiCurrency::transfer(token_id, &minter, &Self::account_id(), token_amount)?;
+			PendingMints::<T>::mutate(token_id, |pending| {
+				*pending = pending.saturating_add(token_amount);
+			});
 			T::MultiCurrency::deposit(vtoken_id, &minter, vtokens_buy)?;
 
 			// Alter mint pool
@@ -280,16 +583,271 @@ pub mod pallet {
 			Self::reduce_mint_pool(token_id, tokens_redeem)?;
 			Self::reduce_mint_pool(vtoken_id, vtoken_amount)?;
 
-			Self::update_redeem_record(token_id, &redeemer, tokens_redeem);
-
 			let current_block = <frame_system::Pallet<T>>::block_number();
 			Self::deposit_event(Event::RedeemStarted(
-				redeemer,
+				redeemer.clone(),
 				vtoken_id,
 				vtoken_amount,
 				current_block,
 			));
 
+			// Net the redeem against the pending mints first. The fee is left in the pending mints
+			// and the mint pool.
+			let instant = tokens_redeem.min(Self::pending_mint(token_id));
+			if !instant.is_zero() {
+				let fee = T::InstantRedeemFee::get() * instant;
+				let paid = instant.saturating_sub(fee);
+				T::MultiCurrency::transfer(token_id, &Self::account_id(), &redeemer, paid)?;
+				PendingMints::<T>::mutate(token_id, |pending| {
+					*pending = pending.saturating_sub(paid);
+				});
+				Self::expand_mint_pool(token_id, fee)?;
+
+				Self::deposit_event(Event::RedeemedInstantly(
+					redeemer.clone(),
+					token_id,
+					paid,
+					fee,
+				));
+			}
+
+			let queued = tokens_redeem.saturating_sub(instant);
+			if !queued.is_zero() {
+				let redeem_period = Self::staking_lock_period(token_id).max(1u32.into());
+				let unlock_at = current_block.saturating_add(redeem_period);
+				let unbonded = if token_id == T::RelayChainToken::get() {
+					Self::unbond(queued, unlock_at)?
+				} else {
+					Zero::zero()
+				};
+				Self::issue_redeem_receipt(
+					&redeemer,
+					token_id,
+					current_block,
+					queued,
+					unbonded,
+					unlock_at,
+				)?;
+
+				Self::deposit_event(Event::RedeemQueued(redeemer, token_id, queued));
+			}
+
+			Ok(())
+		}
+
+		/// Cancel a pending redeem, and get the vtokens back at the current exchange rate. The fee
+		/// is left in the mint pool.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// owner of the receipt.
+		#[pallet::weight(T::WeightInfo::cancel_redeem())]
+		#[transactional]
+		pub fn cancel_redeem(
+			origin: OriginFor<T>,
+			receipt_id: RedeemReceiptIdOf<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let receipt = Self::owned_redeem_receipt(&owner, receipt_id)
+				.ok_or(Error::<T>::RedeemReceiptNotExist)?;
+			let RedeemReceipt { token_id, unlock_at, amount: token_amount, unbonded, .. } = receipt;
+			let vtoken_id = token_id.to_vtoken().map_err(|_| Error::<T>::NotSupportTokenType)?;
+
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			ensure!(current_block < unlock_at, Error::<T>::RedeemAlreadyUnlocked);
+
+			// Total amount of tokens.
+			let token_pool = Self::get_mint_pool(token_id);
+			// Total amount of vtokens.
+			let vtoken_pool = Self::get_mint_pool(vtoken_id);
+			ensure!(!token_pool.is_zero() && !vtoken_pool.is_zero(), Error::<T>::EmptyVtokenPool);
+
+			let fee = T::CancelRedeemFee::get() * token_amount;
+			let vtoken_amount =
+				token_amount.saturating_sub(fee).saturating_mul(vtoken_pool) / token_pool;
+
+			Self::burn_redeem_receipt(&owner, receipt_id)?;
+			RedeemUnlocks::<T>::mutate(unlock_at, |unlocks| {
+				unlocks.retain(|queued| *queued != receipt_id);
+			});
+
+			// The tokens unbonded for the redeem are rebonded. Those already withdrawn to the
+			// redeem account are staked again along with the pending mints.
+			let rebonded = Self::rebond(unbonded)?;
+			let withdrawn = unbonded.saturating_sub(rebonded);
+			if !withdrawn.is_zero() {
+				T::MultiCurrency::transfer(
+					token_id,
+					&Self::redeem_account(),
+					&Self::account_id(),
+					withdrawn,
+				)?;
+				PendingMints::<T>::mutate(token_id, |pending| {
+					*pending = pending.saturating_add(withdrawn);
+				});
+			}
+
+			T::MultiCurrency::deposit(vtoken_id, &owner, vtoken_amount)?;
+
+			// Alter mint pool
+			Self::expand_mint_pool(token_id, token_amount)?;
+			Self::expand_mint_pool(vtoken_id, vtoken_amount)?;
+
+			Self::deposit_event(Event::RedeemCancelled(
+				owner,
+				token_id,
+				token_amount,
+				vtoken_amount,
+				fee,
+			));
+
+			Ok(())
+		}
+
+		/// Claim an unlocked redeem which was not unlocked automatically, because the unbonded
+		/// tokens had not been sent back.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// owner of the receipt.
+		#[pallet::weight(T::WeightInfo::claim_redeem())]
+		#[transactional]
+		pub fn claim_redeem(
+			origin: OriginFor<T>,
+			receipt_id: RedeemReceiptIdOf<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let receipt = Self::owned_redeem_receipt(&owner, receipt_id)
+				.ok_or(Error::<T>::RedeemReceiptNotExist)?;
+
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			ensure!(current_block >= receipt.unlock_at, Error::<T>::RedeemNotUnlocked);
+
+			Self::unlock_redeem(receipt_id)
+		}
+
+		/// Transfer the receipt of a pending redeem, along with the right to claim the redeem.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// owner of the receipt.
+		#[pallet::weight(T::WeightInfo::transfer_redeem_receipt())]
+		#[transactional]
+		pub fn transfer_redeem_receipt(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			receipt_id: RedeemReceiptIdOf<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(
+				Self::owned_redeem_receipt(&owner, receipt_id).is_some(),
+				Error::<T>::RedeemReceiptNotExist
+			);
+			let class_id = Self::redeem_receipt_class().ok_or(Error::<T>::RedeemReceiptNotExist)?;
+			orml_nft::Pallet::<T>::transfer(&owner, &dest, (class_id, receipt_id))?;
+
+			Self::deposit_event(Event::RedeemReceiptTransferred(owner, dest, receipt_id));
+
+			Ok(())
+		}
+
+		/// Withdraw the unbonded tokens of the derivative account on the relay chain, and send
+		/// them back to the redeem account, which pays out the unlocked redeems.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		#[transactional]
+		pub fn withdraw_unbonded(origin: OriginFor<T>, num_slashing_spans: u32) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let index = T::StakingDerivativeIndex::get();
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let withdrawn =
+				StakingLedgers::<T>::try_mutate(index, |maybe_ledger| -> Result<_, Error<T>> {
+					let ledger = maybe_ledger.as_mut().ok_or(Error::<T>::NotBonded)?;
+
+					let mut withdrawn = BalanceOf::<T>::zero();
+					ledger.unlocking.retain(|(unlock_at, amount)| {
+						if *unlock_at <= current_block {
+							withdrawn = withdrawn.saturating_add(*amount);
+							false
+						} else {
+							true
+						}
+					});
+
+					Ok(withdrawn)
+				})?;
+
+			if withdrawn.is_zero() {
+				Self::xcm_ump_staking(StakingCall::WithdrawUnbonded(num_slashing_spans))?;
+			} else {
+				Self::xcm_ump_withdraw_unbonded(num_slashing_spans, withdrawn)?;
+			}
+
+			Self::deposit_event(Event::Withdrawn(index, withdrawn));
+
+			Ok(())
+		}
+
+		/// Nominate the validators for the derivative account on the relay chain.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32))]
+		pub fn nominate(origin: OriginFor<T>, targets: Vec<T::AccountId>) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let index = T::StakingDerivativeIndex::get();
+			ensure!(StakingLedgers::<T>::contains_key(index), Error::<T>::NotBonded);
+
+			Self::xcm_ump_staking(StakingCall::Nominate(
+				targets.iter().cloned().map(MultiAddress::Id).collect(),
+			))?;
+
+			Self::deposit_event(Event::Nominated(index, targets));
+
+			Ok(())
+		}
+
+		/// Report the staking rewards of the derivative account on the relay chain, which are
+		/// staked again. The rewards raise the exchange rate of the vtoken.
+		///
+		/// The rewards can be reported once an era, and at most `MaxStakingRewardsPerEra` of the
+		/// active amount.
+		///
+		/// The dispatch origin for this call must be `ControlOrigin`.
+		#[pallet::weight(T::WeightInfo::report_staking_rewards())]
+		#[transactional]
+		pub fn report_staking_rewards(
+			origin: OriginFor<T>,
+			#[pallet::compact] rewards: BalanceOf<T>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let index = T::StakingDerivativeIndex::get();
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let era = current_block.checked_div(&T::EraLength::get()).unwrap_or(current_block);
+			ensure!(
+				Self::staking_rewards_reported_era(index).map_or(true, |reported| reported < era),
+				Error::<T>::StakingRewardsAlreadyReported
+			);
+
+			StakingLedgers::<T>::try_mutate(index, |maybe_ledger| -> DispatchResult {
+				let ledger = maybe_ledger.as_mut().ok_or(Error::<T>::NotBonded)?;
+				ensure!(
+					rewards <= T::MaxStakingRewardsPerEra::get() * ledger.active,
+					Error::<T>::ExceedMaxStakingRewards
+				);
+				ledger.active = ledger.active.saturating_add(rewards);
+
+				Ok(())
+			})?;
+			StakingRewardsReportedEra::<T>::insert(index, era);
+
+			Self::expand_mint_pool(T::RelayChainToken::get(), rewards)?;
+
+			Self::deposit_event(Event::StakingRewardsReported(index, rewards));
+
 			Ok(())
 		}
 	}
@@ -297,47 +855,414 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(block_number: T::BlockNumber) -> Weight {
-			// Check redeem
-			let _ = Self::check_redeem_period(block_number);
-			471_000 as Weight
+			// Go on migrating the pending redeems left by the runtime upgrade.
+			let migration_weight = migration::migrate_to_v1::<T>();
+
+			// Unlock the redeems maturing in this block.
+			let unlocks = RedeemUnlocks::<T>::take(block_number);
+			let count = unlocks.len() as u32;
+			for receipt_id in unlocks.into_iter() {
+				// The redeem might have been claimed or cancelled already.
+				let _ = Self::unlock_redeem(receipt_id);
+			}
+
+			let mut weight = T::WeightInfo::on_initialize(count).saturating_add(migration_weight);
+
+			// Stake the pending mints and snapshot the rates at the end of the era.
+			let era_length = T::EraLength::get();
+			if !era_length.is_zero() && (block_number % era_length).is_zero() {
+				let pending_mints: Vec<(CurrencyIdOf<T>, BalanceOf<T>)> =
+					PendingMints::<T>::iter().collect();
+				weight = weight.saturating_add(T::WeightInfo::settle_pending_mints(
+					pending_mints.len() as u32,
+				));
+
+				for (token_id, amount) in pending_mints {
+					// The pending mints failing to be staked are retried in the next era.
+					let _ = Self::settle_pending_mint(token_id, amount);
+				}
+
+				let snapshots = Self::snapshot_rates(block_number);
+				weight = weight.saturating_add(T::WeightInfo::snapshot_rates(snapshots));
+			}
+
+			weight
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v1::<T>()
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
-		fn update_redeem_record(
-			currency_id: CurrencyIdOf<T>,
+		/// The current rate of the token: (token pool, vtoken pool)
+		pub fn mint_rate(token_id: CurrencyIdOf<T>) -> (BalanceOf<T>, BalanceOf<T>) {
+			match token_id.to_vtoken() {
+				Ok(vtoken_id) if token_id.is_token() =>
+					(Self::get_mint_pool(token_id), Self::get_mint_pool(vtoken_id)),
+				_ => (Zero::zero(), Zero::zero()),
+			}
+		}
+
+		/// The yield of the vtoken over the rate history, annualized without compounding.
+		pub fn annualized_yield(token_id: CurrencyIdOf<T>) -> Permill {
+			let history = Self::rate_history(token_id);
+			let (first, last) = match (history.first(), history.last()) {
+				(Some(first), Some(last)) if last.0 > first.0 => (first, last),
+				_ => return Permill::zero(),
+			};
+
+			let rate = |token_pool: BalanceOf<T>, vtoken_pool: BalanceOf<T>| {
+				let token_pool: u128 = token_pool.unique_saturated_into();
+				let vtoken_pool: u128 = vtoken_pool.unique_saturated_into();
+				FixedU128::checked_from_rational(token_pool, vtoken_pool)
+			};
+			let growth = match (rate(first.1, first.2), rate(last.1, last.2)) {
+				(Some(first_rate), Some(last_rate)) if !first_rate.is_zero() =>
+					last_rate.saturating_sub(first_rate) / first_rate,
+				_ => return Permill::zero(),
+			};
+
+			let elapsed: u128 = last.0.saturating_sub(first.0).unique_saturated_into();
+			let blocks_per_year: u128 = T::BlocksPerYear::get().unique_saturated_into();
+			let annualized = growth
+				.saturating_mul(FixedU128::saturating_from_rational(blocks_per_year, elapsed));
+
+			Permill::from_rational(annualized.into_inner(), FixedU128::accuracy())
+		}
+
+		/// Snapshot the rates of the tokens, returning the number of the snapshots.
+		fn snapshot_rates(block_number: T::BlockNumber) -> u32 {
+			let token_ids: Vec<CurrencyIdOf<T>> = MintPool::<T>::iter_keys()
+				.filter(|currency_id| currency_id.is_token())
+				.collect();
+
+			let mut snapshots = 0u32;
+			for token_id in token_ids {
+				let (token_pool, vtoken_pool) = Self::mint_rate(token_id);
+				if token_pool.is_zero() || vtoken_pool.is_zero() {
+					continue;
+				}
+
+				RateHistory::<T>::mutate(token_id, |history| {
+					if history.len() >= T::MaxRateHistory::get() as usize && !history.is_empty() {
+						history.remove(0);
+					}
+					let _ = history.try_push((block_number, token_pool, vtoken_pool));
+				});
+				snapshots = snapshots.saturating_add(1);
+			}
+
+			snapshots
+		}
+
+		/// Issue the receipt of a pending redeem, and queue it for unlocking. If the queue of the
+		/// unlock block is full, the redeem is queued in the first following block with room,
+		/// which it unlocks at instead.
+		pub(crate) fn issue_redeem_receipt(
 			who: &T::AccountId,
+			token_id: CurrencyIdOf<T>,
+			redeemed_at: T::BlockNumber,
 			amount: BalanceOf<T>,
-		) {
-			let current_block = <frame_system::Pallet<T>>::block_number();
+			unbonded: BalanceOf<T>,
+			unlock_at: T::BlockNumber,
+		) -> Result<RedeemReceiptIdOf<T>, DispatchError> {
+			let class_id = match Self::redeem_receipt_class() {
+				Some(class_id) => class_id,
+				None => {
+					let class_id =
+						orml_nft::Pallet::<T>::create_class(&Self::account_id(), Vec::new(), ())?;
+					RedeemReceiptClass::<T>::put(class_id);
+					class_id
+				},
+			};
+
+			let receipt_id = orml_nft::Pallet::<T>::mint(who, class_id, Vec::new(), ())?;
+			let mut unlock_at = unlock_at;
+			while RedeemUnlocks::<T>::try_mutate(unlock_at, |unlocks| unlocks.try_push(receipt_id))
+				.is_err()
+			{
+				unlock_at = unlock_at.saturating_add(1u32.into());
+			}
+			RedeemReceipts::<T>::insert(
+				receipt_id,
+				RedeemReceipt { token_id, redeemed_at, unlock_at, amount, unbonded },
+			);
 
-			if RedeemRecord::<T>::contains_key(who, currency_id) {
-				RedeemRecord::<T>::mutate(who, currency_id, |record| {
-					record.push((current_block, amount));
-				})
+			Self::deposit_event(Event::RedeemReceiptIssued(
+				who.clone(),
+				receipt_id,
+				token_id,
+				amount,
+			));
+
+			Ok(receipt_id)
+		}
+
+		/// The pending redeem of the receipt owned by the account.
+		fn owned_redeem_receipt(
+			owner: &T::AccountId,
+			receipt_id: RedeemReceiptIdOf<T>,
+		) -> Option<RedeemReceiptOf<T>> {
+			let class_id = Self::redeem_receipt_class()?;
+			if !orml_nft::Pallet::<T>::is_owner(owner, (class_id, receipt_id)) {
+				return None;
+			}
+
+			Self::redeem_receipt(receipt_id)
+		}
+
+		/// Remove the receipt of the redeem, once the redeem is unlocked or cancelled.
+		fn burn_redeem_receipt(
+			owner: &T::AccountId,
+			receipt_id: RedeemReceiptIdOf<T>,
+		) -> DispatchResult {
+			let class_id = Self::redeem_receipt_class().ok_or(Error::<T>::RedeemReceiptNotExist)?;
+			orml_nft::Pallet::<T>::burn(owner, (class_id, receipt_id))?;
+			RedeemReceipts::<T>::remove(receipt_id);
+
+			Ok(())
+		}
+
+		/// The account keeping the pending mints.
+		pub(crate) fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account()
+		}
+
+		/// The account receiving the unbonded tokens sent back from the relay chain, which pays
+		/// out the unlocked redeems of the relay chain token.
+		pub(crate) fn redeem_account() -> T::AccountId {
+			T::PalletId::get().into_sub_account(T::StakingDerivativeIndex::get())
+		}
+
+		/// Stake the pending mints. The relay chain tokens are sent to the derivative account and
+		/// bonded, the others are burnt.
+		#[transactional]
+		fn settle_pending_mint(token_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+			if !amount.is_zero() {
+				if token_id == T::RelayChainToken::get() {
+					Self::xcm_ump_transfer(Self::account_id(), amount)?;
+					Self::bond(amount)?;
+				} else {
+					T::MultiCurrency::withdraw(token_id, &Self::account_id(), amount)?;
+				}
+			}
+
+			PendingMints::<T>::remove(token_id);
+
+			Self::deposit_event(Event::PendingMintSettled(token_id, amount));
+
+			Ok(())
+		}
+
+		/// Bond the tokens of the derivative account on the relay chain.
+		fn bond(amount: BalanceOf<T>) -> DispatchResult {
+			let index = T::StakingDerivativeIndex::get();
+
+			let call = if StakingLedgers::<T>::contains_key(index) {
+				StakingCall::BondExtra(amount)
 			} else {
-				let mut new_record = Vec::with_capacity(1);
-				new_record.push((current_block, amount));
-				RedeemRecord::<T>::insert(who, currency_id, new_record);
+				StakingCall::Bond(Bond {
+					controller: MultiAddress::Id(Self::staking_account()?),
+					value: amount,
+					payee: RewardDestination::Staked,
+				})
+			};
+			Self::xcm_ump_staking(call)?;
+
+			StakingLedgers::<T>::mutate(index, |maybe_ledger| {
+				let ledger = maybe_ledger.get_or_insert_with(Default::default);
+				ledger.active = ledger.active.saturating_add(amount);
+			});
+
+			Self::deposit_event(Event::Bonded(index, amount));
+
+			Ok(())
+		}
+
+		/// Unbond the tokens of the derivative account on the relay chain, at most the active
+		/// amount, returning the amount unbonded.
+		fn unbond(
+			amount: BalanceOf<T>,
+			unlock_at: T::BlockNumber,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let index = T::StakingDerivativeIndex::get();
+
+			let unbonded = StakingLedgers::<T>::mutate(index, |maybe_ledger| {
+				maybe_ledger.as_mut().map_or(Zero::zero(), |ledger| {
+					let unbonded = amount.min(ledger.active);
+					if !unbonded.is_zero() {
+						ledger.active = ledger.active.saturating_sub(unbonded);
+						match ledger.unlocking.last_mut() {
+							Some((at, unlocking)) if *at == unlock_at =>
+								*unlocking = unlocking.saturating_add(unbonded),
+							_ => ledger.unlocking.push((unlock_at, unbonded)),
+						}
+					}
+					unbonded
+				})
+			});
+
+			if !unbonded.is_zero() {
+				Self::xcm_ump_staking(StakingCall::Unbond(unbonded))?;
+
+				Self::deposit_event(Event::Unbonded(index, unbonded));
 			}
+
+			Ok(unbonded)
 		}
 
-		fn check_redeem_period(n: T::BlockNumber) -> DispatchResult {
-			for (who, currency_id, records) in RedeemRecord::<T>::iter() {
-				let redeem_period = StakingLockPeriod::<T>::get(&currency_id);
-				let mut exist_redeem_record = Vec::new();
-				for (when, amount) in records.iter().cloned() {
-					let rs = n.checked_sub(&when).ok_or(Error::<T>::CalculationOverflow)?;
-					if rs >= redeem_period {
-						T::MultiCurrency::deposit(currency_id, &who, amount)?;
-					} else {
-						exist_redeem_record.push((when, amount));
+		/// Rebond the tokens being unbonded, from the last unlocking chunk on as the relay chain
+		/// does, returning the amount rebonded. It's at most the amount still unlocking.
+		fn rebond(amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+			if amount.is_zero() {
+				return Ok(Zero::zero());
+			}
+
+			let index = T::StakingDerivativeIndex::get();
+			let rebonded =
+				StakingLedgers::<T>::try_mutate(index, |maybe_ledger| -> Result<_, Error<T>> {
+					let ledger = maybe_ledger.as_mut().ok_or(Error::<T>::NotBonded)?;
+
+					let mut rebonded = BalanceOf::<T>::zero();
+					while rebonded < amount {
+						let (_, unlocking) = match ledger.unlocking.last_mut() {
+							Some(chunk) => chunk,
+							None => break,
+						};
+						let part = amount.saturating_sub(rebonded).min(*unlocking);
+						*unlocking = unlocking.saturating_sub(part);
+						rebonded = rebonded.saturating_add(part);
+						if unlocking.is_zero() {
+							ledger.unlocking.pop();
+						}
 					}
-				}
-				RedeemRecord::<T>::mutate(who, currency_id, |record| {
-					*record = exist_redeem_record;
-				});
+					ledger.active = ledger.active.saturating_add(rebonded);
+
+					Ok(rebonded)
+				})?;
+
+			if !rebonded.is_zero() {
+				Self::xcm_ump_staking(StakingCall::Rebond(rebonded))?;
+
+				Self::deposit_event(Event::Rebonded(index, rebonded));
+			}
+
+			Ok(rebonded)
+		}
+
+		/// The derivative account staking on the relay chain.
+		fn staking_account() -> Result<T::AccountId, DispatchError> {
+			match T::StakingSubAccountLocation::get() {
+				MultiLocation { parents: 1, interior: X1(AccountId32 { id, .. }) } =>
+					T::AccountId::decode(&mut &id[..])
+						.map_err(|_| Error::<T>::InvalidStakingAccount.into()),
+				_ => Err(Error::<T>::InvalidStakingAccount.into()),
+			}
+		}
+
+		fn xcm_ump_staking(call: StakingCall<BalanceOf<T>, T::AccountId>) -> DispatchResult {
+			let index = T::StakingDerivativeIndex::get();
+
+			use_relay!({
+				let call = RelaychainCall::Utility::<BalanceOf<T>, T::AccountId, T::BlockNumber>(
+					Box::new(UtilityCall::AsDerivative(index, RelaychainCall::Staking(call))),
+				)
+				.encode()
+				.into();
+
+				T::BifrostXcmExecutor::ump_transact(
+					MultiLocation::here(),
+					call,
+					T::StakingCallWeight::get(),
+					false,
+					0,
+				)
+				.map_err(|_| Error::<T>::XcmFailed)?;
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw the unbonded tokens of the derivative account, and send them back to the
+		/// redeem account in the same batch.
+		fn xcm_ump_withdraw_unbonded(
+			num_slashing_spans: u32,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let index = T::StakingDerivativeIndex::get();
+			let id = <[u8; 32]>::decode(&mut &Self::redeem_account().encode()[..])
+				.map_err(|_| Error::<T>::InvalidRedeemAccount)?;
+			let dest = MultiLocation::new(0, X1(Parachain(T::SelfParaId::get())));
+			let beneficiary =
+				MultiLocation::new(0, X1(AccountId32 { network: NetworkId::Any, id }));
+			let asset = MultiAsset {
+				id: Concrete(MultiLocation::here()),
+				fun: Fungible(amount.unique_saturated_into()),
+			};
+
+			use_relay!({
+				let call = RelaychainCall::Utility::<BalanceOf<T>, T::AccountId, T::BlockNumber>(
+					Box::new(UtilityCall::AsDerivative(
+						index,
+						RelaychainCall::Utility(Box::new(UtilityCall::BatchAll(vec![
+							RelaychainCall::Staking(StakingCall::WithdrawUnbonded(
+								num_slashing_spans,
+							)),
+							RelaychainCall::XcmPallet(XcmCall::ReserveTransferAssets(
+								Box::new(dest.into()),
+								Box::new(beneficiary.into()),
+								Box::new(MultiAssets::from(asset).into()),
+								0,
+							)),
+						]))),
+					)),
+				)
+				.encode()
+				.into();
+
+				T::BifrostXcmExecutor::ump_transact(
+					MultiLocation::here(),
+					call,
+					T::StakingCallWeight::get().saturating_mul(2),
+					false,
+					0,
+				)
+				.map_err(|_| Error::<T>::XcmFailed)?;
+			});
+
+			Ok(())
+		}
+
+		fn xcm_ump_transfer(who: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			T::XcmTransfer::transfer(
+				who,
+				T::RelayChainToken::get(),
+				amount,
+				T::StakingSubAccountLocation::get(),
+				3 * T::BaseXcmWeight::get(),
+			)
+		}
+
+		/// Burn the receipt of the redeem, and pay the redeemed token to the owner of the
+		/// receipt. The tokens unbonded on the relay chain are paid from the redeem account, so
+		/// the redeem fails until they are sent back, and has to be claimed then.
+		#[transactional]
+		fn unlock_redeem(receipt_id: RedeemReceiptIdOf<T>) -> DispatchResult {
+			let RedeemReceipt { token_id, amount, unbonded, .. } =
+				Self::redeem_receipt(receipt_id).ok_or(Error::<T>::RedeemReceiptNotExist)?;
+			let class_id = Self::redeem_receipt_class().ok_or(Error::<T>::RedeemReceiptNotExist)?;
+			let owner = orml_nft::Pallet::<T>::tokens(class_id, receipt_id)
+				.ok_or(Error::<T>::RedeemReceiptNotExist)?
+				.owner;
+
+			if !unbonded.is_zero() {
+				T::MultiCurrency::transfer(token_id, &Self::redeem_account(), &owner, unbonded)?;
 			}
+			T::MultiCurrency::deposit(token_id, &owner, amount.saturating_sub(unbonded))?;
+			Self::burn_redeem_receipt(&owner, receipt_id)?;
+
+			Self::deposit_event(Event::RedeemUnlocked(owner, token_id, amount));
 
 			Ok(())
 		}
@@ -21,9 +21,15 @@
 
 use frame_support::{assert_noop, assert_ok};
 use node_primitives::Balance;
+use sp_runtime::DispatchError::BadOrigin;
 
 use crate::{mock::*, *};
 
+fn receipt_owner(receipt_id: u64) -> Option<AccountId> {
+	let class_id = VtokenMint::redeem_receipt_class()?;
+	OrmlNFT::tokens(class_id, receipt_id).map(|token| token.owner)
+}
+
 #[test]
 fn mint_vtoken_should_be_ok() {
 	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
@@ -150,6 +156,559 @@ fn redeem_token_should_be_ok() {
 	});
 }
 
+#[test]
+fn redeem_unlock_queue_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(DOT, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vDOT, 100));
+
+		let alice_dot = Tokens::free_balance(DOT, &ALICE);
+
+		run_to_block(1);
+		// Only two redeems can be unlocked in a block, the third one is queued in the next block.
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 10));
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 20));
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 30));
+		assert_eq!(VtokenMint::redeem_unlocks(29).into_inner(), vec![0, 1]);
+		assert_eq!(VtokenMint::redeem_unlocks(30).into_inner(), vec![2]);
+		assert_eq!(
+			VtokenMint::redeem_receipt(2),
+			Some(RedeemReceipt {
+				token_id: DOT,
+				redeemed_at: 1,
+				unlock_at: 30,
+				amount: 30,
+				unbonded: 0
+			})
+		);
+
+		run_to_block(29);
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemUnlocked(
+			ALICE, DOT, 20,
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), alice_dot + 30);
+		assert_eq!(VtokenMint::redeem_receipt(0), None);
+		assert_eq!(VtokenMint::redeem_receipt(1), None);
+		assert!(VtokenMint::redeem_receipt(2).is_some());
+		assert!(VtokenMint::redeem_unlocks(29).is_empty());
+
+		assert_noop!(
+			VtokenMint::claim_redeem(Origin::signed(ALICE), 2),
+			Error::<Runtime>::RedeemNotUnlocked
+		);
+
+		run_to_block(30);
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemUnlocked(
+			ALICE, DOT, 30,
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), alice_dot + 60);
+		assert_eq!(VtokenMint::redeem_receipt(2), None);
+		assert_eq!(receipt_owner(2), None);
+
+		assert_noop!(
+			VtokenMint::claim_redeem(Origin::signed(ALICE), 2),
+			Error::<Runtime>::RedeemReceiptNotExist
+		);
+	});
+}
+
+#[test]
+fn redeem_receipt_should_be_transferable() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(DOT, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vDOT, 100));
+
+		let bob_dot = Tokens::free_balance(DOT, &BOB);
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 10));
+		let receipt_issued =
+			mock::Event::VtokenMint(crate::Event::RedeemReceiptIssued(ALICE, 0, DOT, 10));
+		assert!(System::events().iter().any(|record| record.event == receipt_issued));
+		let receipt =
+			RedeemReceipt { token_id: DOT, redeemed_at: 1, unlock_at: 29, amount: 10, unbonded: 0 };
+		assert_eq!(VtokenMint::redeem_receipt(0), Some(receipt.clone()));
+		assert_eq!(receipt_owner(0), Some(ALICE));
+
+		assert_noop!(
+			VtokenMint::transfer_redeem_receipt(Origin::signed(BOB), BOB, 0),
+			Error::<Runtime>::RedeemReceiptNotExist
+		);
+
+		// The redeem is moved to Bob along with the receipt.
+		assert_ok!(VtokenMint::transfer_redeem_receipt(Origin::signed(ALICE), BOB, 0));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemReceiptTransferred(
+			ALICE, BOB, 0,
+		)));
+		assert_eq!(VtokenMint::redeem_receipt(0), Some(receipt));
+		assert_eq!(receipt_owner(0), Some(BOB));
+		assert_noop!(
+			VtokenMint::cancel_redeem(Origin::signed(ALICE), 0),
+			Error::<Runtime>::RedeemReceiptNotExist
+		);
+
+		// The receipt of a cancelled redeem is burnt.
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 20));
+		assert!(VtokenMint::redeem_receipt(1).is_some());
+		assert_ok!(VtokenMint::cancel_redeem(Origin::signed(ALICE), 1));
+		assert_eq!(VtokenMint::redeem_receipt(1), None);
+		assert_eq!(receipt_owner(1), None);
+
+		// The receipt is burnt once the redeem is unlocked to Bob.
+		run_to_block(29);
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemUnlocked(
+			BOB, DOT, 10,
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot + 10);
+		assert_eq!(VtokenMint::redeem_receipt(0), None);
+		assert_eq!(receipt_owner(0), None);
+	});
+}
+
+#[test]
+fn relay_chain_staking_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(KSM, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vKSM, 100));
+		assert_ok!(VtokenMint::set_token_staking_lock_period(Origin::root(), KSM, 28));
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vKSM, 10));
+		assert_eq!(Tokens::free_balance(KSM, &BOB), 90);
+		assert_eq!(Tokens::free_balance(vKSM, &BOB), 10);
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::Minted(BOB, vKSM, 10)));
+		assert_eq!(VtokenMint::staking_ledger(1), None);
+
+		// The KSMs minted are sent to the derivative account and bonded at the end of the era.
+		run_to_block(10);
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::PendingMintSettled(
+			KSM, 10,
+		)));
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::account_id()), 0);
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 10, unlocking: vec![] })
+		);
+
+		let bond_call =
+			xcm_support::kusama::RelaychainCall::Utility::<Balance, AccountId, BlockNumber>(
+				Box::new(xcm_support::UtilityCall::AsDerivative(
+					1,
+					xcm_support::kusama::RelaychainCall::Staking(xcm_support::StakingCall::Bond(
+						xcm_support::Bond {
+							controller: sp_runtime::MultiAddress::Id(STAKING_ACCOUNT),
+							value: 10,
+							payee: xcm_support::RewardDestination::Staked,
+						},
+					)),
+				)),
+			)
+			.encode();
+		assert_eq!(bond_call[..6], [24, 1, 1, 0, 6, 0]);
+		assert_eq!(RELAY_CALLS.with(|calls| calls.borrow().clone()), vec![bond_call]);
+
+		// The bonded derivative account bonds extra.
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vKSM, 20));
+		run_to_block(20);
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 30, unlocking: vec![] })
+		);
+		assert_eq!(RELAY_CALLS.with(|calls| calls.borrow()[1][4..6].to_vec()), vec![6, 1]);
+
+		// Redeeming unbonds the tokens.
+		assert_ok!(VtokenMint::redeem(Origin::signed(BOB), KSM, 10));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 20, unlocking: vec![(48, 10)] })
+		);
+		assert_eq!(RELAY_CALLS.with(|calls| calls.borrow()[2][4..6].to_vec()), vec![6, 2]);
+		assert_eq!(
+			VtokenMint::redeem_receipt(0),
+			Some(RedeemReceipt {
+				token_id: KSM,
+				redeemed_at: 20,
+				unlock_at: 48,
+				amount: 10,
+				unbonded: 10
+			})
+		);
+
+		// The rewards raise the exchange rate, at most 10% of the active amount once an era.
+		assert_noop!(VtokenMint::report_staking_rewards(Origin::signed(BOB), 2), BadOrigin);
+		assert_noop!(
+			VtokenMint::report_staking_rewards(Origin::root(), 3),
+			Error::<Runtime>::ExceedMaxStakingRewards
+		);
+		assert_ok!(VtokenMint::report_staking_rewards(Origin::root(), 2));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::StakingRewardsReported(
+			1, 2,
+		)));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 22, unlocking: vec![(48, 10)] })
+		);
+		assert_eq!(VtokenMint::get_mint_pool(KSM), 122);
+		assert_eq!(VtokenMint::get_mint_pool(vKSM), 120);
+		assert_noop!(
+			VtokenMint::report_staking_rewards(Origin::root(), 1),
+			Error::<Runtime>::StakingRewardsAlreadyReported
+		);
+
+		assert_ok!(VtokenMint::nominate(Origin::root(), vec![ALICE]));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::Nominated(1, vec![ALICE])));
+
+		// Nothing is withdrawn before the unbonding ends.
+		assert_ok!(VtokenMint::withdraw_unbonded(Origin::root(), 0));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::Withdrawn(1, 0)));
+
+		// The redeem can't be unlocked until the unbonded tokens are sent back.
+		run_to_block(48);
+		assert_eq!(Tokens::free_balance(KSM, &BOB), 70);
+		assert!(VtokenMint::redeem_receipt(0).is_some());
+
+		assert_ok!(VtokenMint::withdraw_unbonded(Origin::root(), 0));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::Withdrawn(1, 10)));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 22, unlocking: vec![] })
+		);
+
+		// The unbonded tokens are withdrawn and sent back to the redeem account in a batch.
+		let withdraw_call = RELAY_CALLS.with(|calls| calls.borrow().last().unwrap().clone());
+		assert_eq!(withdraw_call[..8], [24, 1, 1, 0, 24, 2, 8, 6]);
+		assert_noop!(
+			VtokenMint::claim_redeem(Origin::signed(BOB), 0),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(Tokens::deposit(KSM, &VtokenMint::redeem_account(), 10));
+		assert_ok!(VtokenMint::claim_redeem(Origin::signed(BOB), 0));
+		assert_eq!(Tokens::free_balance(KSM, &BOB), 80);
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::redeem_account()), 0);
+		assert_eq!(VtokenMint::redeem_receipt(0), None);
+	});
+}
+
+#[test]
+fn redeem_beyond_bonded_should_mint_the_rest() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		// The pools are not backed by the bonded tokens, as set by the genesis or governance.
+		assert_ok!(VtokenMint::expand_mint_pool(KSM, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vKSM, 100));
+		assert_ok!(VtokenMint::set_token_staking_lock_period(Origin::root(), KSM, 28));
+		assert_ok!(Tokens::deposit(vKSM, &ALICE, 30));
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vKSM, 10));
+		run_to_block(10);
+
+		// Only the bonded tokens are unbonded for the redeem.
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), KSM, 30));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 0, unlocking: vec![(38, 10)] })
+		);
+		assert_eq!(
+			VtokenMint::redeem_receipt(0),
+			Some(RedeemReceipt {
+				token_id: KSM,
+				redeemed_at: 10,
+				unlock_at: 38,
+				amount: 30,
+				unbonded: 10
+			})
+		);
+
+		// The unbonded tokens are paid from the redeem account once sent back, and the rest is
+		// minted.
+		run_to_block(38);
+		assert!(VtokenMint::redeem_receipt(0).is_some());
+		assert_ok!(VtokenMint::withdraw_unbonded(Origin::root(), 0));
+		assert_ok!(Tokens::deposit(KSM, &VtokenMint::redeem_account(), 10));
+		assert_ok!(VtokenMint::claim_redeem(Origin::signed(ALICE), 0));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemUnlocked(
+			ALICE, KSM, 30,
+		)));
+		assert_eq!(Tokens::free_balance(KSM, &ALICE), 30);
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::redeem_account()), 0);
+	});
+}
+
+#[test]
+fn relay_chain_staking_should_fail_on_xcm_error() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(KSM, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vKSM, 100));
+
+		// Only the bonded derivative account can be managed.
+		assert_noop!(
+			VtokenMint::nominate(Origin::root(), vec![ALICE]),
+			Error::<Runtime>::NotBonded
+		);
+		assert_noop!(
+			VtokenMint::report_staking_rewards(Origin::root(), 0),
+			Error::<Runtime>::NotBonded
+		);
+		assert_noop!(VtokenMint::withdraw_unbonded(Origin::root(), 0), Error::<Runtime>::NotBonded);
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vKSM, 10));
+
+		// The pending mints failing to be staked are kept until the next era.
+		MOCK_XCM_RESULT.with(|result| *result.borrow_mut() = false);
+		run_to_block(10);
+		assert_eq!(VtokenMint::pending_mint(KSM), 10);
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::account_id()), 10);
+		assert_eq!(VtokenMint::staking_ledger(1), None);
+
+		MOCK_XCM_RESULT.with(|result| *result.borrow_mut() = true);
+		run_to_block(20);
+		assert_eq!(VtokenMint::pending_mint(KSM), 0);
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::account_id()), 0);
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 10, unlocking: vec![] })
+		);
+	});
+}
+
+#[test]
+fn redeem_should_net_against_pending_mints() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(DOT, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vDOT, 200));
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vDOT, 20));
+		assert_eq!(VtokenMint::pending_mint(DOT), 20);
+		assert_eq!(Tokens::free_balance(DOT, &VtokenMint::account_id()), 20);
+
+		// Alice redeems 30 DOTs, 20 of which are paid out instantly, less the 10% fee left in the
+		// pending mints and the mint pool.
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 60));
+		let redeemed_instantly =
+			mock::Event::VtokenMint(crate::Event::RedeemedInstantly(ALICE, DOT, 18, 2));
+		assert!(System::events().iter().any(|record| record.event == redeemed_instantly));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemQueued(
+			ALICE, DOT, 10,
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 118);
+		assert_eq!(VtokenMint::pending_mint(DOT), 2);
+		assert_eq!(VtokenMint::get_mint_pool(DOT), 92);
+		assert_eq!(VtokenMint::get_mint_pool(vDOT), 180);
+		assert_eq!(
+			VtokenMint::redeem_receipt(0),
+			Some(RedeemReceipt {
+				token_id: DOT,
+				redeemed_at: 1,
+				unlock_at: 29,
+				amount: 10,
+				unbonded: 0
+			})
+		);
+
+		// The redeem is paid out instantly as a whole.
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vDOT, 20));
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 20));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemedInstantly(
+			ALICE, DOT, 9, 1,
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 127);
+		assert_eq!(VtokenMint::pending_mint(DOT), 13);
+		assert_eq!(
+			VtokenMint::redeem_receipt(0),
+			Some(RedeemReceipt {
+				token_id: DOT,
+				redeemed_at: 1,
+				unlock_at: 29,
+				amount: 10,
+				unbonded: 0
+			})
+		);
+
+		// The pending mints left are burnt at the end of the era.
+		run_to_block(10);
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::PendingMintSettled(
+			DOT, 13,
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &VtokenMint::account_id()), 0);
+
+		run_to_block(29);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 137);
+	});
+}
+
+#[test]
+fn cancel_redeem_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(DOT, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vDOT, 200));
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), DOT, 40));
+		assert_eq!(Tokens::free_balance(vDOT, &ALICE), 360);
+		assert_eq!(
+			VtokenMint::redeem_receipt(0),
+			Some(RedeemReceipt {
+				token_id: DOT,
+				redeemed_at: 1,
+				unlock_at: 29,
+				amount: 20,
+				unbonded: 0
+			})
+		);
+
+		assert_noop!(
+			VtokenMint::cancel_redeem(Origin::signed(ALICE), 1),
+			Error::<Runtime>::RedeemReceiptNotExist
+		);
+		assert_noop!(
+			VtokenMint::cancel_redeem(Origin::signed(BOB), 0),
+			Error::<Runtime>::RedeemReceiptNotExist
+		);
+
+		// 10% of the DOTs are charged, and the vDOTs are restored at the current exchange rate.
+		assert_ok!(VtokenMint::cancel_redeem(Origin::signed(ALICE), 0));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemCancelled(
+			ALICE, DOT, 20, 36, 2,
+		)));
+		assert_eq!(Tokens::free_balance(vDOT, &ALICE), 396);
+		assert_eq!(VtokenMint::redeem_receipt(0), None);
+		assert_eq!(receipt_owner(0), None);
+		assert!(VtokenMint::redeem_unlocks(29).is_empty());
+		assert_eq!(VtokenMint::get_mint_pool(DOT), 100);
+		assert_eq!(VtokenMint::get_mint_pool(vDOT), 196);
+
+		run_to_block(29);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+
+		// The relay chain tokens being unbonded are bonded again.
+		assert_ok!(VtokenMint::expand_mint_pool(KSM, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vKSM, 100));
+		assert_ok!(VtokenMint::set_token_staking_lock_period(Origin::root(), KSM, 28));
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vKSM, 10));
+		run_to_block(30);
+
+		assert_ok!(VtokenMint::redeem(Origin::signed(BOB), KSM, 10));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 0, unlocking: vec![(58, 10)] })
+		);
+
+		assert_ok!(VtokenMint::cancel_redeem(Origin::signed(BOB), 1));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemCancelled(
+			BOB, KSM, 10, 9, 1,
+		)));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 10, unlocking: vec![] })
+		);
+		assert_eq!(
+			RELAY_CALLS.with(|calls| calls.borrow().last().unwrap()[4..6].to_vec()),
+			vec![6, 19]
+		);
+
+		// Only the tokens unbonded for the redeem are rebonded.
+		assert_ok!(VtokenMint::set_token_staking_lock_period(Origin::root(), KSM, 10));
+		assert_ok!(Tokens::deposit(vKSM, &ALICE, 50));
+		assert_ok!(VtokenMint::redeem(Origin::signed(ALICE), KSM, 50));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 0, unlocking: vec![(40, 10)] })
+		);
+		assert_ok!(VtokenMint::cancel_redeem(Origin::signed(ALICE), 2));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemCancelled(
+			ALICE, KSM, 50, 44, 5,
+		)));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 10, unlocking: vec![] })
+		);
+	});
+}
+
+#[test]
+fn cancel_redeem_should_rebond_from_the_last_unlocking() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(KSM, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vKSM, 100));
+		assert_ok!(VtokenMint::set_token_staking_lock_period(Origin::root(), KSM, 28));
+
+		run_to_block(1);
+		assert_ok!(VtokenMint::mint(Origin::signed(BOB), vKSM, 20));
+		run_to_block(10);
+		assert_ok!(VtokenMint::redeem(Origin::signed(BOB), KSM, 10));
+		run_to_block(15);
+		assert_ok!(VtokenMint::redeem(Origin::signed(BOB), KSM, 10));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 0, unlocking: vec![(38, 10), (43, 10)] })
+		);
+
+		// The relay chain rebonds the last unlocking chunk first, whichever redeem is cancelled.
+		assert_ok!(VtokenMint::cancel_redeem(Origin::signed(BOB), 0));
+		System::assert_last_event(mock::Event::VtokenMint(crate::Event::RedeemCancelled(
+			BOB, KSM, 10, 9, 1,
+		)));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 10, unlocking: vec![(38, 10)] })
+		);
+
+		// The tokens withdrawn early pay for the redeem left.
+		run_to_block(38);
+		assert_ok!(VtokenMint::withdraw_unbonded(Origin::root(), 0));
+		assert_ok!(Tokens::deposit(KSM, &VtokenMint::redeem_account(), 10));
+
+		// Nothing is left to rebond once it's cancelled too, so the withdrawn tokens are staked
+		// again along with the pending mints.
+		assert_ok!(VtokenMint::cancel_redeem(Origin::signed(BOB), 1));
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 10, unlocking: vec![] })
+		);
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::redeem_account()), 0);
+		assert_eq!(Tokens::free_balance(KSM, &VtokenMint::account_id()), 10);
+		assert_eq!(VtokenMint::pending_mint(KSM), 10);
+
+		run_to_block(40);
+		assert_eq!(
+			VtokenMint::staking_ledger(1),
+			Some(StakingLedger { active: 20, unlocking: vec![] })
+		);
+	});
+}
+
+#[test]
+fn rate_history_should_work() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		assert_ok!(VtokenMint::expand_mint_pool(DOT, 100));
+		assert_ok!(VtokenMint::expand_mint_pool(vDOT, 200));
+		assert_eq!(VtokenMint::mint_rate(DOT), (100, 200));
+		assert_eq!(VtokenMint::mint_rate(vDOT), (0, 0));
+
+		// The rates are snapshotted at the end of the era, except for the empty pools.
+		run_to_block(10);
+		assert_eq!(VtokenMint::rate_history(DOT).into_inner(), vec![(10, 100, 200)]);
+		assert!(VtokenMint::rate_history(KSM).is_empty());
+		assert_eq!(VtokenMint::annualized_yield(DOT), Permill::zero());
+
+		// 1% in an era of 10 blocks, over a year of 100 blocks.
+		assert_ok!(VtokenMint::expand_mint_pool(DOT, 1));
+		run_to_block(20);
+		assert_eq!(VtokenMint::annualized_yield(DOT), Permill::from_percent(10));
+
+		// The oldest snapshot is dropped once the history is full.
+		run_to_block(40);
+		assert_eq!(
+			VtokenMint::rate_history(DOT).into_inner(),
+			vec![(20, 101, 200), (30, 101, 200), (40, 101, 200)]
+		);
+		assert_eq!(VtokenMint::annualized_yield(DOT), Permill::zero());
+	});
+}
+
 #[test]
 fn zero_token_pool_should_not_work() {
 	ExtBuilder::default().zero_for_alice_n_bob().build().execute_with(|| {
@@ -171,3 +730,58 @@ fn zero_token_pool_should_not_work() {
 		);
 	});
 }
+
+#[test]
+fn migrate_to_v1_should_issue_redeem_receipts() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		run_to_block(40);
+		StorageVersion::new(0).put::<VtokenMint>();
+		RedeemRecord::<Runtime>::insert(ALICE, DOT, vec![(1, 10), (20, 20)]);
+
+		migration::migrate_to_v1::<Runtime>();
+		assert_eq!(StorageVersion::get::<VtokenMint>(), STORAGE_VERSION);
+		assert!(!RedeemRecord::<Runtime>::contains_key(ALICE, DOT));
+
+		// The overdue redeem is unlocked in the next block, and minted as before.
+		assert_eq!(
+			VtokenMint::redeem_receipt(0),
+			Some(RedeemReceipt {
+				token_id: DOT,
+				redeemed_at: 1,
+				unlock_at: 41,
+				amount: 10,
+				unbonded: 0
+			})
+		);
+		assert_eq!(receipt_owner(0), Some(ALICE));
+		assert_eq!(VtokenMint::redeem_unlocks(41).into_inner(), vec![0]);
+		assert_eq!(VtokenMint::redeem_unlocks(48).into_inner(), vec![1]);
+
+		run_to_block(48);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 130);
+		assert_eq!(VtokenMint::redeem_receipt(0), None);
+		assert_eq!(VtokenMint::redeem_receipt(1), None);
+	});
+}
+
+#[test]
+fn migrate_to_v1_should_be_bounded_per_block() {
+	ExtBuilder::default().one_hundred_for_alice_n_bob().build().execute_with(|| {
+		StorageVersion::new(0).put::<VtokenMint>();
+		let records: Vec<(BlockNumber, Balance)> =
+			(0..=migration::MAX_REDEEMS_MIGRATED_PER_BLOCK).map(|_| (1, 1)).collect();
+		RedeemRecord::<Runtime>::insert(ALICE, DOT, records);
+
+		// The redeems left are migrated in the following blocks.
+		run_to_block(1);
+		assert_eq!(RedeemRecord::<Runtime>::get(ALICE, DOT), vec![(1, 1)]);
+		assert_eq!(StorageVersion::get::<VtokenMint>(), StorageVersion::new(0));
+
+		run_to_block(2);
+		assert!(!RedeemRecord::<Runtime>::contains_key(ALICE, DOT));
+		assert_eq!(StorageVersion::get::<VtokenMint>(), STORAGE_VERSION);
+		assert!(
+			VtokenMint::redeem_receipt(migration::MAX_REDEEMS_MIGRATED_PER_BLOCK as u64).is_some()
+		);
+	});
+}
@@ -31,10 +31,19 @@ pub trait WeightInfo {
 	fn set_token_staking_lock_period() -> Weight;
 	fn mint() -> Weight;
 	fn redeem() -> Weight;
-	fn on_initialize() -> Weight;
+	fn cancel_redeem() -> Weight;
+	fn claim_redeem() -> Weight;
+	fn transfer_redeem_receipt() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn nominate(n: u32) -> Weight;
+	fn report_staking_rewards() -> Weight;
+	fn on_initialize(n: u32) -> Weight;
+	fn settle_pending_mints(n: u32) -> Weight;
+	fn snapshot_rates(n: u32) -> Weight;
 }
 
-// For backwards compatibility and tests
+// For backwards compatibility and tests. The extrinsics not benchmarked yet are weighed
+// conservatively here, since the runtimes fall back on these weights until they are.
 impl WeightInfo for () {
 	fn set_vtoken_pool() -> Weight {
 		(50_000_000 as Weight)
@@ -52,7 +61,64 @@ impl WeightInfo for () {
 		(50_000_000 as Weight)
 	}
 
-	fn on_initialize() -> Weight {
+	fn cancel_redeem() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+
+	fn claim_redeem() -> Weight {
+		(150_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+
+	fn transfer_redeem_receipt() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+
+	fn withdraw_unbonded() -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+
+	fn nominate(n: u32) -> Weight {
+		(200_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+
+	fn report_staking_rewards() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn on_initialize(n: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((150_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn settle_pending_mints(n: u32) -> Weight {
 		(50_000_000 as Weight)
+			.saturating_add((300_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+
+	fn snapshot_rates(n: u32) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
 	}
 }
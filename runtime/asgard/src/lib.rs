@@ -72,8 +72,8 @@ use xcm_support::Get;
 /// Constant values used within the runtime.
 pub mod constants;
 use bifrost_flexible_fee::{
-	fee_dealer::{FeeDealer, FixedCurrencyFeeRate},
-	misc_fees::{ExtraFeeMatcher, MiscFeeHandler, NameGetter},
+	fee_dealer::FeeDealer,
+	misc_fees::{ExtraFeeAmount, ExtraFeeInfo},
 };
 use bifrost_runtime_common::{
 	constants::parachains,
@@ -92,10 +92,9 @@ use frame_support::{
 	traits::{EnsureOrigin, OnRuntimeUpgrade},
 };
 pub use node_primitives::{
-	traits::CheckSubAccount, AccountId, Amount, Balance, BlockNumber, CurrencyId, ExtraFeeName,
-	Moment, Nonce, ParaId, ParachainDerivedProxyAccountType, ParachainTransactProxyType,
-	ParachainTransactType, PoolId, RpcContributionStatus, TokenSymbol, TransferOriginType,
-	XcmBaseWeight,
+	traits::CheckSubAccount, AccountId, Amount, Balance, BlockNumber, CurrencyId, Moment, Nonce,
+	ParaId, ParachainDerivedProxyAccountType, ParachainTransactProxyType, ParachainTransactType,
+	PoolId, RpcContributionStatus, TokenSymbol, TransferOriginType, XcmBaseWeight,
 };
 // orml imports
 use orml_currencies::BasicCurrencyAdapter;
@@ -1135,7 +1134,9 @@ impl Contains<AccountId> for DustRemovalWhitelist {
 	fn contains(a: &AccountId) -> bool {
 		get_all_pallet_accounts().contains(a) ||
 			LiquidityMiningPalletId::get().check_sub_account::<PoolId>(a) ||
-			LiquidityMiningDOTPalletId::get().check_sub_account::<PoolId>(a)
+			LiquidityMiningDOTPalletId::get().check_sub_account::<PoolId>(a) ||
+			LighteningRedeemPalletId::get()
+				.check_sub_account::<bifrost_lightening_redeem::RedeemPoolId>(a)
 	}
 }
 
@@ -1155,6 +1156,20 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = weights::orml_tokens::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxClassMetadata: u32 = 0;
+	pub const MaxTokenMetadata: u32 = 0;
+}
+
+impl orml_nft::Config for Runtime {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ();
+	type TokenData = ();
+	type MaxClassMetadata = MaxClassMetadata;
+	type MaxTokenMetadata = MaxTokenMetadata;
+}
+
 parameter_types! {
 	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(ParachainInfo::get().into())));
 }
@@ -1189,81 +1204,85 @@ parameter_types! {
 	// 3 hours(1800 blocks) as an era
 	pub const VtokenMintDuration: BlockNumber = 3 * 60 * MINUTES;
 	pub const StakingPalletId: PalletId = PalletId(*b"staking ");
+	pub const MaxRateHistory: u32 = 28 * 4;
+	pub const BlocksPerYear: BlockNumber = 365 * DAYS;
+	pub const CancelRedeemFee: Permill = Permill::from_percent(1);
+	pub const InstantRedeemFee: Permill = Permill::from_percent(1);
+	pub const MaxRedeemUnlocksPerBlock: u32 = 50;
+	pub const StakingDerivativeIndex: u16 = ParachainDerivedProxyAccountType::Staking as u16;
+	pub StakingSubAccountLocation: MultiLocation = create_x2_multilocation(StakingDerivativeIndex::get());
+	pub StakingCallWeight: XcmBaseWeight = XCM_WEIGHT.into();
+	pub const MaxStakingRewardsPerEra: Permill = Permill::from_perthousand(1);
 }
 impl bifrost_vtoken_mint::Config for Runtime {
 	type Event = Event;
 	type MinterReward = MinterReward;
 	type MultiCurrency = Currencies;
+	type PalletId = StakingPalletId;
+	type EraLength = VtokenMintDuration;
+	type ControlOrigin =
+		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type RelayChainToken = RelayCurrencyId;
+	type RelayNetwork = RelayNetwork;
+	type BifrostXcmExecutor = BifrostXcmAdaptor<XcmRouter, XcmWeight, WeightToFee, SelfParaId>;
+	type XcmTransfer = XTokens;
+	type StakingDerivativeIndex = StakingDerivativeIndex;
+	type StakingSubAccountLocation = StakingSubAccountLocation;
+	type BaseXcmWeight = XcmWeight;
+	type StakingCallWeight = StakingCallWeight;
+	type SelfParaId = SelfParaId;
+	type MaxStakingRewardsPerEra = MaxStakingRewardsPerEra;
+	type MaxRateHistory = MaxRateHistory;
+	type BlocksPerYear = BlocksPerYear;
+	type CancelRedeemFee = CancelRedeemFee;
+	type InstantRedeemFee = InstantRedeemFee;
+	type MaxRedeemUnlocksPerBlock = MaxRedeemUnlocksPerBlock;
 	type WeightInfo = weights::bifrost_vtoken_mint::WeightInfo<Runtime>;
 }
 
-// Aggregate name getter to get fee names if the call needs to pay extra fees.
-// If any call need to pay extra fees, it should be added as an item here.
-// Used together with AggregateExtraFeeFilter below.
-pub struct FeeNameGetter;
-impl NameGetter<Call> for FeeNameGetter {
-	fn get_name(c: &Call) -> ExtraFeeName {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => ExtraFeeName::SalpContribute,
-			_ => ExtraFeeName::NoExtraFee,
-		}
-	}
-}
-
-// Aggregate filter to filter if the call needs to pay extra fees
-// If any call need to pay extra fees, it should be added as an item here.
-pub struct AggregateExtraFeeFilter;
-impl Contains<Call> for AggregateExtraFeeFilter {
-	fn contains(c: &Call) -> bool {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => true,
-			_ => false,
-		}
-	}
-}
-
-pub struct ContributeFeeFilter;
-impl Contains<Call> for ContributeFeeFilter {
-	fn contains(c: &Call) -> bool {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => true,
-			_ => false,
-		}
-	}
-}
-
 parameter_types! {
-	pub const AltFeeCurrencyExchangeRate: (u32, u32) = (1, 100);
+	pub const FeeSwapSlippage: Perbill = Perbill::from_percent(5);
+	pub const MaxCallNameLength: u32 = 32;
+	pub const MaxSponsoredCalls: u32 = 50;
+	pub const MaxSponsoredUsers: u32 = 1_000;
 	pub SalpWeightHolder: XcmBaseWeight = XcmBaseWeight::from(4 * XCM_WEIGHT) + ContributionWeight::get() + u64::pow(2, 24).into();
+	// The extra fees charged from genesis, which used to be hard-coded in the runtime.
+	pub DefaultExtraFees: Vec<(Vec<u8>, Vec<u8>, ExtraFeeInfo<CurrencyId, Balance, AccountId>)> = vec![(
+		b"Salp".to_vec(),
+		b"contribute".to_vec(),
+		ExtraFeeInfo {
+			currency_id: RelayCurrencyId::get(),
+			amount: ExtraFeeAmount::Weight(SalpWeightHolder::get().into()),
+			receiver: None,
+		},
+	)];
 }
 
 impl bifrost_flexible_fee::Config for Runtime {
 	type Currency = Balances;
 	type DexOperator = ZenlinkProtocol;
-	// type FeeDealer = FlexibleFee;
-	type FeeDealer = FixedCurrencyFeeRate<Runtime>;
+	type FeeDealer = FlexibleFee;
 	type Event = Event;
 	type MultiCurrency = Currencies;
 	type TreasuryAccount = BifrostTreasuryAccount;
 	type NativeCurrencyId = NativeCurrencyId;
 	type AlternativeFeeCurrencyId = RelayCurrencyId;
-	type AltFeeCurrencyExchangeRate = AltFeeCurrencyExchangeRate;
+	type ControlOrigin =
+		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type FeeSwapSlippage = FeeSwapSlippage;
+	type MaxCallNameLength = MaxCallNameLength;
+	type MaxSponsoredCalls = MaxSponsoredCalls;
+	type MaxSponsoredUsers = MaxSponsoredUsers;
 	type OnUnbalanced = Treasury;
 	type WeightInfo = weights::bifrost_flexible_fee::WeightInfo<Runtime>;
-	type ExtraFeeMatcher = ExtraFeeMatcher<Runtime, FeeNameGetter, AggregateExtraFeeFilter>;
-	type MiscFeeHandler = MiscFeeHandler<
-		Runtime,
-		RelayCurrencyId,
-		WeightToFee,
-		SalpWeightHolder,
-		ContributeFeeFilter,
-	>;
+	type ExtraFeeWeightToFee = WeightToFee;
 }
 
 parameter_types! {
 	pub const HalvingCycle: u32 = 60;
 	pub const RewardWindow: u32 = 10;
 	pub const MaximumExtendedPeriod: u32 = 20;
+	pub const TwapWindow: u32 = 10;
 }
 
 impl bifrost_minter_reward::Config for Runtime {
@@ -1275,6 +1294,10 @@ impl bifrost_minter_reward::Config for Runtime {
 	type RewardWindow = RewardWindow;
 	type ShareWeight = Balance;
 	type StableCurrencyId = StableCurrencyId;
+	type TwapWindow = TwapWindow;
+	type NativeCurrencyId = NativeCurrencyId;
+	type ControlOrigin =
+		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
 	type WeightInfo = weights::bifrost_minter_reward::WeightInfo<Runtime>;
 }
 
@@ -1364,7 +1387,7 @@ impl bifrost_salp::Config for Runtime {
 }
 
 impl bifrost_salp_lite::Config for Runtime {
-	type BancorPool = ();
+	type BancorPool = Bancor;
 	type Event = Event;
 	type LeasePeriod = LeasePeriod;
 	type MinContribution = MinContribution;
@@ -1385,6 +1408,8 @@ impl bifrost_salp_lite::Config for Runtime {
 parameter_types! {
 	pub const InterventionPercentage: Percent = Percent::from_percent(75);
 	pub const DailyReleasePercentage: Percent = Percent::from_percent(5);
+	pub const BancorReleaseEpoch: u64 = 60 * 60;
+	pub const BancorCircuitBreakerSamples: u32 = 5;
 }
 
 impl bifrost_bancor::Config for Runtime {
@@ -1392,12 +1417,21 @@ impl bifrost_bancor::Config for Runtime {
 	type InterventionPercentage = InterventionPercentage;
 	type DailyReleasePercentage = DailyReleasePercentage;
 	type MultiCurrency = Currencies;
+	type ControlOrigin =
+		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type ReleaseEpoch = BancorReleaseEpoch;
+	type UnixTime = Timestamp;
+	type MarketPrice = bifrost_bancor::ZenlinkMarketPrice<AccountId, ZenlinkProtocol>;
+	type CircuitBreakerSamples = BancorCircuitBreakerSamples;
 	type WeightInfo = weights::bifrost_bancor::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const MaximumOrderInTrade: u32 = 5;
 	pub const MinimumSupply: Balance = 0;
+	pub const MaximumAuctionDuration: BlockNumber = 14 * DAYS;
+	pub const AntiSnipingPeriod: BlockNumber = 10 * MINUTES;
+	pub const MaxAuctionsEndPerBlock: u32 = 50;
 }
 
 impl bifrost_vsbond_auction::Config for Runtime {
@@ -1406,6 +1440,9 @@ impl bifrost_vsbond_auction::Config for Runtime {
 	type MaximumOrderInTrade = MaximumOrderInTrade;
 	type MinimumAmount = MinimumSupply;
 	type MultiCurrency = Currencies;
+	type MaximumAuctionDuration = MaximumAuctionDuration;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MaxAuctionsEndPerBlock = MaxAuctionsEndPerBlock;
 	type WeightInfo = weights::bifrost_vsbond_auction::WeightInfo<Runtime>;
 }
 
@@ -1450,11 +1487,18 @@ impl bifrost_liquidity_mining::Config<bifrost_liquidity_mining::Instance2> for R
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const IssuePeriod: BlockNumber = DAYS;
+	pub const MaxIssueProposalsPerIssuer: u32 = 20;
+}
+
 impl bifrost_token_issuer::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Currencies;
 	type ControlOrigin =
 		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type IssuePeriod = IssuePeriod;
+	type MaxIssueProposalsPerIssuer = MaxIssueProposalsPerIssuer;
 	type WeightInfo = weights::bifrost_token_issuer::WeightInfo<Runtime>;
 }
 
@@ -1464,6 +1508,7 @@ impl bifrost_lightening_redeem::Config for Runtime {
 	type ControlOrigin =
 		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
 	type PalletId = LighteningRedeemPalletId;
+	type TreasuryAccount = BifrostTreasuryAccount;
 	type WeightInfo = weights::bifrost_lightening_redeem::WeightInfo<Runtime>;
 }
 
@@ -1642,11 +1687,12 @@ construct_runtime! {
 		Currencies: orml_currencies::{Pallet, Call, Event<T>} = 72,
 		UnknownTokens: orml_unknown_tokens::{Pallet, Storage, Event} = 73,
 		OrmlXcm: orml_xcm::{Pallet, Call, Event<T>} = 74,
+		OrmlNFT: orml_nft::{Pallet, Storage} = 75,
 
 		ZenlinkProtocol: zenlink_protocol::{Pallet, Call, Storage, Event<T>} = 80,
 
 		// Bifrost modules
-		FlexibleFee: bifrost_flexible_fee::{Pallet, Call, Storage, Event<T>} = 100,
+		FlexibleFee: bifrost_flexible_fee::{Pallet, Call, Storage, Event<T>, Config<T>} = 100,
 		VtokenMint: bifrost_vtoken_mint::{Pallet, Call, Storage, Event<T>, Config<T>} = 101,
 		MinterReward: bifrost_minter_reward::{Pallet, Call, Storage, Event<T>, Config<T>} = 102,
 		Salp: bifrost_salp::{Pallet, Call, Storage, Event<T>} = 105,
@@ -1700,7 +1746,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
-	CustomOnRuntimeUpgrade,
+	(
+		CustomOnRuntimeUpgrade,
+		bifrost_flexible_fee::migration::MigrateToV2<Runtime, DefaultExtraFees>,
+	),
 >;
 
 impl_runtime_apis! {
@@ -1814,12 +1863,72 @@ impl_runtime_apis! {
 
 	impl bifrost_flexible_fee_rpc_runtime_api::FlexibleFeeRuntimeApi<Block, AccountId> for Runtime {
 		fn get_fee_token_and_amount(who: AccountId, fee: Balance) -> (CurrencyId, Balance) {
-			let rs = FlexibleFee::cal_fee_token_and_amount(&who, fee);
+			let rs = <Runtime as bifrost_flexible_fee::Config>::FeeDealer::cal_fee_token_and_amount(&who, fee);
 			match rs {
 				Ok(val) => val,
 				_ => (CurrencyId::Native(TokenSymbol::ASG), Zero::zero()),
 			}
 		}
+
+		fn query_fee_quote(
+			who: AccountId,
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+		) -> node_primitives::FeeQuote<AccountId, CurrencyId, Balance> {
+			let fee_details = TransactionPayment::query_fee_details(uxt.clone(), len);
+			let base_fee = fee_details.inclusion_fee.map_or(Zero::zero(), |fee| fee.inclusion_fee());
+			// The tip is only accessible through the encoding of `ChargeTransactionPayment`, which
+			// is typed so that a reorder of the signed extensions fails to compile.
+			let tip = uxt
+				.signature
+				.as_ref()
+				.and_then(|(_, _, (.., charge_transaction_payment))| {
+					let charge_transaction_payment: &pallet_transaction_payment::ChargeTransactionPayment<Runtime> =
+						charge_transaction_payment;
+					codec::Compact::<Balance>::decode(&mut &charge_transaction_payment.encode()[..]).ok()
+				})
+				.map_or(Zero::zero(), |tip| tip.0);
+
+			FlexibleFee::quote_fee(&who, &uxt.function, base_fee, tip)
+		}
+	}
+
+	impl bifrost_vtoken_mint_rpc_runtime_api::VtokenMintPriceApi<Block, CurrencyId, BlockNumber, Balance> for Runtime {
+		fn get_vtoken_mint_rate(asset_id: CurrencyId) -> (Balance, Balance) {
+			VtokenMint::mint_rate(asset_id)
+		}
+
+		fn get_vtoken_mint_rate_history(asset_id: CurrencyId) -> Vec<(BlockNumber, Balance, Balance)> {
+			VtokenMint::rate_history(asset_id).into_inner()
+		}
+
+		fn get_annualized_yield(asset_id: CurrencyId) -> Permill {
+			VtokenMint::annualized_yield(asset_id)
+		}
+	}
+
+	impl bifrost_lightening_redeem_rpc_runtime_api::LighteningRedeemRuntimeApi<Block, bifrost_lightening_redeem::RedeemPoolId, BlockNumber, Balance> for Runtime {
+		fn get_exchangeable_amount(pool_id: bifrost_lightening_redeem::RedeemPoolId) -> Balance {
+			LighteningRedeem::exchangeable_amount(pool_id)
+		}
+
+		fn get_next_release(pool_id: bifrost_lightening_redeem::RedeemPoolId) -> Option<(BlockNumber, Balance)> {
+			LighteningRedeem::next_release(pool_id)
+		}
+
+		fn get_amount_needed(pool_id: bifrost_lightening_redeem::RedeemPoolId, token_amount: Balance) -> Option<Balance> {
+			LighteningRedeem::amount_needed(pool_id, token_amount).ok()
+		}
+	}
+
+	impl bifrost_token_issuer_rpc_runtime_api::TokenIssuerRuntimeApi<Block, CurrencyId, AccountId> for Runtime {
+		fn get_issue_whitelist(currency_id: CurrencyId) -> Vec<AccountId> {
+			TokenIssuer::issue_whitelist(currency_id)
+		}
+
+		fn get_transfer_whitelist(currency_id: CurrencyId) -> Vec<AccountId> {
+			TokenIssuer::transfer_whitelist(currency_id)
+		}
 	}
 
 	impl bifrost_bancor_runtime_api::BancorRuntimeApi<Block, CurrencyId, Balance> for Runtime {
@@ -1854,6 +1963,10 @@ impl_runtime_apis! {
 				_ => (Zero::zero(), Zero::zero()),
 			}
 		}
+
+		fn get_token_ceiling_forecast(currency_id: CurrencyId, days: u32) -> Vec<Balance> {
+			Bancor::forecast_token_ceiling(currency_id, days).unwrap_or_default()
+		}
 	}
 
 	// zenlink runtime outer apis
@@ -71,9 +71,24 @@ impl<T: frame_system::Config> bifrost_bancor::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	// Storage: Bancor BancorReserve (r:3 w:0)
-	fn on_initialize() -> Weight {
-		(78_733_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn create_bancor_pool() -> Weight {
+		<() as bifrost_bancor::WeightInfo>::create_bancor_pool()
+	}
+	fn retire_bancor_pool() -> Weight {
+		<() as bifrost_bancor::WeightInfo>::retire_bancor_pool()
+	}
+	fn set_price_oracle() -> Weight {
+		<() as bifrost_bancor::WeightInfo>::set_price_oracle()
+	}
+	fn remove_price_oracle() -> Weight {
+		<() as bifrost_bancor::WeightInfo>::remove_price_oracle()
+	}
+	fn resume_bancor_pool() -> Weight {
+		<() as bifrost_bancor::WeightInfo>::resume_bancor_pool()
+	}
+	fn on_initialize(n: u32) -> Weight {
+		<() as bifrost_bancor::WeightInfo>::on_initialize(n)
 	}
 }
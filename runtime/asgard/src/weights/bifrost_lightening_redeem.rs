@@ -47,22 +47,6 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `bifrost_lightening_redeem`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> bifrost_lightening_redeem::WeightInfo for WeightInfo<T> {
-	// Storage: Tokens Accounts (r:2 w:2)
-	// Storage: System Account (r:1 w:1)
-	fn add_ksm_to_pool() -> Weight {
-		(154_085_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-	}
-	// Storage: LighteningRedeem PoolAmount (r:1 w:1)
-	// Storage: LighteningRedeem ExchangePriceDiscount (r:1 w:0)
-	// Storage: Tokens Accounts (r:6 w:6)
-	// Storage: System Account (r:1 w:1)
-	fn exchange_for_ksm() -> Weight {
-		(309_253_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(9 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
-	}
 	// Storage: LighteningRedeem ExchangePriceDiscount (r:1 w:1)
 	fn edit_exchange_price() -> Weight {
 		(53_436_000 as Weight)
@@ -81,9 +65,15 @@ impl<T: frame_system::Config> bifrost_lightening_redeem::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
-	// Storage: LighteningRedeem StartEndReleaseBlock (r:1 w:0)
-	fn on_initialize() -> Weight {
-		(11_427_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn create_pool() -> Weight {
+		<() as bifrost_lightening_redeem::WeightInfo>::create_pool()
+	}
+	fn add_token_to_pool() -> Weight {
+		<() as bifrost_lightening_redeem::WeightInfo>::add_token_to_pool()
+	}
+	fn exchange_for_token() -> Weight {
+		<() as bifrost_lightening_redeem::WeightInfo>::exchange_for_token()
 	}
 }
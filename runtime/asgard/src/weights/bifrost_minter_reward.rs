@@ -64,4 +64,18 @@ impl<T: frame_system::Config> bifrost_minter_reward::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn set_currency_weight() -> Weight {
+		<() as bifrost_minter_reward::WeightInfo>::set_currency_weight()
+	}
+	fn set_reward_per_block() -> Weight {
+		<() as bifrost_minter_reward::WeightInfo>::set_reward_per_block()
+	}
+	fn set_reward_currency() -> Weight {
+		<() as bifrost_minter_reward::WeightInfo>::set_reward_currency()
+	}
+	fn sample_prices(n: u32) -> Weight {
+		<() as bifrost_minter_reward::WeightInfo>::sample_prices(n)
+	}
 }
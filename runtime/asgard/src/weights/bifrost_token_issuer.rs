@@ -86,4 +86,39 @@ impl<T: frame_system::Config> bifrost_token_issuer::WeightInfo for WeightInfo<T>
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn propose_issue() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::propose_issue()
+	}
+	fn approve_issue() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::approve_issue()
+	}
+	fn cancel_issue_proposal() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::cancel_issue_proposal()
+	}
+	fn burn() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::burn()
+	}
+	fn set_issuer_cap() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::set_issuer_cap()
+	}
+	fn set_supply_cap() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::set_supply_cap()
+	}
+	fn set_two_step_issue() -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::set_two_step_issue()
+	}
+	fn batch_add_to_issue_whitelist(n: u32) -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::batch_add_to_issue_whitelist(n)
+	}
+	fn batch_remove_from_issue_whitelist(n: u32) -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::batch_remove_from_issue_whitelist(n)
+	}
+	fn batch_add_to_transfer_whitelist(n: u32) -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::batch_add_to_transfer_whitelist(n)
+	}
+	fn batch_remove_from_transfer_whitelist(n: u32) -> Weight {
+		<() as bifrost_token_issuer::WeightInfo>::batch_remove_from_transfer_whitelist(n)
+	}
 }
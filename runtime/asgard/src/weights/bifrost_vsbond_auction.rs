@@ -65,4 +65,21 @@ impl<T: frame_system::Config> bifrost_vsbond_auction::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn create_auction() -> Weight {
+		<() as bifrost_vsbond_auction::WeightInfo>::create_auction()
+	}
+	fn revoke_auction() -> Weight {
+		<() as bifrost_vsbond_auction::WeightInfo>::revoke_auction()
+	}
+	fn clinch_auction() -> Weight {
+		<() as bifrost_vsbond_auction::WeightInfo>::clinch_auction()
+	}
+	fn bid_auction() -> Weight {
+		<() as bifrost_vsbond_auction::WeightInfo>::bid_auction()
+	}
+	fn on_initialize(n: u32) -> Weight {
+		<() as bifrost_vsbond_auction::WeightInfo>::on_initialize(n)
+	}
 }
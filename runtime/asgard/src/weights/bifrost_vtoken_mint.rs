@@ -65,7 +65,33 @@ impl<T: frame_system::Config> bifrost_vtoken_mint::WeightInfo for WeightInfo<T>
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
-	fn on_initialize() -> Weight {
-		(451_000 as Weight)
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn cancel_redeem() -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::cancel_redeem()
+	}
+	fn claim_redeem() -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::claim_redeem()
+	}
+	fn transfer_redeem_receipt() -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::transfer_redeem_receipt()
+	}
+	fn withdraw_unbonded() -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::withdraw_unbonded()
+	}
+	fn nominate(n: u32) -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::nominate(n)
+	}
+	fn report_staking_rewards() -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::report_staking_rewards()
+	}
+	fn on_initialize(n: u32) -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::on_initialize(n)
+	}
+	fn settle_pending_mints(n: u32) -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::settle_pending_mints(n)
+	}
+	fn snapshot_rates(n: u32) -> Weight {
+		<() as bifrost_vtoken_mint::WeightInfo>::snapshot_rates(n)
 	}
 }
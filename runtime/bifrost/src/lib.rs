@@ -65,8 +65,8 @@ use static_assertions::const_assert;
 /// Constant values used within the runtime.
 pub mod constants;
 use bifrost_flexible_fee::{
-	fee_dealer::{FeeDealer, FixedCurrencyFeeRate},
-	misc_fees::{ExtraFeeMatcher, MiscFeeHandler, NameGetter},
+	fee_dealer::FeeDealer,
+	misc_fees::{ExtraFeeAmount, ExtraFeeInfo},
 };
 use bifrost_runtime_common::{
 	constants::parachains,
@@ -87,10 +87,9 @@ use frame_support::{
 use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
 use hex_literal::hex;
 pub use node_primitives::{
-	traits::CheckSubAccount, AccountId, Amount, Balance, BlockNumber, CurrencyId, ExtraFeeName,
-	Moment, Nonce, ParaId, ParachainDerivedProxyAccountType, ParachainTransactProxyType,
-	ParachainTransactType, PoolId, RpcContributionStatus, TokenSymbol, TransferOriginType,
-	XcmBaseWeight,
+	traits::CheckSubAccount, AccountId, Amount, Balance, BlockNumber, CurrencyId, Moment, Nonce,
+	ParaId, ParachainDerivedProxyAccountType, ParachainTransactProxyType, ParachainTransactType,
+	PoolId, RpcContributionStatus, TokenSymbol, TransferOriginType, XcmBaseWeight,
 };
 // orml imports
 use orml_currencies::BasicCurrencyAdapter;
@@ -1125,7 +1124,9 @@ impl Contains<AccountId> for DustRemovalWhitelist {
 			AccountIdConversion::<AccountId>::into_account(&BifrostSalpLiteCrowdloanId::get())
 				.eq(a) || AccountIdConversion::<AccountId>::into_account(&LighteningRedeemPalletId::get())
 			.eq(a) || LiquidityMiningPalletId::get().check_sub_account::<PoolId>(a) ||
-			LiquidityMiningDOTPalletId::get().check_sub_account::<PoolId>(a)
+			LiquidityMiningDOTPalletId::get().check_sub_account::<PoolId>(a) ||
+			LighteningRedeemPalletId::get()
+				.check_sub_account::<bifrost_lightening_redeem::RedeemPoolId>(a)
 	}
 }
 
@@ -1175,67 +1176,42 @@ impl orml_xcm::Config for Runtime {
 
 // Bifrost modules start
 
-// Aggregate name getter to get fee names if the call needs to pay extra fees.
-// If any call need to pay extra fees, it should be added as an item here.
-// Used together with AggregateExtraFeeFilter below.
-pub struct FeeNameGetter;
-impl NameGetter<Call> for FeeNameGetter {
-	fn get_name(c: &Call) -> ExtraFeeName {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => ExtraFeeName::SalpContribute,
-			_ => ExtraFeeName::NoExtraFee,
-		}
-	}
-}
-
-// Aggregate filter to filter if the call needs to pay extra fees
-// If any call need to pay extra fees, it should be added as an item here.
-pub struct AggregateExtraFeeFilter;
-impl Contains<Call> for AggregateExtraFeeFilter {
-	fn contains(c: &Call) -> bool {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => true,
-			_ => false,
-		}
-	}
-}
-
-pub struct ContributeFeeFilter;
-impl Contains<Call> for ContributeFeeFilter {
-	fn contains(c: &Call) -> bool {
-		match *c {
-			Call::Salp(bifrost_salp::Call::contribute { .. }) => true,
-			_ => false,
-		}
-	}
-}
-
 parameter_types! {
-	pub const AltFeeCurrencyExchangeRate: (u32, u32) = (1, 100);
+	pub const FeeSwapSlippage: Perbill = Perbill::from_percent(5);
+	pub const MaxCallNameLength: u32 = 32;
+	pub const MaxSponsoredCalls: u32 = 50;
+	pub const MaxSponsoredUsers: u32 = 1_000;
 	pub SalpWeightHolder: XcmBaseWeight = XcmBaseWeight::from(4 * XCM_WEIGHT) + ContributionWeight::get() + u64::pow(2, 24).into();
+	// The extra fees charged from genesis, which used to be hard-coded in the runtime.
+	pub DefaultExtraFees: Vec<(Vec<u8>, Vec<u8>, ExtraFeeInfo<CurrencyId, Balance, AccountId>)> = vec![(
+		b"Salp".to_vec(),
+		b"contribute".to_vec(),
+		ExtraFeeInfo {
+			currency_id: RelayCurrencyId::get(),
+			amount: ExtraFeeAmount::Weight(SalpWeightHolder::get().into()),
+			receiver: None,
+		},
+	)];
 }
 
 impl bifrost_flexible_fee::Config for Runtime {
 	type Currency = Balances;
 	type DexOperator = ZenlinkProtocol;
-	// type FeeDealer = FlexibleFee;
-	type FeeDealer = FixedCurrencyFeeRate<Runtime>;
+	type FeeDealer = FlexibleFee;
 	type Event = Event;
 	type MultiCurrency = Currencies;
 	type TreasuryAccount = BifrostTreasuryAccount;
 	type NativeCurrencyId = NativeCurrencyId;
 	type AlternativeFeeCurrencyId = RelayCurrencyId;
-	type AltFeeCurrencyExchangeRate = AltFeeCurrencyExchangeRate;
+	type ControlOrigin =
+		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type FeeSwapSlippage = FeeSwapSlippage;
+	type MaxCallNameLength = MaxCallNameLength;
+	type MaxSponsoredCalls = MaxSponsoredCalls;
+	type MaxSponsoredUsers = MaxSponsoredUsers;
 	type OnUnbalanced = Treasury;
-	type WeightInfo = ();
-	type ExtraFeeMatcher = ExtraFeeMatcher<Runtime, FeeNameGetter, AggregateExtraFeeFilter>;
-	type MiscFeeHandler = MiscFeeHandler<
-		Runtime,
-		RelayCurrencyId,
-		WeightToFee,
-		SalpWeightHolder,
-		ContributeFeeFilter,
-	>;
+	type WeightInfo = weights::bifrost_flexible_fee::WeightInfo<Runtime>;
+	type ExtraFeeWeightToFee = WeightToFee;
 }
 
 pub struct EnsureConfirmAsMultiSig;
@@ -1289,7 +1265,7 @@ parameter_types! {
 }
 
 impl bifrost_salp::Config for Runtime {
-	type BancorPool = ();
+	type BancorPool = Bancor;
 	type BifrostXcmExecutor = BifrostXcmAdaptor<XcmRouter, XcmWeight, WeightToFee, SelfParaId>;
 	type Event = Event;
 	type LeasePeriod = LeasePeriod;
@@ -1348,7 +1324,7 @@ impl EnsureOrigin<Origin> for EnsureSalpLiteConfirmAsMultiSig {
 }
 
 impl bifrost_salp_lite::Config for Runtime {
-	type BancorPool = ();
+	type BancorPool = Bancor;
 	type Event = Event;
 	type LeasePeriod = PolkaLeasePeriod;
 	type MinContribution = PolkaMinContribution;
@@ -1366,6 +1342,31 @@ impl bifrost_salp_lite::Config for Runtime {
 		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
 }
 
+parameter_types! {
+	pub const InterventionPercentage: Percent = Percent::from_percent(75);
+	pub const DailyReleasePercentage: Percent = Percent::from_percent(5);
+	pub const BancorReleaseEpoch: u64 = 60 * 60;
+	pub const BancorCircuitBreakerSamples: u32 = 5;
+	pub DefaultBancorPools: Vec<(CurrencyId, Balance)> = vec![
+		(CurrencyId::Token(TokenSymbol::DOT), 10_000 * DOLLARS),
+		(CurrencyId::Token(TokenSymbol::KSM), 1_000_000 * DOLLARS),
+	];
+}
+
+impl bifrost_bancor::Config for Runtime {
+	type Event = Event;
+	type InterventionPercentage = InterventionPercentage;
+	type DailyReleasePercentage = DailyReleasePercentage;
+	type MultiCurrency = Currencies;
+	type ControlOrigin =
+		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type ReleaseEpoch = BancorReleaseEpoch;
+	type UnixTime = Timestamp;
+	type MarketPrice = bifrost_bancor::ZenlinkMarketPrice<AccountId, ZenlinkProtocol>;
+	type CircuitBreakerSamples = BancorCircuitBreakerSamples;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const RelayChainTokenSymbolKSM: TokenSymbol = TokenSymbol::KSM;
 	pub const RelayChainTokenSymbolDOT: TokenSymbol = TokenSymbol::DOT;
@@ -1407,11 +1408,18 @@ impl bifrost_liquidity_mining::Config<bifrost_liquidity_mining::Instance2> for R
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const IssuePeriod: BlockNumber = DAYS;
+	pub const MaxIssueProposalsPerIssuer: u32 = 20;
+}
+
 impl bifrost_token_issuer::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Currencies;
 	type ControlOrigin =
 		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
+	type IssuePeriod = IssuePeriod;
+	type MaxIssueProposalsPerIssuer = MaxIssueProposalsPerIssuer;
 	type WeightInfo = ();
 }
 
@@ -1421,6 +1429,7 @@ impl bifrost_lightening_redeem::Config for Runtime {
 	type ControlOrigin =
 		EnsureOneOf<AccountId, MoreThanHalfCouncil, EnsureRootOrAllTechnicalCommittee>;
 	type PalletId = LighteningRedeemPalletId;
+	type TreasuryAccount = BifrostTreasuryAccount;
 	type WeightInfo = ();
 }
 
@@ -1600,8 +1609,9 @@ construct_runtime! {
 		ZenlinkProtocol: zenlink_protocol::{Pallet, Call, Storage, Event<T>} = 80,
 
 		// Bifrost modules
-		FlexibleFee: bifrost_flexible_fee::{Pallet, Call, Storage, Event<T>} = 100,
+		FlexibleFee: bifrost_flexible_fee::{Pallet, Call, Storage, Event<T>, Config<T>} = 100,
 		Salp: bifrost_salp::{Pallet, Call, Storage, Event<T>} = 105,
+		Bancor: bifrost_bancor::{Pallet, Call, Storage, Event<T>, Config<T>} = 106,
 		LiquidityMiningDOT: bifrost_liquidity_mining::<Instance2>::{Pallet, Call, Storage, Event<T>} = 107,
 		LiquidityMining: bifrost_liquidity_mining::<Instance1>::{Pallet, Call, Storage, Event<T>} = 108,
 		TokenIssuer: bifrost_token_issuer::{Pallet, Call, Storage, Event<T>} = 109,
@@ -1650,7 +1660,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
-	(),
+	(
+		bifrost_flexible_fee::migration::MigrateToV2<Runtime, DefaultExtraFees>,
+		bifrost_bancor::migration::CreateBancorPools<Runtime, DefaultBancorPools>,
+	),
 >;
 
 impl_runtime_apis! {
@@ -1764,12 +1777,34 @@ impl_runtime_apis! {
 
 	impl bifrost_flexible_fee_rpc_runtime_api::FlexibleFeeRuntimeApi<Block, AccountId> for Runtime {
 		fn get_fee_token_and_amount(who: AccountId, fee: Balance) -> (CurrencyId, Balance) {
-			let rs = FlexibleFee::cal_fee_token_and_amount(&who, fee);
+			let rs = <Runtime as bifrost_flexible_fee::Config>::FeeDealer::cal_fee_token_and_amount(&who, fee);
 			match rs {
 				Ok(val) => val,
 				_ => (CurrencyId::Native(TokenSymbol::BNC), Zero::zero()),
 			}
 		}
+
+		fn query_fee_quote(
+			who: AccountId,
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+		) -> node_primitives::FeeQuote<AccountId, CurrencyId, Balance> {
+			let fee_details = TransactionPayment::query_fee_details(uxt.clone(), len);
+			let base_fee = fee_details.inclusion_fee.map_or(Zero::zero(), |fee| fee.inclusion_fee());
+			// The tip is only accessible through the encoding of `ChargeTransactionPayment`, which
+			// is typed so that a reorder of the signed extensions fails to compile.
+			let tip = uxt
+				.signature
+				.as_ref()
+				.and_then(|(_, _, (.., charge_transaction_payment))| {
+					let charge_transaction_payment: &pallet_transaction_payment::ChargeTransactionPayment<Runtime> =
+						charge_transaction_payment;
+					codec::Compact::<Balance>::decode(&mut &charge_transaction_payment.encode()[..]).ok()
+				})
+				.map_or(Zero::zero(), |tip| tip.0);
+
+			FlexibleFee::quote_fee(&who, &uxt.function, base_fee, tip)
+		}
 	}
 
 	// zenlink runtime outer apis
@@ -1846,6 +1881,85 @@ impl_runtime_apis! {
 		}
 	}
 
+	// The vtoken-mint pallet isn't deployed on bifrost yet. The node requires every runtime to
+	// implement this API, but bifrost doesn't serve the vtoken-mint RPC, so these values are never
+	// exposed to clients.
+	impl bifrost_vtoken_mint_rpc_runtime_api::VtokenMintPriceApi<Block, CurrencyId, BlockNumber, Balance> for Runtime {
+		fn get_vtoken_mint_rate(_asset_id: CurrencyId) -> (Balance, Balance) {
+			(Zero::zero(), Zero::zero())
+		}
+
+		fn get_vtoken_mint_rate_history(_asset_id: CurrencyId) -> Vec<(BlockNumber, Balance, Balance)> {
+			Vec::new()
+		}
+
+		fn get_annualized_yield(_asset_id: CurrencyId) -> Permill {
+			Permill::zero()
+		}
+	}
+
+	impl bifrost_lightening_redeem_rpc_runtime_api::LighteningRedeemRuntimeApi<Block, bifrost_lightening_redeem::RedeemPoolId, BlockNumber, Balance> for Runtime {
+		fn get_exchangeable_amount(pool_id: bifrost_lightening_redeem::RedeemPoolId) -> Balance {
+			LighteningRedeem::exchangeable_amount(pool_id)
+		}
+
+		fn get_next_release(pool_id: bifrost_lightening_redeem::RedeemPoolId) -> Option<(BlockNumber, Balance)> {
+			LighteningRedeem::next_release(pool_id)
+		}
+
+		fn get_amount_needed(pool_id: bifrost_lightening_redeem::RedeemPoolId, token_amount: Balance) -> Option<Balance> {
+			LighteningRedeem::amount_needed(pool_id, token_amount).ok()
+		}
+	}
+
+	impl bifrost_token_issuer_rpc_runtime_api::TokenIssuerRuntimeApi<Block, CurrencyId, AccountId> for Runtime {
+		fn get_issue_whitelist(currency_id: CurrencyId) -> Vec<AccountId> {
+			TokenIssuer::issue_whitelist(currency_id)
+		}
+
+		fn get_transfer_whitelist(currency_id: CurrencyId) -> Vec<AccountId> {
+			TokenIssuer::transfer_whitelist(currency_id)
+		}
+	}
+
+	impl bifrost_bancor_runtime_api::BancorRuntimeApi<Block, CurrencyId, Balance> for Runtime {
+		fn get_bancor_token_amount_out(token_id: CurrencyId, vstoken_amount: Balance) -> Balance {
+			let rs = Bancor::calculate_price_for_token(token_id, vstoken_amount);
+			match rs {
+				Ok(val) => val,
+				_ => Zero::zero(),
+			}
+		}
+
+		fn get_bancor_vstoken_amount_out(token_id: CurrencyId, token_amount: Balance) -> Balance {
+			let rs = Bancor::calculate_price_for_vstoken(token_id, token_amount);
+			match rs {
+				Ok(val) => val,
+				_ => Zero::zero(),
+			}
+		}
+
+		fn get_instant_vstoken_price(currency_id: CurrencyId) -> (Balance, Balance) {
+			let rs = Bancor::get_instant_vstoken_price(currency_id);
+			match rs {
+				Ok((nominator, denominator)) => (nominator, denominator),
+				_ => (Zero::zero(), Zero::zero()),
+			}
+		}
+
+		fn get_instant_token_price(currency_id: CurrencyId) -> (Balance, Balance) {
+			let rs = Bancor::get_instant_token_price(currency_id);
+			match rs {
+				Ok((nominator, denominator)) => (nominator, denominator),
+				_ => (Zero::zero(), Zero::zero()),
+			}
+		}
+
+		fn get_token_ceiling_forecast(currency_id: CurrencyId, days: u32) -> Vec<Balance> {
+			Bancor::forecast_token_ceiling(currency_id, days).unwrap_or_default()
+		}
+	}
+
 	impl bifrost_liquidity_mining_rpc_runtime_api::LiquidityMiningRuntimeApi<Block, AccountId, PoolId> for Runtime {
 		fn get_rewards(who: AccountId, pid: PoolId, pallet_instance: u32) -> Vec<(CurrencyId, Balance)> {
 			match pallet_instance {
@@ -1869,6 +1983,7 @@ impl_runtime_apis! {
 
 			list_benchmark!(list, extra, bifrost_flexible_fee, FlexibleFee);
 			list_benchmark!(list, extra, bifrost_salp, Salp);
+			list_benchmark!(list, extra, bifrost_bancor, Bancor);
 			list_benchmark!(list, extra, bifrost_salp_lite, SalpLite);
 			list_benchmark!(list, extra, bifrost_liquidity_mining, LiquidityMining);
 			list_benchmark!(list, extra, bifrost_token_issuer, TokenIssuer);
@@ -1907,6 +2022,7 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, bifrost_flexible_fee, FlexibleFee);
 			add_benchmark!(params, batches, bifrost_salp, Salp);
+			add_benchmark!(params, batches, bifrost_bancor, Bancor);
 			add_benchmark!(params, batches, bifrost_salp_lite, SalpLite);
 			add_benchmark!(params, batches, bifrost_liquidity_mining, LiquidityMining);
 			add_benchmark!(params, batches, bifrost_token_issuer, TokenIssuer);
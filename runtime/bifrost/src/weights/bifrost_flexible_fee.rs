@@ -52,4 +52,39 @@ impl<T: frame_system::Config> bifrost_flexible_fee::WeightInfo for WeightInfo<T>
 		(12_007_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn set_fee_currency_exchange_rate() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::set_fee_currency_exchange_rate()
+	}
+	fn remove_fee_currency_exchange_rate() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::remove_fee_currency_exchange_rate()
+	}
+	fn set_extra_fee() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::set_extra_fee()
+	}
+	fn remove_extra_fee() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::remove_extra_fee()
+	}
+	fn set_sponsorship() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::set_sponsorship()
+	}
+	fn fund_sponsorship() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::fund_sponsorship()
+	}
+	fn close_sponsorship(n: u32) -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::close_sponsorship(n)
+	}
+	fn update_sponsored_calls(n: u32) -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::update_sponsored_calls(n)
+	}
+	fn update_sponsored_users(n: u32) -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::update_sponsored_users(n)
+	}
+	fn accept_sponsorship() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::accept_sponsorship()
+	}
+	fn leave_sponsorship() -> Weight {
+		<() as bifrost_flexible_fee::WeightInfo>::leave_sponsorship()
+	}
 }
@@ -47,30 +47,6 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `bifrost_lightening_redeem`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> bifrost_lightening_redeem::WeightInfo for WeightInfo<T> {
-	// Storage: Tokens Accounts (r:2 w:2)
-	// Storage: System Account (r:1 w:1)
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	fn add_ksm_to_pool() -> Weight {
-		(150_126_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
-	}
-	// Storage: LighteningRedeem PoolAmount (r:1 w:1)
-	// Storage: LighteningRedeem ExchangePriceDiscount (r:1 w:0)
-	// Storage: Tokens Accounts (r:6 w:6)
-	// Storage: System Account (r:1 w:1)
-	// Storage: System Number (r:1 w:0)
-	// Storage: System ExecutionPhase (r:1 w:0)
-	// Storage: System EventCount (r:1 w:1)
-	// Storage: System Events (r:1 w:1)
-	fn exchange_for_ksm() -> Weight {
-		(300_822_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(13 as Weight))
-			.saturating_add(T::DbWeight::get().writes(10 as Weight))
-	}
 	// Storage: LighteningRedeem ExchangePriceDiscount (r:1 w:1)
 	// Storage: System Number (r:1 w:0)
 	// Storage: System ExecutionPhase (r:1 w:0)
@@ -101,9 +77,15 @@ impl<T: frame_system::Config> bifrost_lightening_redeem::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	// Storage: LighteningRedeem StartEndReleaseBlock (r:1 w:0)
-	fn on_initialize() -> Weight {
-		(11_999_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	// Not benchmarked yet: the conservative weights of the pallet are used until the
+	// benchmark CLI is run for these extrinsics.
+	fn create_pool() -> Weight {
+		<() as bifrost_lightening_redeem::WeightInfo>::create_pool()
+	}
+	fn add_token_to_pool() -> Weight {
+		<() as bifrost_lightening_redeem::WeightInfo>::add_token_to_pool()
+	}
+	fn exchange_for_token() -> Weight {
+		<() as bifrost_lightening_redeem::WeightInfo>::exchange_for_token()
 	}
 }
@@ -17,9 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use codec::{Decode, Encode};
-use frame_support::{sp_runtime::MultiSignature, RuntimeDebug};
+use frame_support::{
+	sp_runtime::{MultiAddress, MultiSignature},
+	RuntimeDebug,
+};
 use node_primitives::ParaId;
-use sp_std::vec::Vec;
+use sp_std::{boxed::Box, vec::Vec};
+use xcm::{VersionedMultiAssets, VersionedMultiLocation};
 
 #[derive(Encode, Decode, RuntimeDebug)]
 pub enum UtilityCall<RelayChainCall> {
@@ -30,9 +34,47 @@ pub enum UtilityCall<RelayChainCall> {
 }
 
 #[derive(Encode, Decode, RuntimeDebug)]
-pub enum StakingCall {
+pub enum StakingCall<BalanceOf, AccountIdOf> {
+	#[codec(index = 0)]
+	Bond(Bond<BalanceOf, AccountIdOf>),
+	#[codec(index = 1)]
+	BondExtra(#[codec(compact)] BalanceOf),
+	#[codec(index = 2)]
+	Unbond(#[codec(compact)] BalanceOf),
 	#[codec(index = 3)]
 	WithdrawUnbonded(u32),
+	#[codec(index = 5)]
+	Nominate(Vec<MultiAddress<AccountIdOf, ()>>),
+	#[codec(index = 19)]
+	Rebond(#[codec(compact)] BalanceOf),
+}
+
+#[derive(Encode, Decode, RuntimeDebug)]
+pub enum XcmCall {
+	#[codec(index = 2)]
+	ReserveTransferAssets(
+		Box<VersionedMultiLocation>,
+		Box<VersionedMultiLocation>,
+		Box<VersionedMultiAssets>,
+		u32,
+	),
+}
+
+#[derive(PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Bond<BalanceOf, AccountIdOf> {
+	pub controller: MultiAddress<AccountIdOf, ()>,
+	#[codec(compact)]
+	pub value: BalanceOf,
+	pub payee: RewardDestination<AccountIdOf>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum RewardDestination<AccountIdOf> {
+	Staked,
+	Stash,
+	Controller,
+	Account(AccountIdOf),
+	None,
 }
 
 pub mod kusama {
@@ -45,6 +87,12 @@ pub mod kusama {
 		Crowdloan(ContributeCall<BalanceOf, AccountIdOf>),
 		#[codec(index = 30)]
 		Proxy(ProxyCall<AccountIdOf, BlockNumberOf>),
+		#[codec(index = 6)]
+		Staking(StakingCall<BalanceOf, AccountIdOf>),
+		#[codec(index = 24)]
+		Utility(Box<UtilityCall<Self>>),
+		#[codec(index = 99)]
+		XcmPallet(XcmCall),
 	}
 }
 
@@ -58,6 +106,12 @@ pub mod polkadot {
 		Crowdloan(ContributeCall<BalanceOf, AccountIdOf>),
 		#[codec(index = 29)]
 		Proxy(ProxyCall<AccountIdOf, BlockNumberOf>),
+		#[codec(index = 7)]
+		Staking(StakingCall<BalanceOf, AccountIdOf>),
+		#[codec(index = 26)]
+		Utility(Box<UtilityCall<Self>>),
+		#[codec(index = 99)]
+		XcmPallet(XcmCall),
 	}
 }
 